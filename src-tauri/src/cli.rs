@@ -1,14 +1,16 @@
 use std::env;
 use std::path::PathBuf;
 
+use crate::git;
+
 /// CLI command types
 pub enum CliCommand {
     /// Open the GUI (default)
     OpenGui,
     /// Create a snapshot with optional name
     Save(Option<String>),
-    /// Restore the last snapshot
-    Undo,
+    /// Restore the last snapshot, taking a safety snapshot first unless disabled
+    Undo { backup: bool },
     /// Show project status
     Status,
     /// Show help
@@ -30,7 +32,9 @@ pub fn parse_args() -> CliCommand {
             let name = args.get(2).cloned();
             CliCommand::Save(name)
         }
-        "undo" => CliCommand::Undo,
+        "undo" => CliCommand::Undo {
+            backup: !args.iter().any(|a| a == "--no-backup"),
+        },
         "status" => CliCommand::Status,
         "help" | "--help" | "-h" => CliCommand::Help,
         "version" | "--version" | "-v" => CliCommand::Version,
@@ -67,21 +71,20 @@ pub fn execute(command: CliCommand) -> Result<bool, String> {
 
             println!("Creating snapshot: {}", snapshot_name);
 
-            // TODO: Call actual snapshot creation
-            // For now, just create the structure
-            let lumen_dir = project_path.join(".lumen");
-            let snapshots_dir = lumen_dir.join("snapshots");
-
-            if !snapshots_dir.exists() {
-                std::fs::create_dir_all(&snapshots_dir)
-                    .map_err(|e| format!("Failed to create snapshots directory: {}", e))?;
-            }
+            // Initializes the snapshot repo on first use
+            let info = git::create_snapshot(&project_path, &snapshot_name, None)
+                .map_err(|e| format!("Failed to create snapshot: {}", e))?;
 
-            println!("✓ Snapshot '{}' created", snapshot_name);
+            println!(
+                "✓ Snapshot '{}' created ({}, {} files)",
+                info.name,
+                &info.id[..7.min(info.id.len())],
+                info.files_changed
+            );
             Ok(true)
         }
 
-        CliCommand::Undo => {
+        CliCommand::Undo { backup } => {
             let project_path = get_current_project_path()?;
 
             if !is_lumen_project(&project_path) {
@@ -89,10 +92,36 @@ pub fn execute(command: CliCommand) -> Result<bool, String> {
                 return Ok(true);
             }
 
-            println!("Restoring last snapshot...");
+            // Newest first; the initial commit is already filtered out
+            let snapshots = git::list_snapshots(&project_path)?;
+            let Some(latest) = snapshots.first() else {
+                println!("No snapshots to undo.");
+                return Ok(true);
+            };
+
+            // Keep the current state recoverable if there's anything to lose
+            if backup {
+                let has_changes = git::get_working_tree_status(&project_path)
+                    .map(|s| s.total() > 0)
+                    .unwrap_or(true);
+                if has_changes {
+                    let name = format!("before-undo-{}", chrono::Utc::now().timestamp());
+                    let safety = git::create_snapshot(&project_path, &name, None)
+                        .map_err(|e| format!("Failed to create safety snapshot: {}", e))?;
+                    println!(
+                        "Saved current state as '{}' ({})",
+                        safety.name,
+                        &safety.id[..7.min(safety.id.len())]
+                    );
+                }
+            }
+
+            println!("Restoring snapshot '{}'...", latest.name);
+
+            git::restore_snapshot(&project_path, &latest.id)
+                .map_err(|e| format!("Failed to restore snapshot: {}", e))?;
 
-            // TODO: Call actual snapshot restore
-            println!("✓ Restored to last snapshot");
+            println!("✓ Restored to snapshot '{}'", latest.name);
             Ok(true)
         }
 
@@ -161,7 +190,8 @@ USAGE:
 COMMANDS:
     (none)      Open the Lumen GUI
     save [name] Create a snapshot with optional name
-    undo        Restore the last snapshot
+    undo        Restore the last snapshot (saves current state first;
+                --no-backup to skip)
     status      Show project status
     help        Show this help message
     version     Show version information
@@ -1,3 +1,4 @@
+use crate::commands::sessions;
 use crate::db;
 use serde::{Deserialize, Serialize};
 
@@ -39,6 +40,27 @@ pub struct ProjectStats {
     pub tokens_estimate: i32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutcomeStats {
+    pub outcome: String,
+    pub session_count: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdleGap {
+    pub start: String,
+    pub end: String,
+    pub minutes: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Streak {
+    pub current: i32,
+    pub longest: i32,
+    /// Last day with a session (YYYY-MM-DD, local time), empty if there are none
+    pub last_active: String,
+}
+
 /// Get daily stats for the last N days
 #[tauri::command]
 pub fn get_daily_stats(days: i32) -> Result<Vec<DailyStats>, String> {
@@ -248,3 +270,196 @@ pub fn get_project_stats() -> Result<Vec<ProjectStats>, String> {
 
     Ok(stats)
 }
+
+/// Get the distribution of session outcomes (completed / errored / abandoned)
+#[tauri::command]
+pub fn get_outcome_stats(project_id: Option<String>) -> Result<Vec<OutcomeStats>, String> {
+    let conn = db::get_connection()?;
+
+    // Make sure stale open sessions are counted as abandoned
+    sessions::mark_abandoned_sessions(&conn)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT
+                COALESCE(outcome, CASE WHEN ended_at IS NULL THEN 'in_progress' ELSE 'unclassified' END) as outcome,
+                COUNT(*) as session_count
+             FROM sessions
+             WHERE ?1 IS NULL OR project_id = ?1
+             GROUP BY 1
+             ORDER BY session_count DESC",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let stats = stmt
+        .query_map([&project_id], |row| {
+            Ok(OutcomeStats {
+                outcome: row.get(0)?,
+                session_count: row.get(1)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query outcome stats: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(stats)
+}
+
+/// Get the idle gaps between sessions on a given day (YYYY-MM-DD, local time),
+/// longest first. Overlapping sessions count as one block of activity.
+#[tauri::command]
+pub fn get_idle_gaps(date: String) -> Result<Vec<IdleGap>, String> {
+    let conn = db::get_connection()?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT started_at, ended_at
+             FROM sessions
+             WHERE date(started_at, 'localtime') = ?1
+             ORDER BY started_at ASC",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let now = chrono::Utc::now();
+    let parse = |t: &str| {
+        chrono::DateTime::parse_from_rfc3339(t)
+            .ok()
+            .map(|t| t.with_timezone(&chrono::Utc))
+    };
+
+    let mut intervals: Vec<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)> = stmt
+        .query_map([&date], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        })
+        .map_err(|e| format!("Failed to query sessions: {}", e))?
+        .filter_map(|r| r.ok())
+        .filter_map(|(started_at, ended_at)| {
+            let start = parse(&started_at)?;
+            // Sessions still open are active until now
+            let end = ended_at.as_deref().and_then(parse).unwrap_or(now);
+            Some((start, end.max(start)))
+        })
+        .collect();
+
+    intervals.sort_by_key(|(start, _)| *start);
+
+    let mut gaps = Vec::new();
+    let mut active_until: Option<chrono::DateTime<chrono::Utc>> = None;
+
+    for (start, end) in intervals {
+        match active_until {
+            Some(until) if start > until => {
+                gaps.push(IdleGap {
+                    start: until.to_rfc3339(),
+                    end: start.to_rfc3339(),
+                    minutes: start.signed_duration_since(until).num_minutes() as i32,
+                });
+                active_until = Some(end);
+            }
+            Some(until) => active_until = Some(until.max(end)),
+            None => active_until = Some(end),
+        }
+    }
+
+    gaps.sort_by_key(|gap| std::cmp::Reverse(gap.minutes));
+
+    Ok(gaps)
+}
+
+/// Get session counts by day of week and hour of day (local time), for a heatmap.
+/// Rows are days of the week starting with Sunday, columns hours 0-23.
+#[tauri::command]
+pub fn get_hourly_stats() -> Result<Vec<Vec<i32>>, String> {
+    let conn = db::get_connection()?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT
+                CAST(strftime('%w', started_at, 'localtime') AS INTEGER) as weekday,
+                CAST(strftime('%H', started_at, 'localtime') AS INTEGER) as hour,
+                COUNT(*) as session_count
+             FROM sessions
+             WHERE started_at IS NOT NULL
+             GROUP BY weekday, hour",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let mut matrix = vec![vec![0; 24]; 7];
+
+    let counts = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, Option<i64>>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, i32>(2)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to query hourly stats: {}", e))?
+        .filter_map(|r| r.ok());
+
+    for (weekday, hour, count) in counts {
+        // Unparseable timestamps give NULL
+        if let (Some(weekday @ 0..=6), Some(hour @ 0..=23)) = (weekday, hour) {
+            matrix[weekday as usize][hour as usize] = count;
+        }
+    }
+
+    Ok(matrix)
+}
+
+/// Get the current and longest runs of consecutive days with a session (local time).
+/// The current streak is still alive if the last session was yesterday.
+#[tauri::command]
+pub fn get_streak() -> Result<Streak, String> {
+    let conn = db::get_connection()?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT DISTINCT date(started_at, 'localtime') as day
+             FROM sessions
+             WHERE day IS NOT NULL
+             ORDER BY day ASC",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let days: Vec<chrono::NaiveDate> = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to query session days: {}", e))?
+        .filter_map(|r| r.ok())
+        .filter_map(|day| chrono::NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok())
+        .collect();
+
+    let Some(&last_active) = days.last() else {
+        return Ok(Streak {
+            current: 0,
+            longest: 0,
+            last_active: String::new(),
+        });
+    };
+
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<chrono::NaiveDate> = None;
+    for &day in &days {
+        run = match previous {
+            Some(prev) if day.signed_duration_since(prev).num_days() == 1 => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(day);
+    }
+
+    // `run` ends at the last active day; it only counts if that was today or yesterday
+    let today = chrono::Local::now().date_naive();
+    let current = if today.signed_duration_since(last_active).num_days() <= 1 {
+        run
+    } else {
+        0
+    };
+
+    Ok(Streak {
+        current,
+        longest,
+        last_active: last_active.format("%Y-%m-%d").to_string(),
+    })
+}
@@ -0,0 +1,222 @@
+use crate::commands::claude_code;
+use crate::db;
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// Usage fractions at which a budget warning is raised
+const WARNING_THRESHOLD: f64 = 0.8;
+const EXCEEDED_THRESHOLD: f64 = 1.0;
+
+lazy_static::lazy_static! {
+    /// Last warning level emitted per project, so we only emit when a threshold is crossed
+    static ref LAST_WARNING_LEVEL: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatus {
+    pub project_id: String,
+    pub monthly_tokens: i64,
+    pub used_tokens: i64,
+    pub remaining_tokens: i64,
+    pub percent_used: f64,
+    /// "ok", "warning" (>= 80%) or "exceeded" (>= 100%)
+    pub level: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetWarning {
+    pub project_id: String,
+    pub project_name: String,
+    pub status: BudgetStatus,
+}
+
+/// Tokens used by a project this calendar month. Counts the real usage in the
+/// project's Claude Code transcripts, plus an estimate from session time
+/// (~15 tokens per second, same as today's stats) for each session this month
+/// that has no transcript usage.
+pub fn month_token_usage(conn: &rusqlite::Connection, project_id: &str) -> Result<i64, String> {
+    let month_start = chrono::Local::now()
+        .date_naive()
+        .with_day(1)
+        .unwrap_or_default();
+
+    let project_path: Option<String> = match conn.query_row(
+        "SELECT path FROM projects WHERE id = ?1",
+        [project_id],
+        |row| row.get(0),
+    ) {
+        Ok(path) => Some(path),
+        Err(rusqlite::Error::QueryReturnedNoRows) => None,
+        Err(e) => return Err(format!("Failed to load project: {}", e)),
+    };
+    let transcript_usage = project_path
+        .and_then(|path| claude_code::project_token_usage_since(&path, month_start))
+        .unwrap_or_default();
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT started_at, COALESCE(ended_at, strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+                COALESCE(tokens_estimate,
+                    (julianday(COALESCE(ended_at, 'now')) - julianday(started_at)) * 24 * 60 * 60 * 15)
+             FROM sessions
+             WHERE project_id = ?1
+             AND julianday(started_at) >= julianday(date('now', 'localtime', 'start of month'))",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let sessions = stmt
+        .query_map([project_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, f64>(2)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to query sessions: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to compute token usage: {}", e))?;
+
+    let mut tokens: f64 = transcript_usage.iter().map(|(_, t)| *t as f64).sum();
+    for (started_at, ended_at, estimate) in sessions {
+        let window = chrono::DateTime::parse_from_rfc3339(&started_at)
+            .ok()
+            .zip(chrono::DateTime::parse_from_rfc3339(&ended_at).ok());
+        let has_transcript = window.is_some_and(|(start, end)| {
+            transcript_usage
+                .iter()
+                .any(|(time, _)| *time >= start && *time <= end)
+        });
+        if !has_transcript {
+            tokens += estimate;
+        }
+    }
+
+    Ok(tokens as i64)
+}
+
+/// Compute the budget status for a project, if it has a budget
+pub fn budget_status(
+    conn: &rusqlite::Connection,
+    project_id: &str,
+) -> Result<Option<BudgetStatus>, String> {
+    let monthly_tokens: Option<i64> = conn
+        .query_row(
+            "SELECT monthly_tokens FROM project_budgets WHERE project_id = ?1",
+            [project_id],
+            |row| row.get(0),
+        )
+        .ok();
+
+    let Some(monthly_tokens) = monthly_tokens else {
+        return Ok(None);
+    };
+
+    let used_tokens = month_token_usage(conn, project_id)?;
+    let fraction = if monthly_tokens > 0 {
+        used_tokens as f64 / monthly_tokens as f64
+    } else {
+        0.0
+    };
+
+    let level = if fraction >= EXCEEDED_THRESHOLD {
+        "exceeded"
+    } else if fraction >= WARNING_THRESHOLD {
+        "warning"
+    } else {
+        "ok"
+    };
+
+    Ok(Some(BudgetStatus {
+        project_id: project_id.to_string(),
+        monthly_tokens,
+        used_tokens,
+        remaining_tokens: (monthly_tokens - used_tokens).max(0),
+        percent_used: fraction * 100.0,
+        level: level.to_string(),
+    }))
+}
+
+/// Check every budgeted project and emit `budget-warning` when a threshold is newly crossed
+pub fn check_budgets(app_handle: &AppHandle) -> Result<(), String> {
+    let conn = db::get_connection()?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT b.project_id, COALESCE(p.name, 'Unknown')
+             FROM project_budgets b
+             LEFT JOIN projects p ON b.project_id = p.id",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let projects: Vec<(String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to query budgets: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut last_levels = LAST_WARNING_LEVEL.lock().map_err(|e| e.to_string())?;
+
+    for (project_id, project_name) in projects {
+        let Some(status) = budget_status(&conn, &project_id)? else {
+            continue;
+        };
+
+        let previous = last_levels.insert(project_id.clone(), status.level.clone());
+        if status.level != "ok" && previous.as_deref() != Some(status.level.as_str()) {
+            let _ = app_handle.emit(
+                "budget-warning",
+                BudgetWarning {
+                    project_id,
+                    project_name,
+                    status,
+                },
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Set (or clear, with `None`) a project's monthly token budget
+#[tauri::command]
+pub fn set_project_token_budget(
+    project_id: String,
+    monthly_tokens: Option<i64>,
+) -> Result<Option<BudgetStatus>, String> {
+    let conn = db::get_connection()?;
+
+    match monthly_tokens {
+        Some(tokens) => {
+            if tokens <= 0 {
+                return Err("Budget must be greater than zero".to_string());
+            }
+            conn.execute(
+                "INSERT INTO project_budgets (project_id, monthly_tokens, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(project_id) DO UPDATE SET monthly_tokens = ?2, updated_at = ?3",
+                rusqlite::params![project_id, tokens, chrono::Utc::now().to_rfc3339()],
+            )
+            .map_err(|e| format!("Failed to set budget: {}", e))?;
+        }
+        None => {
+            conn.execute("DELETE FROM project_budgets WHERE project_id = ?1", [&project_id])
+                .map_err(|e| format!("Failed to clear budget: {}", e))?;
+        }
+    }
+
+    if let Ok(mut last_levels) = LAST_WARNING_LEVEL.lock() {
+        last_levels.remove(&project_id);
+    }
+
+    budget_status(&conn, &project_id)
+}
+
+/// Get a project's budget status (None if no budget is set)
+#[tauri::command]
+pub fn get_project_token_budget(project_id: String) -> Result<Option<BudgetStatus>, String> {
+    let conn = db::get_connection()?;
+    budget_status(&conn, &project_id)
+}
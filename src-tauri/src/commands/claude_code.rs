@@ -1,7 +1,10 @@
+use crate::commands::{projects, sessions};
+use crate::config;
+use crate::db;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Daily activity from Claude Code
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,17 +24,17 @@ pub struct DailyModelTokens {
     pub tokens_by_model: HashMap<String, u64>,
 }
 
-/// Model usage stats
+/// Model usage stats. Also reads the snake_case `usage` of transcript messages.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ModelUsage {
-    #[serde(default)]
+    #[serde(default, alias = "input_tokens")]
     pub input_tokens: u64,
-    #[serde(default)]
+    #[serde(default, alias = "output_tokens")]
     pub output_tokens: u64,
-    #[serde(default)]
+    #[serde(default, alias = "cache_read_input_tokens")]
     pub cache_read_input_tokens: u64,
-    #[serde(default)]
+    #[serde(default, alias = "cache_creation_input_tokens")]
     pub cache_creation_input_tokens: u64,
     #[serde(default)]
     pub web_search_requests: u32,
@@ -39,6 +42,25 @@ pub struct ModelUsage {
     pub cost_usd: f64,
 }
 
+impl ModelUsage {
+    /// Input, output and cache tokens together
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens
+            + self.output_tokens
+            + self.cache_read_input_tokens
+            + self.cache_creation_input_tokens
+    }
+
+    fn add(&mut self, other: &ModelUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_input_tokens += other.cache_read_input_tokens;
+        self.cache_creation_input_tokens += other.cache_creation_input_tokens;
+        self.web_search_requests += other.web_search_requests;
+        self.cost_usd += other.cost_usd;
+    }
+}
+
 /// Longest session info
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -92,11 +114,55 @@ pub struct ClaudeCodeSession {
     pub modified: String,
 }
 
-/// Get the Claude Code home directory
+/// Entries, any one of which marks a directory as a Claude Code home
+const CLAUDE_HOME_MARKERS: &[&str] =
+    &["projects", "settings.json", "stats-cache.json", ".credentials.json"];
+
+/// Get the Claude Code home directory: the configured override, or ~/.claude
 fn get_claude_home() -> Option<PathBuf> {
+    if let Some(home) = config::load_config().ok().and_then(|c| c.claude_home) {
+        return Some(PathBuf::from(home));
+    }
     dirs::home_dir().map(|h| h.join(".claude"))
 }
 
+/// Get the Claude Code home directory override, if one is set
+#[tauri::command]
+pub fn get_claude_home_override() -> Result<Option<String>, String> {
+    Ok(config::load_config()?.claude_home)
+}
+
+/// Read Claude Code data from `path` instead of ~/.claude (e.g. for a second
+/// profile), or go back to ~/.claude when `path` is None. The directory must look
+/// like a Claude Code home. Returns the normalized path.
+#[tauri::command]
+pub fn set_claude_home(path: Option<String>) -> Result<Option<String>, String> {
+    let claude_home = match path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(path) => {
+            let normalized = projects::normalize_path(path)?;
+            let dir = Path::new(&normalized);
+            if !dir.is_dir() {
+                return Err(format!("Not a directory: {}", normalized));
+            }
+            if !CLAUDE_HOME_MARKERS.iter().any(|m| dir.join(m).exists()) {
+                return Err(format!(
+                    "{} doesn't look like a Claude Code directory (expected one of: {})",
+                    normalized,
+                    CLAUDE_HOME_MARKERS.join(", ")
+                ));
+            }
+            Some(normalized)
+        }
+        None => None,
+    };
+
+    let mut config = config::load_config()?;
+    config.claude_home = claude_home;
+    config::save_config(&config)?;
+
+    Ok(config.claude_home)
+}
+
 /// Read Claude Code stats from stats-cache.json
 #[tauri::command]
 pub fn get_claude_code_stats() -> Result<ClaudeCodeStats, String> {
@@ -117,6 +183,7 @@ pub fn get_claude_code_stats() -> Result<ClaudeCodeStats, String> {
 /// Get Claude Code sessions for a specific project
 #[tauri::command]
 pub fn get_claude_code_sessions(project_path: String) -> Result<Vec<ClaudeCodeSession>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let claude_home = get_claude_home().ok_or("Could not find home directory")?;
     let projects_dir = claude_home.join("projects");
 
@@ -124,27 +191,28 @@ pub fn get_claude_code_sessions(project_path: String) -> Result<Vec<ClaudeCodeSe
         return Ok(vec![]);
     }
 
-    // Convert project path to Claude Code's directory naming convention
-    // /home/ghost/dev_projects/maximus -> -home-ghost-dev-projects-maximus
-    let dir_name = project_path.replace('/', "-");
-    let dir_name = if dir_name.starts_with('-') {
-        dir_name
-    } else {
-        format!("-{}", dir_name)
-    };
-
-    let project_dir = projects_dir.join(&dir_name);
-    if !project_dir.exists() {
+    let project_dirs = claude_project_dirs(&projects_dir, &project_path);
+    if project_dirs.is_empty() {
         return Ok(vec![]);
     }
 
     let mut sessions = Vec::new();
 
-    // Read all JSONL files in the project directory
-    if let Ok(entries) = fs::read_dir(&project_dir) {
+    // Read all JSONL files in the project's directories
+    for project_dir in &project_dirs {
+        let Ok(entries) = fs::read_dir(project_dir) else {
+            continue;
+        };
         for entry in entries.flatten() {
             let path = entry.path();
             if path.extension().map_or(false, |ext| ext == "jsonl") {
+                // Other paths can share the directory name; skip their sessions
+                if let Some(cwd) = transcript_cwd(&path) {
+                    if projects::normalize_path(&cwd).unwrap_or(cwd) != project_path {
+                        continue;
+                    }
+                }
+
                 if let Ok(metadata) = fs::metadata(&path) {
                     let file_size = metadata.len();
                     let modified = metadata
@@ -200,7 +268,68 @@ pub fn get_claude_code_sessions(project_path: String) -> Result<Vec<ClaudeCodeSe
     Ok(sessions)
 }
 
-/// Get all Claude Code projects
+/// Claude Code's directory name for a project: every character other than an
+/// ASCII letter or digit becomes `-`, so `/home/ghost/dev_projects/maximus` is
+/// stored as `-home-ghost-dev-projects-maximus`. Different paths can share a
+/// name, so it can't be decoded back into a path.
+fn encode_project_dir_name(project_path: &str) -> String {
+    project_path
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Claude Code's transcript directories for a normalized project path. Claude Code
+/// names the directory after the path it was started from, which may be a symlink
+/// to the project; when the resolved path has no directory, look for ones whose
+/// transcripts record a working directory that resolves to the project.
+fn claude_project_dirs(projects_dir: &Path, project_path: &str) -> Vec<PathBuf> {
+    let dir = projects_dir.join(encode_project_dir_name(project_path));
+    if dir.is_dir() {
+        return vec![dir];
+    }
+
+    fs::read_dir(projects_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|d| d.is_dir())
+                .filter(|d| {
+                    fs::read_dir(d)
+                        .ok()
+                        .and_then(|files| {
+                            files
+                                .flatten()
+                                .map(|f| f.path())
+                                .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+                                .find_map(|p| transcript_cwd(&p))
+                        })
+                        .is_some_and(|cwd| {
+                            projects::normalize_path(&cwd).is_ok_and(|cwd| cwd == project_path)
+                        })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The working directory recorded in a transcript, reading only as far as the
+/// first entry that has one
+fn transcript_cwd(path: &Path) -> Option<String> {
+    use std::io::BufRead;
+
+    let file = fs::File::open(path).ok()?;
+    std::io::BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(&line).ok())
+        .find_map(|entry| entry.get("cwd").and_then(|v| v.as_str()).map(|s| s.to_string()))
+}
+
+/// Get all Claude Code projects, using the working directory recorded in each
+/// project's transcripts. Project directories without a transcript that records
+/// one are left out, since the directory name can't be decoded reliably.
 #[tauri::command]
 pub fn get_claude_code_projects() -> Result<Vec<String>, String> {
     let claude_home = get_claude_home().ok_or("Could not find home directory")?;
@@ -214,21 +343,640 @@ pub fn get_claude_code_projects() -> Result<Vec<String>, String> {
 
     if let Ok(entries) = fs::read_dir(&projects_dir) {
         for entry in entries.flatten() {
-            if entry.path().is_dir() {
-                // Convert directory name back to path
-                // -home-ghost-dev-projects-maximus -> /home/ghost/dev_projects/maximus
-                let dir_name = entry.file_name().to_string_lossy().to_string();
-                let path = dir_name.replace('-', "/");
-                // Fix double slashes at start
-                let path = if path.starts_with("//") {
-                    path[1..].to_string()
-                } else {
-                    path
-                };
-                projects.push(path);
+            if !entry.path().is_dir() {
+                continue;
+            }
+            let Ok(files) = fs::read_dir(entry.path()) else {
+                continue;
+            };
+
+            let cwd = files
+                .flatten()
+                .map(|f| f.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+                .find_map(|p| transcript_cwd(&p));
+            if let Some(cwd) = cwd {
+                if !projects.contains(&cwd) {
+                    projects.push(cwd);
+                }
             }
         }
     }
 
     Ok(projects)
 }
+
+/// A file Claude touched during a session, per its transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeFileChange {
+    /// Path relative to the project
+    pub path: String,
+    /// Number of tool calls that touched the file
+    pub edit_count: u32,
+    /// Tools that touched the file (Edit, Write, Bash, ...)
+    pub tools: Vec<String>,
+}
+
+/// Read a Claude Code transcript, one JSON value per line (unparseable lines are
+/// skipped and invalid UTF-8 is replaced)
+fn read_transcript(path: &Path) -> Result<Vec<serde_json::Value>, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read transcript: {}", e))?;
+    let content = String::from_utf8_lossy(&bytes);
+
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// What a session did, read from its transcript
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptSummary {
+    /// Claude's last message of the session, or the transcript's summary entry
+    pub summary: Option<String>,
+    /// Files Claude edited or wrote, in the order they were first touched
+    pub files_touched: Vec<String>,
+    /// Minutes between the first and last timestamped entries
+    pub duration_minutes: Option<i32>,
+}
+
+/// Summarize a Claude Code transcript. Paths under `project_dir` are made
+/// relative to it; unparseable lines are skipped.
+pub fn summarize_transcript(
+    path: &Path,
+    project_dir: Option<&Path>,
+) -> Result<TranscriptSummary, String> {
+    let entries = read_transcript(path)?;
+
+    let mut last_assistant_text = None;
+    let mut summary_entry = None;
+    let mut files_touched: Vec<String> = Vec::new();
+    let mut first_timestamp = None;
+    let mut last_timestamp = None;
+
+    for entry in &entries {
+        if let Some(timestamp) = entry
+            .get("timestamp")
+            .and_then(|v| v.as_str())
+            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        {
+            first_timestamp.get_or_insert(timestamp);
+            last_timestamp = Some(timestamp);
+        }
+
+        match entry.get("type").and_then(|v| v.as_str()) {
+            Some("summary") => {
+                summary_entry = entry
+                    .get("summary")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+            }
+            Some("assistant") => {
+                let Some(content) = entry
+                    .get("message")
+                    .and_then(|m| m.get("content"))
+                    .and_then(|c| c.as_array())
+                else {
+                    continue;
+                };
+
+                let text: Vec<&str> = content
+                    .iter()
+                    .filter(|b| b.get("type").and_then(|v| v.as_str()) == Some("text"))
+                    .filter_map(|b| b.get("text").and_then(|v| v.as_str()))
+                    .map(|t| t.trim())
+                    .filter(|t| !t.is_empty())
+                    .collect();
+                if !text.is_empty() {
+                    last_assistant_text = Some(text.join("\n\n"));
+                }
+
+                for block in content {
+                    if block.get("type").and_then(|v| v.as_str()) != Some("tool_use") {
+                        continue;
+                    }
+                    let key = match block.get("name").and_then(|v| v.as_str()) {
+                        Some("Edit" | "MultiEdit" | "Write") => "file_path",
+                        Some("NotebookEdit") => "notebook_path",
+                        _ => continue,
+                    };
+                    let Some(file) = block
+                        .get("input")
+                        .and_then(|i| i.get(key))
+                        .and_then(|v| v.as_str())
+                    else {
+                        continue;
+                    };
+
+                    let file = project_dir
+                        .and_then(|dir| Path::new(file).strip_prefix(dir).ok())
+                        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+                        .unwrap_or_else(|| file.to_string());
+                    if !files_touched.contains(&file) {
+                        files_touched.push(file);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let duration_minutes = match (first_timestamp, last_timestamp) {
+        (Some(first), Some(last)) if last > first => {
+            Some(((last - first).num_seconds() as f64 / 60.0).round() as i32)
+        }
+        _ => None,
+    };
+
+    Ok(TranscriptSummary {
+        summary: last_assistant_text.or(summary_entry),
+        files_touched,
+        duration_minutes,
+    })
+}
+
+/// Commands whose non-flag arguments are files they modify
+const FILE_MODIFYING_COMMANDS: &[&str] = &["touch", "rm", "mv", "tee", "truncate"];
+
+/// Best-effort list of files a shell command writes to: redirection targets, the
+/// arguments of file-modifying commands, `cp` destinations and `sed -i` targets
+fn files_written_by_command(command: &str) -> Vec<String> {
+    let mut files = Vec::new();
+
+    for segment in command.split(['\n', ';', '|', '&']) {
+        let words: Vec<&str> = segment
+            .split_whitespace()
+            .map(|w| w.trim_matches(|c| c == '"' || c == '\''))
+            .collect();
+
+        let mut iter = words.iter().enumerate();
+        while let Some((i, word)) = iter.next() {
+            if *word == ">" || *word == ">>" {
+                if let Some((_, target)) = iter.next() {
+                    files.push(target.to_string());
+                }
+            } else if let Some(target) = word.strip_prefix(">>").or_else(|| word.strip_prefix('>')) {
+                if !target.is_empty() && !target.starts_with('&') {
+                    files.push(target.to_string());
+                }
+            } else if i > 0 && word.chars().next().is_some_and(|c| c.is_ascii_digit()) && word.contains('>') {
+                // 2>file, 2>&1
+                let target = &word[word.find('>').unwrap_or(0) + 1..];
+                if !target.is_empty() && !target.starts_with('&') {
+                    files.push(target.to_string());
+                }
+            }
+        }
+
+        // Arguments up to the first redirection
+        let args: Vec<&str> = words
+            .iter()
+            .skip(1)
+            .take_while(|w| !w.contains('>'))
+            .filter(|w| !w.starts_with('-'))
+            .copied()
+            .collect();
+        match words.first().copied() {
+            Some(cmd) if FILE_MODIFYING_COMMANDS.contains(&cmd) => {
+                files.extend(args.iter().map(|a| a.to_string()));
+            }
+            Some("cp") if args.len() >= 2 => {
+                files.extend(args.last().map(|a| a.to_string()));
+            }
+            Some("sed") if words.iter().any(|w| w.starts_with("-i")) => {
+                // The first non-flag argument is the script
+                files.extend(args.iter().skip(1).map(|a| a.to_string()));
+            }
+            _ => {}
+        }
+    }
+
+    files.retain(|f| f != "/dev/null" && !f.contains('$') && !f.contains('*'));
+    files
+}
+
+/// Resolve `.` and `..` without touching the filesystem (the file may no longer exist)
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// List the files Claude touched in a session, from the Edit/Write/Bash tool calls
+/// in its transcript, with how many calls touched each. Paths are relative to the
+/// project; files outside it are left out.
+#[tauri::command]
+pub fn claude_session_file_changes(
+    project_path: String,
+    session_file: String,
+) -> Result<Vec<ClaudeFileChange>, String> {
+    let project_path = PathBuf::from(projects::normalize_path(&project_path)?);
+    let entries = read_transcript(Path::new(&session_file))?;
+
+    let mut changes: BTreeMap<String, ClaudeFileChange> = BTreeMap::new();
+
+    for entry in &entries {
+        if entry.get("type").and_then(|v| v.as_str()) != Some("assistant") {
+            continue;
+        }
+        let Some(content) = entry
+            .get("message")
+            .and_then(|m| m.get("content"))
+            .and_then(|c| c.as_array())
+        else {
+            continue;
+        };
+        // Bash paths are relative to the directory Claude was running in
+        let cwd = entry
+            .get("cwd")
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| project_path.clone());
+
+        for block in content {
+            if block.get("type").and_then(|v| v.as_str()) != Some("tool_use") {
+                continue;
+            }
+            let tool = block.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+            let input = block.get("input");
+            let input_str = |key: &str| {
+                input
+                    .and_then(|i| i.get(key))
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+            };
+
+            let files = match tool {
+                "Edit" | "MultiEdit" | "Write" => input_str("file_path").into_iter().collect(),
+                "NotebookEdit" => input_str("notebook_path").into_iter().collect(),
+                "Bash" => input_str("command")
+                    .map(|c| files_written_by_command(&c))
+                    .unwrap_or_default(),
+                _ => continue,
+            };
+
+            let mut seen = HashSet::new();
+            for file in files {
+                let absolute = normalize_lexically(&cwd.join(&file));
+                let Ok(relative) = absolute.strip_prefix(&project_path) else {
+                    continue;
+                };
+                let relative = relative.to_string_lossy().replace('\\', "/");
+                if relative.is_empty() || !seen.insert(relative.clone()) {
+                    continue;
+                }
+
+                let change = changes.entry(relative.clone()).or_insert_with(|| ClaudeFileChange {
+                    path: relative,
+                    edit_count: 0,
+                    tools: Vec::new(),
+                });
+                change.edit_count += 1;
+                if !change.tools.iter().any(|t| t == tool) {
+                    change.tools.push(tool.to_string());
+                }
+            }
+        }
+    }
+
+    Ok(changes.into_values().collect())
+}
+
+/// List prices in USD per million input and output tokens, matched in order
+/// against the model name. Cache writes cost 1.25x input and cache reads 0.1x.
+const MODEL_PRICES: &[(&str, f64, f64)] = &[
+    // Opus 4 and 4.1 (claude-opus-4-2025..., claude-opus-4-1-...) and Claude 3 Opus
+    ("opus-4-1", 15.0, 75.0),
+    ("opus-4-2", 15.0, 75.0),
+    ("3-opus", 15.0, 75.0),
+    ("opus", 5.0, 25.0),
+    ("sonnet", 3.0, 15.0),
+    ("3-haiku", 0.25, 1.25),
+    ("3-5-haiku", 0.8, 4.0),
+    ("haiku", 1.0, 5.0),
+];
+
+/// Prices for models not in MODEL_PRICES
+const DEFAULT_MODEL_PRICES: (f64, f64) = (3.0, 15.0);
+
+/// Estimated cost of a model's usage at list prices
+fn usage_cost(model: &str, usage: &ModelUsage) -> f64 {
+    let (input, output) = MODEL_PRICES
+        .iter()
+        .find(|(name, _, _)| model.contains(name))
+        .map(|(_, input, output)| (*input, *output))
+        .unwrap_or(DEFAULT_MODEL_PRICES);
+
+    (usage.input_tokens as f64 * input
+        + usage.cache_creation_input_tokens as f64 * input * 1.25
+        + usage.cache_read_input_tokens as f64 * input * 0.1
+        + usage.output_tokens as f64 * output)
+        / 1_000_000.0
+}
+
+/// Inclusive range of local dates (YYYY-MM-DD)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DateRange {
+    pub start: String,
+    pub end: String,
+}
+
+/// Token usage for one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyTokenUsage {
+    pub date: String,
+    pub total_tokens: u64,
+    pub cost_usd: f64,
+    /// Empty when the day's usage is estimated from session time
+    pub usage_by_model: HashMap<String, ModelUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenUsageReport {
+    /// Days with any usage, oldest first
+    pub days: Vec<DailyTokenUsage>,
+    pub total_tokens: u64,
+    pub cost_usd: f64,
+    pub usage_by_model: HashMap<String, ModelUsage>,
+    /// True when no transcripts were found and the numbers are estimated from session time
+    pub estimated: bool,
+}
+
+impl TokenUsageReport {
+    fn from_days(days: Vec<DailyTokenUsage>, estimated: bool) -> Self {
+        let mut usage_by_model: HashMap<String, ModelUsage> = HashMap::new();
+        for day in &days {
+            for (model, usage) in &day.usage_by_model {
+                usage_by_model.entry(model.clone()).or_default().add(usage);
+            }
+        }
+
+        TokenUsageReport {
+            total_tokens: days.iter().map(|d| d.total_tokens).sum(),
+            cost_usd: days.iter().map(|d| d.cost_usd).sum(),
+            usage_by_model,
+            days,
+            estimated,
+        }
+    }
+}
+
+/// Parse a YYYY-MM-DD date
+fn parse_date(date: &str) -> Result<chrono::NaiveDate, String> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date {}: {}", date, e))
+}
+
+/// The `usage` of each assistant message in transcripts, with its time and model,
+/// within an optional local date range. Claude Code logs a message once per content
+/// block, so each message id is counted once. Costs are filled in per model.
+fn transcript_usage_entries(
+    transcripts: &[PathBuf],
+    range: Option<(chrono::NaiveDate, chrono::NaiveDate)>,
+) -> Vec<(chrono::DateTime<chrono::Local>, String, ModelUsage)> {
+    let mut entries_with_usage = Vec::new();
+    let mut seen = HashSet::new();
+
+    for path in transcripts {
+        // A transcript last written before the range can't contain any of it
+        if let Some((start, _)) = range {
+            let modified = fs::metadata(path)
+                .and_then(|m| m.modified())
+                .map(|t| chrono::DateTime::<chrono::Local>::from(t).date_naive());
+            if modified.is_ok_and(|m| m < start) {
+                continue;
+            }
+        }
+
+        let Ok(entries) = read_transcript(path) else {
+            continue;
+        };
+        for entry in entries {
+            if entry.get("type").and_then(|v| v.as_str()) != Some("assistant") {
+                continue;
+            }
+            let Some(message) = entry.get("message") else {
+                continue;
+            };
+            let Some(usage) = message
+                .get("usage")
+                .and_then(|u| serde_json::from_value::<ModelUsage>(u.clone()).ok())
+            else {
+                continue;
+            };
+            let model = message.get("model").and_then(|v| v.as_str()).unwrap_or("unknown");
+            if model == "<synthetic>" {
+                continue;
+            }
+            let Some(time) = entry
+                .get("timestamp")
+                .and_then(|v| v.as_str())
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&chrono::Local))
+            else {
+                continue;
+            };
+            let date = time.date_naive();
+            if range.is_some_and(|(start, end)| date < start || date > end) {
+                continue;
+            }
+            if let Some(id) = message.get("id").and_then(|v| v.as_str()) {
+                if !seen.insert(id.to_string()) {
+                    continue;
+                }
+            }
+
+            let usage = ModelUsage {
+                cost_usd: usage_cost(model, &usage),
+                ..usage
+            };
+            entries_with_usage.push((time, model.to_string(), usage));
+        }
+    }
+
+    entries_with_usage
+}
+
+/// Sum the `usage` of assistant messages in transcripts per local day and model,
+/// within an optional date range. None if there are no transcripts.
+fn transcript_token_usage(
+    transcripts: &[PathBuf],
+    range: Option<(chrono::NaiveDate, chrono::NaiveDate)>,
+) -> Option<Vec<DailyTokenUsage>> {
+    if transcripts.is_empty() {
+        return None;
+    }
+
+    let mut by_day: BTreeMap<chrono::NaiveDate, HashMap<String, ModelUsage>> = BTreeMap::new();
+    for (time, model, usage) in transcript_usage_entries(transcripts, range) {
+        by_day
+            .entry(time.date_naive())
+            .or_default()
+            .entry(model)
+            .or_default()
+            .add(&usage);
+    }
+
+    Some(
+        by_day
+            .into_iter()
+            .map(|(date, usage_by_model)| DailyTokenUsage {
+                date: date.format("%Y-%m-%d").to_string(),
+                total_tokens: usage_by_model.values().map(|u| u.total_tokens()).sum(),
+                cost_usd: usage_by_model.values().map(|u| u.cost_usd).sum(),
+                usage_by_model,
+            })
+            .collect(),
+    )
+}
+
+/// Tokens used by each message in a project's transcripts since a local date, with
+/// the message's time. None if the project has no transcripts.
+pub fn project_token_usage_since(
+    project_path: &str,
+    since: chrono::NaiveDate,
+) -> Option<Vec<(chrono::DateTime<chrono::Local>, u64)>> {
+    let transcripts = transcript_files(Some(project_path));
+    if transcripts.is_empty() {
+        return None;
+    }
+
+    let today = chrono::Local::now().date_naive();
+    Some(
+        transcript_usage_entries(&transcripts, Some((since, today)))
+            .into_iter()
+            .map(|(time, _, usage)| (time, usage.total_tokens()))
+            .collect(),
+    )
+}
+
+/// Transcript files for a project, or for every project if `project_path` is None
+fn transcript_files(project_path: Option<&str>) -> Vec<PathBuf> {
+    let Some(projects_dir) = get_claude_home().map(|h| h.join("projects")) else {
+        return Vec::new();
+    };
+
+    let dirs: Vec<PathBuf> = match project_path {
+        Some(path) => claude_project_dirs(&projects_dir, path),
+        None => fs::read_dir(&projects_dir)
+            .map(|entries| entries.flatten().map(|e| e.path()).collect())
+            .unwrap_or_default(),
+    };
+
+    dirs.iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+        .filter(|p| match (project_path, transcript_cwd(p)) {
+            // Other paths can share the directory name
+            (Some(project), Some(cwd)) => projects::normalize_path(&cwd).unwrap_or(cwd) == project,
+            _ => true,
+        })
+        .collect()
+}
+
+/// Token usage and cost across all projects for a local date, from transcripts.
+/// None if there are no transcripts to read.
+pub fn token_usage_for_date(date: chrono::NaiveDate) -> Option<TokenUsageReport> {
+    transcript_token_usage(&transcript_files(None), Some((date, date)))
+        .map(|days| TokenUsageReport::from_days(days, false))
+}
+
+/// Get a project's token usage and cost per day from its Claude Code transcripts,
+/// optionally limited to a date range. Without any transcripts, falls back to
+/// estimating from the project's session time.
+#[tauri::command]
+pub fn get_real_token_usage(
+    project_path: String,
+    date_range: Option<DateRange>,
+) -> Result<TokenUsageReport, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let range = match &date_range {
+        Some(r) => Some((parse_date(&r.start)?, parse_date(&r.end)?)),
+        None => None,
+    };
+
+    if let Some(days) = transcript_token_usage(&transcript_files(Some(&project_path)), range) {
+        return Ok(TokenUsageReport::from_days(days, false));
+    }
+
+    let conn = db::get_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT date(s.started_at, 'localtime') AS day,
+                    SUM((julianday(COALESCE(s.ended_at, 'now')) - julianday(s.started_at)) * 24 * 60)
+             FROM sessions s
+             JOIN projects p ON p.id = s.project_id
+             WHERE p.path = ?1
+             GROUP BY day
+             ORDER BY day",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let days = stmt
+        .query_map([&project_path], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+        })
+        .map_err(|e| format!("Failed to query sessions: {}", e))?
+        .filter_map(|r| r.ok())
+        .filter(|(day, _)| match (range, parse_date(day)) {
+            (Some((start, end)), Ok(date)) => date >= start && date <= end,
+            _ => true,
+        })
+        .map(|(date, minutes)| {
+            let (total_tokens, cost_usd) = sessions::estimate_token_usage(minutes);
+            DailyTokenUsage {
+                date,
+                total_tokens,
+                cost_usd,
+                usage_by_model: HashMap::new(),
+            }
+        })
+        .collect();
+
+    Ok(TokenUsageReport::from_days(days, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_transcript() {
+        let dir =
+            std::env::temp_dir().join(format!("lumen-transcript-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+
+        let lines = [
+            r#"{"type":"user","timestamp":"2024-05-01T10:00:00Z","message":{"content":"fix the bug"}}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Edit","input":{"file_path":"/work/app/src/main.rs"}}]}}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"tool_u"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Write","input":{"file_path":"/tmp/notes.md"}},{"type":"tool_use","name":"Read","input":{"file_path":"/work/app/README.md"}}]}}"#,
+            r#"{"type":"assistant","timestamp":"2024-05-01T10:42:20Z","message":{"content":[{"type":"tool_use","name":"Edit","input":{"file_path":"/work/app/src/main.rs"}},{"type":"text","text":" Fixed it. "}]}}"#,
+        ];
+        let mut content = lines.join("\n").into_bytes();
+        // Invalid UTF-8 is replaced rather than failing the whole read
+        content.extend_from_slice(b"\n{\"type\":\"summary\",\"summary\":\"\xff\"}\n");
+        fs::write(&path, content).unwrap();
+
+        let summary = summarize_transcript(&path, Some(Path::new("/work/app"))).unwrap();
+        assert_eq!(summary.summary.as_deref(), Some("Fixed it."));
+        assert_eq!(summary.files_touched, vec!["src/main.rs", "/tmp/notes.md"]);
+        assert_eq!(summary.duration_minutes, Some(42));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
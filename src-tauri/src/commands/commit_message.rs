@@ -0,0 +1,206 @@
+/// Longest subject line `build_commit_message` accepts
+const MAX_SUBJECT_LEN: usize = 72;
+
+/// Check a type or scope: a single word of letters, digits, `-`, `_`, `/` or `.`
+fn check_token(kind: &str, value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        return Err(format!("Commit {} cannot be empty", kind));
+    }
+    if !value
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/' | '.'))
+    {
+        return Err(format!(
+            "Commit {} can only contain letters, digits, '-', '_', '/' and '.': {}",
+            kind, value
+        ));
+    }
+    Ok(())
+}
+
+/// Check a commit subject: one line, at most 72 characters, no trailing period
+fn check_subject(subject: &str) -> Result<(), String> {
+    if subject.is_empty() {
+        return Err("Commit subject cannot be empty".to_string());
+    }
+    if subject.contains('\n') {
+        return Err("Commit subject must be a single line".to_string());
+    }
+    let len = subject.chars().count();
+    if len > MAX_SUBJECT_LEN {
+        return Err(format!(
+            "Commit subject is {} characters; keep it to {} or fewer",
+            len, MAX_SUBJECT_LEN
+        ));
+    }
+    if subject.ends_with('.') {
+        return Err("Commit subject should not end with a period".to_string());
+    }
+    Ok(())
+}
+
+/// Format a Conventional Commits message: `type(scope): subject`, then the body
+/// and a `BREAKING CHANGE:` footer as separate paragraphs. A `breaking` change with
+/// a `breaking_description` gets the footer; without one the header is marked with `!`.
+/// The description is ignored unless `breaking` is set.
+#[tauri::command]
+pub fn build_commit_message(
+    commit_type: String,
+    scope: Option<String>,
+    subject: String,
+    body: Option<String>,
+    breaking: bool,
+    breaking_description: Option<String>,
+) -> Result<String, String> {
+    let commit_type = commit_type.trim();
+    check_token("type", commit_type)?;
+
+    let scope = scope.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    if let Some(scope) = scope {
+        check_token("scope", scope)?;
+    }
+
+    let subject = subject.trim();
+    check_subject(subject)?;
+
+    let breaking_description = breaking_description
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| breaking && !d.is_empty());
+    let mut message = commit_type.to_string();
+    if let Some(scope) = scope {
+        message.push_str(&format!("({})", scope));
+    }
+    if breaking && breaking_description.is_none() {
+        message.push('!');
+    }
+    message.push_str(": ");
+    message.push_str(subject);
+
+    if let Some(body) = body.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+        message.push_str("\n\n");
+        message.push_str(body);
+    }
+
+    if let Some(description) = breaking_description {
+        message.push_str("\n\nBREAKING CHANGE: ");
+        message.push_str(description);
+    }
+
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(
+        commit_type: &str,
+        scope: Option<&str>,
+        subject: &str,
+        body: Option<&str>,
+        breaking: bool,
+        breaking_description: Option<&str>,
+    ) -> Result<String, String> {
+        build_commit_message(
+            commit_type.to_string(),
+            scope.map(String::from),
+            subject.to_string(),
+            body.map(String::from),
+            breaking,
+            breaking_description.map(String::from),
+        )
+    }
+
+    #[test]
+    fn test_spec_examples() {
+        assert_eq!(
+            build("docs", None, "correct spelling of CHANGELOG", None, false, None).unwrap(),
+            "docs: correct spelling of CHANGELOG"
+        );
+        assert_eq!(
+            build("feat", Some("lang"), "add Polish language", None, false, None).unwrap(),
+            "feat(lang): add Polish language"
+        );
+        assert_eq!(
+            build("feat", None, "send an email to the customer when a product is shipped", None, true, None)
+                .unwrap(),
+            "feat!: send an email to the customer when a product is shipped"
+        );
+        assert_eq!(
+            build("feat", Some("api"), "send an email to the customer when a product is shipped", None, true, None)
+                .unwrap(),
+            "feat(api)!: send an email to the customer when a product is shipped"
+        );
+        assert_eq!(
+            build(
+                "feat",
+                None,
+                "allow provided config object to extend other configs",
+                None,
+                true,
+                Some("`extends` key in config file is now used for extending other config files"),
+            )
+            .unwrap(),
+            "feat: allow provided config object to extend other configs\n\n\
+             BREAKING CHANGE: `extends` key in config file is now used for extending other config files"
+        );
+    }
+
+    #[test]
+    fn test_body_and_footer() {
+        let message = build(
+            "fix",
+            Some(""),
+            "prevent racing of requests",
+            Some("Introduce a request id and a reference to latest request.\n\nRemove timeouts.\n"),
+            true,
+            Some("responses from stale requests are now dropped"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            message,
+            "fix: prevent racing of requests\n\n\
+             Introduce a request id and a reference to latest request.\n\n\
+             Remove timeouts.\n\n\
+             BREAKING CHANGE: responses from stale requests are now dropped"
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_subjects() {
+        assert!(build("feat", None, "add a thing.", None, false, None).is_err());
+        assert!(build("feat", None, &"a".repeat(73), None, false, None).is_err());
+        assert!(build("feat", None, &"a".repeat(72), None, false, None).is_ok());
+        assert!(build("feat", None, "  ", None, false, None).is_err());
+        assert!(build("feat", None, "two\nlines", None, false, None).is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_type_and_scope() {
+        assert!(build("", None, "subject", None, false, None).is_err());
+        assert!(build("new feature", None, "subject", None, false, None).is_err());
+        assert!(build("feat", Some("a(b)"), "subject", None, false, None).is_err());
+        assert_eq!(
+            build(" feat ", Some(" ui "), " subject ", None, false, None).unwrap(),
+            "feat(ui): subject"
+        );
+    }
+
+    #[test]
+    fn test_breaking_description_needs_breaking() {
+        assert_eq!(
+            build("feat", None, "subject", None, false, Some("ignored")).unwrap(),
+            "feat: subject"
+        );
+        assert_eq!(
+            build("feat", None, "subject", None, true, Some("  ")).unwrap(),
+            "feat!: subject"
+        );
+        assert_eq!(
+            build("feat", Some("api/v2"), "subject", None, false, None).unwrap(),
+            "feat(api/v2): subject"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use crate::commands::github::{self, GitStatus};
+use crate::commands::projects;
+use crate::git;
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Quiet period before a burst of file events triggers a status refresh
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(500);
+
+lazy_static::lazy_static! {
+    static ref WATCHERS: Mutex<HashMap<String, Debouncer<RecommendedWatcher>>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStatusChanged {
+    pub project_path: String,
+    pub status: GitStatus,
+}
+
+/// Whether a changed path can affect `git status`.
+/// Inside .git only the index, HEAD and refs matter; elsewhere, excluded paths
+/// (build output, node_modules, ...) are ignored.
+fn is_relevant_change(path: &Path, project_path: &Path) -> bool {
+    let relative = path.strip_prefix(project_path).unwrap_or(path);
+
+    if let Ok(in_git) = relative.strip_prefix(".git") {
+        let first = in_git
+            .components()
+            .next()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .unwrap_or_default();
+        return !matches!(first.as_str(), "objects" | "logs" | "hooks" | "")
+            && !first.ends_with(".lock");
+    }
+
+    !git::should_exclude(path, project_path)
+}
+
+/// Watch a project's working tree and .git directory, emitting `git-status-changed`
+/// with a fresh status whenever something relevant changes
+#[tauri::command]
+pub fn watch_git_status(app_handle: AppHandle, project_path: String) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let root = PathBuf::from(&project_path);
+    if !root.join(".git").exists() {
+        return Err("Not a git repository".to_string());
+    }
+
+    let mut watchers = WATCHERS.lock().map_err(|e| e.to_string())?;
+    if watchers.contains_key(&project_path) {
+        return Ok(());
+    }
+
+    let watched_path = project_path.clone();
+    let watched_root = root.clone();
+    let mut debouncer = new_debouncer(DEBOUNCE_INTERVAL, move |result: DebounceEventResult| {
+        let Ok(events) = result else {
+            return;
+        };
+
+        if !events
+            .iter()
+            .any(|event| is_relevant_change(&event.path, &watched_root))
+        {
+            return;
+        }
+
+        if let Ok(status) = github::get_git_status(watched_path.clone()) {
+            let _ = app_handle.emit(
+                "git-status-changed",
+                GitStatusChanged {
+                    project_path: watched_path.clone(),
+                    status,
+                },
+            );
+        }
+    })
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    debouncer
+        .watcher()
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch project: {}", e))?;
+
+    watchers.insert(project_path, debouncer);
+
+    Ok(())
+}
+
+/// Stop watching a project's git status
+#[tauri::command]
+pub fn unwatch_git_status(project_path: String) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path).unwrap_or(project_path);
+    let mut watchers = WATCHERS.lock().map_err(|e| e.to_string())?;
+    // Dropping the debouncer stops the watcher
+    watchers.remove(&project_path);
+    Ok(())
+}
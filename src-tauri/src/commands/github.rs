@@ -1,3 +1,5 @@
+use crate::commands::projects;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::process::Command;
@@ -23,20 +25,81 @@ const SENSITIVE_PATTERNS: &[&str] = &[
     ".pypirc", // Can contain auth tokens
 ];
 
+/// Known secret formats looked for inside files: (description, pattern)
+const SECRET_PATTERNS: &[(&str, &str)] = &[
+    ("AWS access key ID", r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),
+    ("GitHub token", r"\bgh[pousr]_[A-Za-z0-9]{36,}\b"),
+    ("GitHub token", r"\bgithub_pat_[A-Za-z0-9_]{22,}\b"),
+    ("API secret key", r"\bsk-(?:ant-|proj-|live_|test_)?[A-Za-z0-9_-]{20,}"),
+    ("Slack token", r"\bxox[abprs]-[A-Za-z0-9-]{10,}"),
+    ("Private key", r"-----BEGIN (?:[A-Z0-9]+ )*PRIVATE KEY-----"),
+];
+
+/// Names that make a high-entropy value on the same line look like a credential
+const SECRET_ASSIGNMENT_HINTS: &[&str] = &["key", "secret", "token", "password", "passwd", "credential", "auth"];
+
+/// Files larger than this aren't scanned for secrets
+const SECRET_SCAN_MAX_BYTES: u64 = 1024 * 1024;
+
+lazy_static::lazy_static! {
+    static ref SECRET_REGEXES: Vec<(&'static str, Regex)> = SECRET_PATTERNS
+        .iter()
+        .map(|(name, pattern)| (*name, Regex::new(pattern).expect("invalid secret pattern")))
+        .collect();
+}
+
+/// Project-specific additions to `SENSITIVE_PATTERNS`, stored in
+/// `.lumen/sensitive_patterns.json`. Patterns use the same syntax: a file name
+/// (matched in any directory) or `*suffix`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SensitivePatterns {
+    /// Extra patterns to block
+    pub deny: Vec<String>,
+    /// Patterns that are never treated as sensitive, overriding the defaults and `deny`
+    pub allow: Vec<String>,
+}
+
+/// Get the path to a project's sensitive pattern overrides
+fn get_sensitive_patterns_path(project_path: &Path) -> std::path::PathBuf {
+    project_path.join(".lumen").join("sensitive_patterns.json")
+}
+
+/// Load a project's sensitive pattern overrides. A missing or malformed file
+/// means no overrides, so the defaults always apply.
+pub(crate) fn load_sensitive_patterns(project_path: &Path) -> SensitivePatterns {
+    fs::read_to_string(get_sensitive_patterns_path(project_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Check if a lowercased path matches a single sensitive pattern
+fn matches_sensitive_pattern(lower: &str, pattern: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    if let Some(suffix) = pattern.strip_prefix('*') {
+        // Wildcard pattern like *.pem
+        lower.ends_with(suffix)
+    } else {
+        lower == pattern || lower.ends_with(&format!("/{}", pattern))
+    }
+}
+
 /// Check if a filename matches sensitive patterns
-fn is_sensitive_file(filename: &str) -> bool {
+fn is_sensitive_file(filename: &str, overrides: &SensitivePatterns) -> bool {
     let lower = filename.to_lowercase();
 
-    for pattern in SENSITIVE_PATTERNS {
-        if pattern.starts_with('*') {
-            // Wildcard pattern like *.pem
-            let suffix = &pattern[1..];
-            if lower.ends_with(suffix) {
-                return true;
-            }
-        } else if lower == *pattern || lower.ends_with(&format!("/{}", pattern)) {
-            return true;
-        }
+    if overrides.allow.iter().any(|p| matches_sensitive_pattern(&lower, p)) {
+        return false;
+    }
+
+    if SENSITIVE_PATTERNS
+        .iter()
+        .copied()
+        .chain(overrides.deny.iter().map(String::as_str))
+        .any(|p| matches_sensitive_pattern(&lower, p))
+    {
+        return true;
     }
 
     // Also check for common secret indicators in filename
@@ -54,8 +117,9 @@ fn is_sensitive_file(filename: &str) -> bool {
 }
 
 /// Scan directory for sensitive files that would be committed
-fn find_sensitive_files(project_path: &Path) -> Vec<String> {
+pub(crate) fn find_sensitive_files(project_path: &Path) -> Vec<String> {
     let mut sensitive_files = Vec::new();
+    let overrides = load_sensitive_patterns(project_path);
 
     // Get list of files that would be staged (respecting .gitignore)
     let output = Command::new("git")
@@ -66,14 +130,14 @@ fn find_sensitive_files(project_path: &Path) -> Vec<String> {
     if let Ok(out) = output {
         let files = String::from_utf8_lossy(&out.stdout);
         for file in files.lines() {
-            if is_sensitive_file(file) {
+            if is_sensitive_file(file, &overrides) {
                 sensitive_files.push(file.to_string());
             }
         }
     }
 
     // Also check for sensitive files that might exist but aren't in .gitignore
-    fn scan_dir(dir: &Path, base: &Path, sensitive: &mut Vec<String>) {
+    fn scan_dir(dir: &Path, base: &Path, overrides: &SensitivePatterns, sensitive: &mut Vec<String>) {
         if let Ok(entries) = fs::read_dir(dir) {
             for entry in entries.flatten() {
                 let path = entry.path();
@@ -92,7 +156,7 @@ fn find_sensitive_files(project_path: &Path) -> Vec<String> {
                 if path.is_file() {
                     let relative = path.strip_prefix(base).unwrap_or(&path);
                     let rel_str = relative.to_string_lossy().to_string();
-                    if is_sensitive_file(&rel_str) {
+                    if is_sensitive_file(&rel_str, overrides) {
                         // Check if it's already in .gitignore
                         let check = Command::new("git")
                             .current_dir(base)
@@ -105,23 +169,260 @@ fn find_sensitive_files(project_path: &Path) -> Vec<String> {
                         }
                     }
                 } else if path.is_dir() {
-                    scan_dir(&path, base, sensitive);
+                    scan_dir(&path, base, overrides, sensitive);
                 }
             }
         }
     }
 
-    scan_dir(project_path, project_path, &mut sensitive_files);
+    scan_dir(project_path, project_path, &overrides, &mut sensitive_files);
 
     // Deduplicate
     sensitive_files.sort();
     sensitive_files.dedup();
 
+    let candidates = files_to_be_committed(project_path);
+    add_secret_findings(project_path, &candidates, &overrides, &mut sensitive_files);
+
     sensitive_files
 }
 
+/// Add hardcoded secrets found in `files` to `sensitive`, skipping files that are
+/// already listed as sensitive by name or explicitly allowed
+fn add_secret_findings(
+    project_path: &Path,
+    files: &[String],
+    overrides: &SensitivePatterns,
+    sensitive: &mut Vec<String>,
+) {
+    let to_scan: Vec<String> = files
+        .iter()
+        .filter(|file| !sensitive.contains(file))
+        .filter(|file| {
+            let lower = file.to_lowercase();
+            !overrides.allow.iter().any(|p| matches_sensitive_pattern(&lower, p))
+        })
+        .cloned()
+        .collect();
+
+    let secrets = find_secrets_in_files(project_path, &to_scan);
+    sensitive.extend(secrets);
+}
+
+/// Sensitive files and hardcoded secrets among the files staging `paths` would add
+fn find_sensitive_files_among(project_path: &Path, paths: &[String]) -> Vec<String> {
+    let requested: Vec<String> = paths.iter().map(|p| relative_git_path(project_path, p)).collect();
+
+    let candidates: Vec<String> = files_to_be_committed(project_path)
+        .into_iter()
+        .filter(|file| {
+            requested
+                .iter()
+                .any(|r| r.is_empty() || file == r || file.starts_with(&format!("{}/", r)))
+        })
+        .collect();
+
+    find_sensitive_candidates(project_path, &candidates)
+}
+
+/// Sensitive files and hardcoded secrets among files about to be committed
+fn find_sensitive_candidates(project_path: &Path, candidates: &[String]) -> Vec<String> {
+    let overrides = load_sensitive_patterns(project_path);
+    let mut sensitive: Vec<String> = candidates
+        .iter()
+        .filter(|file| is_sensitive_file(file, &overrides))
+        .cloned()
+        .collect();
+    add_secret_findings(project_path, candidates, &overrides, &mut sensitive);
+
+    sensitive
+}
+
+/// A path as git reports it relative to the project: no `./` prefix or trailing slash
+fn relative_git_path(project_path: &Path, path: &str) -> String {
+    let relative = Path::new(path)
+        .strip_prefix(project_path)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| path.to_string())
+        .replace('\\', "/");
+    let relative = relative.trim_start_matches("./").trim_end_matches('/');
+    if relative == "." {
+        String::new()
+    } else {
+        relative.to_string()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct SensitivePatternSettings {
+    /// Built-in patterns, always applied unless allowed
+    pub defaults: Vec<String>,
+    pub deny: Vec<String>,
+    pub allow: Vec<String>,
+}
+
+/// Get the built-in sensitive file patterns and the project's deny/allow overrides
+#[tauri::command]
+pub fn get_sensitive_patterns(project_path: String) -> Result<SensitivePatternSettings, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let overrides = load_sensitive_patterns(Path::new(&project_path));
+
+    Ok(SensitivePatternSettings {
+        defaults: SENSITIVE_PATTERNS.iter().map(|p| p.to_string()).collect(),
+        deny: overrides.deny,
+        allow: overrides.allow,
+    })
+}
+
+/// Save a project's sensitive file overrides to .lumen/sensitive_patterns.json.
+/// Blank and duplicate patterns are dropped; with none left the file is removed.
+#[tauri::command]
+pub fn set_sensitive_patterns(
+    project_path: String,
+    deny: Vec<String>,
+    allow: Vec<String>,
+) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = get_sensitive_patterns_path(Path::new(&project_path));
+
+    let clean = |patterns: Vec<String>| {
+        let mut cleaned: Vec<String> = Vec::new();
+        for pattern in patterns {
+            let pattern = pattern.trim().to_string();
+            if !pattern.is_empty() && !cleaned.contains(&pattern) {
+                cleaned.push(pattern);
+            }
+        }
+        cleaned
+    };
+    let overrides = SensitivePatterns {
+        deny: clean(deny),
+        allow: clean(allow),
+    };
+
+    if overrides.deny.is_empty() && overrides.allow.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove sensitive patterns: {}", e))?;
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create .lumen directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(&overrides)
+        .map_err(|e| format!("Failed to serialize sensitive patterns: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write sensitive patterns: {}", e))
+}
+
+/// Files `git add -A` would stage: staged, modified and untracked (not ignored)
+fn files_to_be_committed(project_path: &Path) -> Vec<String> {
+    list_git_files(
+        project_path,
+        &[
+            &["diff", "--name-only", "--relative", "-z", "--cached"],
+            &["diff", "--name-only", "--relative", "-z"],
+            &["ls-files", "-z", "--others", "--exclude-standard"],
+        ],
+    )
+}
+
+/// Changed tracked files, staged or not: what `git add -u` plus the index would commit
+fn tracked_changes(project_path: &Path) -> Vec<String> {
+    list_git_files(
+        project_path,
+        &[
+            &["diff", "--name-only", "--relative", "-z", "--cached"],
+            &["diff", "--name-only", "--relative", "-z"],
+        ],
+    )
+}
+
+/// Run each NUL-separated git file listing and merge the results
+fn list_git_files(project_path: &Path, listings: &[&[&str]]) -> Vec<String> {
+    let mut files: Vec<String> = listings
+        .iter()
+        .filter_map(|args| {
+            Command::new("git")
+                .current_dir(project_path)
+                .args(*args)
+                .output()
+                .ok()
+        })
+        .filter(|out| out.status.success())
+        .flat_map(|out| {
+            String::from_utf8_lossy(&out.stdout)
+                .split('\0')
+                .filter(|f| !f.is_empty())
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// What kind of secret a line appears to contain, if any
+fn find_secret_in_line(line: &str) -> Option<&'static str> {
+    if let Some((name, _)) = SECRET_REGEXES.iter().find(|(_, re)| re.is_match(line)) {
+        return Some(name);
+    }
+
+    // Random-looking values only count when assigned to something credential-like
+    let lower = line.to_lowercase();
+    if SECRET_ASSIGNMENT_HINTS.iter().any(|hint| lower.contains(hint))
+        && find_high_entropy_token(line).is_some()
+    {
+        return Some("High-entropy credential");
+    }
+
+    None
+}
+
+/// Scan text files for hardcoded secrets, returning "file:line (kind)" for each
+/// suspicious line. Binary files, lockfiles and files over 1 MB are skipped.
+fn find_secrets_in_files(project_path: &Path, files: &[String]) -> Vec<String> {
+    let mut findings = Vec::new();
+
+    for file in files {
+        if is_lockfile(file) {
+            continue;
+        }
+
+        let path = project_path.join(file);
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue; // Deleted
+        };
+        if !metadata.is_file() || metadata.len() > SECRET_SCAN_MAX_BYTES {
+            continue;
+        }
+
+        let Ok(bytes) = fs::read(&path) else {
+            continue;
+        };
+        if bytes.iter().take(8000).any(|&b| b == 0) {
+            continue; // Binary
+        }
+
+        let content = String::from_utf8_lossy(&bytes);
+        for (i, line) in content.lines().enumerate() {
+            if let Some(kind) = find_secret_in_line(line) {
+                findings.push(format!("{}:{} ({})", file, i + 1, kind));
+            }
+        }
+    }
+
+    findings
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct GitStatus {
     pub branch: String,
     pub ahead: i32,
@@ -158,6 +459,7 @@ pub struct PrResult {
 /// Get git status for a project
 #[tauri::command]
 pub fn get_git_status(project_path: String) -> Result<GitStatus, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let path = Path::new(&project_path);
 
     if !path.join(".git").exists() {
@@ -232,36 +534,428 @@ fn get_ahead_behind(path: &Path, branch: &str) -> (i32, i32, bool) {
     if let Ok(output) = remote_output {
         if output.status.success() {
             // Get ahead/behind
-            let ab_output = Command::new("git")
-                .current_dir(path)
-                .args(["rev-list", "--left-right", "--count", &format!("{}...{}@{{upstream}}", branch, branch)])
-                .output();
-
-            if let Ok(ab) = ab_output {
-                let counts = String::from_utf8_lossy(&ab.stdout);
-                let parts: Vec<&str> = counts.trim().split('\t').collect();
-                if parts.len() == 2 {
-                    let ahead = parts[0].parse().unwrap_or(0);
-                    let behind = parts[1].parse().unwrap_or(0);
-                    return (ahead, behind, true);
-                }
+            if let Some((ahead, behind)) =
+                count_ahead_behind(path, branch, &format!("{}@{{upstream}}", branch))
+            {
+                return (ahead, behind, true);
+            }
+        }
+    }
+
+    (0, 0, false)
+}
+
+/// Count commits on `local` not on `upstream` (ahead) and vice versa (behind)
+fn count_ahead_behind(path: &Path, local: &str, upstream: &str) -> Option<(i32, i32)> {
+    let ab = Command::new("git")
+        .current_dir(path)
+        .args(["rev-list", "--left-right", "--count", &format!("{}...{}", local, upstream)])
+        .output()
+        .ok()?;
+
+    if !ab.status.success() {
+        return None;
+    }
+
+    let counts = String::from_utf8_lossy(&ab.stdout);
+    let parts: Vec<&str> = counts.trim().split('\t').collect();
+    if parts.len() == 2 {
+        let ahead = parts[0].parse().unwrap_or(0);
+        let behind = parts[1].parse().unwrap_or(0);
+        return Some((ahead, behind));
+    }
+
+    None
+}
+
+/// Whether the repo's current branch has any commits yet
+fn has_commits(project_path: &Path) -> bool {
+    Command::new("git")
+        .current_dir(project_path)
+        .args(["rev-parse", "--verify", "-q", "HEAD"])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitInfo {
+    pub sha: String,
+    pub short_sha: String,
+    pub author: String,
+    pub date: String,
+    pub subject: String,
+}
+
+/// Get recent commits on the current branch, newest first (default 50).
+/// With `file_path`, only commits touching that file, following renames.
+#[tauri::command]
+pub fn get_git_log(
+    project_path: String,
+    limit: Option<usize>,
+    file_path: Option<String>,
+) -> Result<Vec<CommitInfo>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+
+    // A repo without commits has no history rather than an error
+    if !has_commits(path) {
+        return Ok(Vec::new());
+    }
+
+    let mut args = vec![
+        "log".to_string(),
+        format!("-n{}", limit.unwrap_or(50).clamp(1, 5000)),
+        "--no-color".to_string(),
+        "--pretty=format:%H%x09%h%x09%an%x09%aI%x09%s".to_string(),
+    ];
+    if let Some(file) = &file_path {
+        args.push("--follow".to_string());
+        args.push("--".to_string());
+        args.push(file.clone());
+    }
+
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to read git log: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.splitn(5, '\t').collect();
+            if parts.len() < 5 {
+                return None;
+            }
+            Some(CommitInfo {
+                sha: parts[0].to_string(),
+                short_sha: parts[1].to_string(),
+                author: parts[2].to_string(),
+                date: parts[3].to_string(),
+                subject: parts[4].to_string(),
+            })
+        })
+        .collect())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchStatus {
+    pub name: String,
+    pub is_current: bool,
+    pub upstream: Option<String>,
+    pub ahead: i32,
+    pub behind: i32,
+    pub last_commit_date: String,
+}
+
+/// Get upstream and ahead/behind counts for every local branch
+#[tauri::command]
+pub fn get_branches_status(project_path: String) -> Result<Vec<BranchStatus>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+
+    let output = Command::new("git")
+        .current_dir(path)
+        .args([
+            "for-each-ref",
+            "refs/heads",
+            "--sort=-committerdate",
+            "--format=%(refname:short)%09%(upstream:short)%09%(committerdate:iso-strict)%09%(HEAD)",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to list branches: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let branches = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split('\t').collect();
+            if parts.len() < 4 {
+                return None;
+            }
+
+            let name = parts[0].to_string();
+            let upstream = Some(parts[1].to_string()).filter(|u| !u.is_empty());
+
+            // Upstream may be configured but gone (deleted on remote)
+            let (ahead, behind) = upstream
+                .as_deref()
+                .and_then(|u| count_ahead_behind(path, &name, u))
+                .unwrap_or((0, 0));
+
+            Some(BranchStatus {
+                name,
+                is_current: parts[3].trim() == "*",
+                upstream,
+                ahead,
+                behind,
+                last_commit_date: parts[2].to_string(),
+            })
+        })
+        .collect();
+
+    Ok(branches)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoOperationState {
+    /// "merge", "rebase", "am", "cherry-pick", "revert" or "bisect"
+    pub operation: Option<String>,
+    pub in_progress: bool,
+    pub conflicted_files: Vec<String>,
+}
+
+/// Resolve the real git directory (handles worktrees where .git is a file)
+fn get_git_dir(path: &Path) -> Result<std::path::PathBuf, String> {
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(["rev-parse", "--git-dir"])
+        .output()
+        .map_err(|e| format!("Failed to locate git directory: {}", e))?;
+
+    if !output.status.success() {
+        return Err("Not a git repository".to_string());
+    }
+
+    let git_dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok(path.join(git_dir))
+}
+
+/// Detect an in-progress merge/rebase/cherry-pick/revert/bisect from the git directory markers
+fn detect_operation(git_dir: &Path) -> Option<&'static str> {
+    if git_dir.join("rebase-merge").exists() {
+        return Some("rebase");
+    }
+    if git_dir.join("rebase-apply").exists() {
+        if git_dir.join("rebase-apply").join("applying").exists() {
+            return Some("am");
+        }
+        return Some("rebase");
+    }
+    if git_dir.join("MERGE_HEAD").exists() {
+        return Some("merge");
+    }
+    if git_dir.join("CHERRY_PICK_HEAD").exists() {
+        return Some("cherry-pick");
+    }
+    if git_dir.join("REVERT_HEAD").exists() {
+        return Some("revert");
+    }
+    if git_dir.join("BISECT_LOG").exists() {
+        return Some("bisect");
+    }
+    None
+}
+
+/// Files with unresolved merge conflicts
+fn list_conflicted_files(path: &Path) -> Result<Vec<String>, String> {
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(["diff", "--name-only", "--diff-filter=U"])
+        .output()
+        .map_err(|e| format!("Failed to list conflicts: {}", e))?;
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Report whether the repo is mid-merge/rebase/etc. and which files are conflicted
+#[tauri::command]
+pub fn get_repo_operation_state(project_path: String) -> Result<RepoOperationState, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+    let git_dir = get_git_dir(path)?;
+
+    let operation = detect_operation(&git_dir);
+    let conflicted_files = list_conflicted_files(path)?;
+
+    Ok(RepoOperationState {
+        operation: operation.map(String::from),
+        in_progress: operation.is_some(),
+        conflicted_files,
+    })
+}
+
+/// Abort whichever merge/rebase/cherry-pick/revert/bisect is in progress
+#[tauri::command]
+pub fn git_abort_operation(project_path: String) -> Result<GitPushResult, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+    let git_dir = get_git_dir(path)?;
+
+    let Some(operation) = detect_operation(&git_dir) else {
+        return Ok(GitPushResult {
+            success: false,
+            message: "No operation in progress".to_string(),
+        });
+    };
+
+    let args: &[&str] = match operation {
+        "bisect" => &["bisect", "reset"],
+        "merge" => &["merge", "--abort"],
+        "rebase" => &["rebase", "--abort"],
+        "am" => &["am", "--abort"],
+        "cherry-pick" => &["cherry-pick", "--abort"],
+        _ => &["revert", "--abort"],
+    };
+
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to abort {}: {}", operation, e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    Ok(GitPushResult {
+        success: true,
+        message: format!("Aborted {}", operation),
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStash {
+    /// Position in the stash list; 0 is the most recent
+    pub index: usize,
+    /// e.g. "stash@{0}"
+    pub reference: String,
+    /// Branch the stash was made on
+    pub branch: Option<String>,
+    pub message: String,
+    pub date: String,
+}
+
+/// Stash uncommitted changes to tracked files, with an optional message
+#[tauri::command]
+pub fn git_stash_save(project_path: String, message: Option<String>) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path)?;
+
+    let mut args = vec!["stash".to_string(), "push".to_string()];
+    if let Some(message) = message.map(|m| m.trim().to_string()).filter(|m| !m.is_empty()) {
+        args.push("-m".to_string());
+        args.push(message);
+    }
+
+    let output = Command::new("git")
+        .current_dir(&project_path)
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to stash: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    // git exits 0 without creating a stash when there's nothing to save
+    if String::from_utf8_lossy(&output.stdout).contains("No local changes to save") {
+        return Err("No local changes to stash".to_string());
+    }
+
+    Ok(())
+}
+
+/// List stashes, most recent first
+#[tauri::command]
+pub fn git_stash_list(project_path: String) -> Result<Vec<GitStash>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+
+    let output = Command::new("git")
+        .current_dir(&project_path)
+        .args(["stash", "list", "--format=%gd%x09%cI%x09%gs"])
+        .output()
+        .map_err(|e| format!("Failed to list stashes: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let parts: Vec<&str> = line.splitn(3, '\t').collect();
+            if parts.len() < 3 {
+                return None;
             }
+
+            // "On main: message", or "WIP on main: abc1234 last commit" without a message
+            let subject = parts[2];
+            let (branch, message) = match subject
+                .strip_prefix("WIP on ")
+                .or_else(|| subject.strip_prefix("On "))
+                .and_then(|rest| rest.split_once(": "))
+            {
+                Some((branch, message)) => (Some(branch.to_string()), message.to_string()),
+                None => (None, subject.to_string()),
+            };
+
+            Some(GitStash {
+                index,
+                reference: parts[0].to_string(),
+                branch,
+                message,
+                date: parts[1].to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Apply a stash and drop it (default: the most recent). On conflicts git keeps
+/// the stash and the error lists the conflicted files.
+#[tauri::command]
+pub fn git_stash_pop(project_path: String, index: Option<usize>) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+
+    let reference = format!("stash@{{{}}}", index.unwrap_or(0));
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(["stash", "pop", &reference])
+        .output()
+        .map_err(|e| format!("Failed to pop stash: {}", e))?;
+
+    if !output.status.success() {
+        let conflicted = list_conflicted_files(path).unwrap_or_default();
+        if !conflicted.is_empty() {
+            return Err(format!(
+                "Stash applied with conflicts (it was kept in the stash list):\n- {}",
+                conflicted.join("\n- ")
+            ));
         }
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
     }
 
-    (0, 0, false)
+    Ok(())
 }
 
 /// Stage all changes
 #[tauri::command]
 pub fn git_stage_all(project_path: String) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let path = Path::new(&project_path);
 
     // SECURITY CHECK: Scan for sensitive files BEFORE staging
     let sensitive_files = find_sensitive_files(path);
     if !sensitive_files.is_empty() {
         return Err(format!(
-            "SECURITY WARNING: Cannot stage - sensitive files detected:\n- {}\n\nAdd them to .gitignore first.",
+            "SECURITY WARNING: Cannot stage - sensitive files or secrets detected:\n- {}\n\nAdd sensitive files to .gitignore and move secrets out of source files first.",
             sensitive_files.join("\n- ")
         ));
     }
@@ -279,9 +973,78 @@ pub fn git_stage_all(project_path: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Reject an empty file list before staging or unstaging
+fn check_file_list(files: &[String]) -> Result<(), String> {
+    if files.is_empty() || files.iter().any(|f| f.trim().is_empty()) {
+        return Err("No files given".to_string());
+    }
+    Ok(())
+}
+
+/// Stage specific files (or directories), after checking just those for
+/// sensitive files and secrets. Returns the updated status.
+#[tauri::command]
+pub fn git_stage_files(project_path: String, files: Vec<String>) -> Result<GitStatus, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+    check_file_list(&files)?;
+
+    // SECURITY CHECK: Scan the files being staged BEFORE staging
+    let sensitive_files = find_sensitive_files_among(path, &files);
+    if !sensitive_files.is_empty() {
+        return Err(format!(
+            "SECURITY WARNING: Cannot stage - sensitive files or secrets detected:\n- {}\n\nAdd sensitive files to .gitignore and move secrets out of source files first.",
+            sensitive_files.join("\n- ")
+        ));
+    }
+
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(["add", "-A", "--"])
+        .args(&files)
+        .output()
+        .map_err(|e| format!("Failed to stage: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    get_git_status(project_path)
+}
+
+/// Unstage specific files (or directories), keeping their changes in the
+/// working tree. Returns the updated status.
+#[tauri::command]
+pub fn git_unstage_files(project_path: String, files: Vec<String>) -> Result<GitStatus, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+    check_file_list(&files)?;
+
+    // Before the first commit there's no HEAD to reset to, so drop them from the index
+    let args: &[&str] = if has_commits(path) {
+        &["reset", "-q", "--"]
+    } else {
+        &["rm", "-r", "-q", "--cached", "--"]
+    };
+
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(args)
+        .args(&files)
+        .output()
+        .map_err(|e| format!("Failed to unstage: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    get_git_status(project_path)
+}
+
 /// Create a commit
 #[tauri::command]
 pub fn git_commit(project_path: String, message: String) -> Result<GitCommitResult, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     // First stage all
     git_stage_all(project_path.clone())?;
 
@@ -303,31 +1066,99 @@ pub fn git_commit(project_path: String, message: String) -> Result<GitCommitResu
         return Err(stderr.to_string());
     }
 
-    // Get the commit SHA
-    let sha_output = Command::new("git")
-        .current_dir(&project_path)
+    Ok(GitCommitResult {
+        success: true,
+        message: "Committed successfully".to_string(),
+        sha: head_sha(Path::new(&project_path)),
+    })
+}
+
+/// SHA of the current HEAD commit
+fn head_sha(path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(path)
         .args(["rev-parse", "HEAD"])
         .output()
-        .ok();
+        .ok()?;
 
-    let sha = sha_output.and_then(|o| {
-        if o.status.success() {
-            Some(String::from_utf8_lossy(&o.stdout).trim().to_string())
-        } else {
-            None
-        }
-    });
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        None
+    }
+}
+
+/// Amend the last commit with the current changes to tracked files, keeping its
+/// message unless `new_message` is given. Refuses if the commit has already been
+/// pushed to the upstream branch, unless `force` is set.
+#[tauri::command]
+pub fn git_commit_amend(
+    project_path: String,
+    new_message: Option<String>,
+    force: Option<bool>,
+) -> Result<GitCommitResult, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+
+    if !has_commits(path) {
+        return Err("There is no commit to amend".to_string());
+    }
+
+    // Nothing ahead of the upstream means HEAD is already on the remote
+    let (ahead, _, has_upstream) = get_ahead_behind(path, "HEAD");
+    if has_upstream && ahead == 0 && !force.unwrap_or(false) {
+        return Err(
+            "The last commit has already been pushed. Amending it rewrites published history; use force to amend anyway."
+                .to_string(),
+        );
+    }
+
+    // SECURITY CHECK: Scan the tracked changes BEFORE staging them
+    let sensitive_files = find_sensitive_candidates(path, &tracked_changes(path));
+    if !sensitive_files.is_empty() {
+        return Err(format!(
+            "SECURITY WARNING: Cannot stage - sensitive files or secrets detected:\n- {}\n\nAdd sensitive files to .gitignore and move secrets out of source files first.",
+            sensitive_files.join("\n- ")
+        ));
+    }
+
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(["add", "-u"])
+        .output()
+        .map_err(|e| format!("Failed to stage: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let mut args = vec!["commit", "--amend"];
+    match new_message.as_deref().map(str::trim).filter(|m| !m.is_empty()) {
+        Some(message) => args.extend(["-m", message]),
+        None => args.push("--no-edit"),
+    }
+
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to amend commit: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
 
     Ok(GitCommitResult {
         success: true,
-        message: "Committed successfully".to_string(),
-        sha,
+        message: "Amended the last commit".to_string(),
+        sha: head_sha(path),
     })
 }
 
 /// Push to remote
 #[tauri::command]
 pub fn git_push(project_path: String) -> Result<GitPushResult, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let output = Command::new("git")
         .current_dir(&project_path)
         .args(["push"])
@@ -374,6 +1205,7 @@ pub fn git_push(project_path: String) -> Result<GitPushResult, String> {
 /// Pull from remote
 #[tauri::command]
 pub fn git_pull(project_path: String) -> Result<GitPushResult, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let output = Command::new("git")
         .current_dir(&project_path)
         .args(["pull"])
@@ -488,6 +1320,7 @@ pub struct GitRepoInfo {
 /// Get git repository info for a project
 #[tauri::command]
 pub fn get_git_repo_info(project_path: String) -> GitRepoInfo {
+    let project_path = projects::normalize_path(&project_path).unwrap_or(project_path);
     let path = Path::new(&project_path);
 
     // Check if it's a git repo
@@ -582,6 +1415,7 @@ pub fn set_git_config(user_name: String, user_email: String) -> Result<(), Strin
 /// Initialize a git repository
 #[tauri::command]
 pub fn git_init(project_path: String, default_branch: Option<String>) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let branch = default_branch.unwrap_or_else(|| "main".to_string());
 
     let output = Command::new("git")
@@ -613,6 +1447,7 @@ pub fn create_github_repo(
     description: Option<String>,
     is_private: bool,
 ) -> Result<CreateRepoResult, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     if !check_gh_cli() {
         return Err("GitHub CLI (gh) is not installed".to_string());
     }
@@ -626,7 +1461,7 @@ pub fn create_github_repo(
             success: false,
             url: None,
             message: format!(
-                "SECURITY WARNING: The following sensitive files would be committed:\n- {}\n\nPlease add them to .gitignore first.",
+                "SECURITY WARNING: The following sensitive files or secrets would be committed:\n- {}\n\nPlease add sensitive files to .gitignore and move secrets out of source files first.",
                 sensitive_files.join("\n- ")
             ),
         });
@@ -723,6 +1558,7 @@ pub fn create_github_repo(
 /// Add a remote to an existing git repository
 #[tauri::command]
 pub fn git_add_remote(project_path: String, url: String) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let output = Command::new("git")
         .current_dir(&project_path)
         .args(["remote", "add", "origin", &url])
@@ -750,6 +1586,439 @@ pub fn git_add_remote(project_path: String, url: String) -> Result<(), String> {
     Ok(())
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitTag {
+    pub name: String,
+    pub sha: String,
+    pub date: String,
+    pub message: Option<String>,
+}
+
+/// Create a tag on HEAD (annotated if a message is given) and optionally push it
+#[tauri::command]
+pub fn git_tag(
+    project_path: String,
+    name: String,
+    message: Option<String>,
+    push: bool,
+    force: Option<bool>,
+) -> Result<GitPushResult, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let force = force.unwrap_or(false);
+
+    // Validate the tag name
+    let valid = Command::new("git")
+        .current_dir(&project_path)
+        .args(["check-ref-format", &format!("refs/tags/{}", name)])
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false);
+
+    if !valid || name.starts_with('-') {
+        return Err(format!("Invalid tag name: {}", name));
+    }
+
+    // Refuse to move an existing tag unless forced
+    let exists = Command::new("git")
+        .current_dir(&project_path)
+        .args(["rev-parse", "-q", "--verify", &format!("refs/tags/{}", name)])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+
+    if exists && !force {
+        return Err(format!("Tag '{}' already exists", name));
+    }
+
+    let mut args = vec!["tag".to_string()];
+    if force {
+        args.push("-f".to_string());
+    }
+    if let Some(msg) = message.filter(|m| !m.trim().is_empty()) {
+        args.push("-a".to_string());
+        args.push(name.clone());
+        args.push("-m".to_string());
+        args.push(msg);
+    } else {
+        args.push(name.clone());
+    }
+
+    let output = Command::new("git")
+        .current_dir(&project_path)
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to create tag: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    if !push {
+        return Ok(GitPushResult {
+            success: true,
+            message: format!("Created tag {}", name),
+        });
+    }
+
+    let mut push_args = vec!["push", "origin"];
+    if force {
+        push_args.push("--force");
+    }
+    let tag_ref = format!("refs/tags/{}", name);
+    push_args.push(&tag_ref);
+
+    let push_output = Command::new("git")
+        .current_dir(&project_path)
+        .args(&push_args)
+        .output()
+        .map_err(|e| format!("Failed to push tag: {}", e))?;
+
+    if !push_output.status.success() {
+        return Err(format!(
+            "Created tag {} but failed to push: {}",
+            name,
+            String::from_utf8_lossy(&push_output.stderr)
+        ));
+    }
+
+    Ok(GitPushResult {
+        success: true,
+        message: format!("Created and pushed tag {}", name),
+    })
+}
+
+/// List tags, newest first
+#[tauri::command]
+pub fn git_list_tags(project_path: String) -> Result<Vec<GitTag>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let output = Command::new("git")
+        .current_dir(&project_path)
+        .args([
+            "for-each-ref",
+            "refs/tags",
+            "--sort=-creatordate",
+            "--format=%(refname:short)%09%(if)%(*objectname)%(then)%(*objectname:short)%(else)%(objectname:short)%(end)%09%(creatordate:iso-strict)%09%(if)%(*objectname)%(then)%(contents:subject)%(end)",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to list tags: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let tags = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.splitn(4, '\t').collect();
+            if parts.len() < 3 {
+                return None;
+            }
+            Some(GitTag {
+                name: parts[0].to_string(),
+                sha: parts[1].to_string(),
+                date: parts[2].to_string(),
+                message: parts
+                    .get(3)
+                    .map(|m| m.trim().to_string())
+                    .filter(|m| !m.is_empty()),
+            })
+        })
+        .collect();
+
+    Ok(tags)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleBranch {
+    pub name: String,
+    pub last_commit_date: String,
+    pub days_since_commit: i64,
+    /// Fully merged into the default branch, so `git_delete_branch` can safely remove it
+    pub merged: bool,
+}
+
+/// Check whether a local branch exists
+fn local_branch_exists(path: &Path, branch: &str) -> bool {
+    Command::new("git")
+        .current_dir(path)
+        .args(["rev-parse", "-q", "--verify", &format!("refs/heads/{}", branch)])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// The repo's default branch: whatever origin/HEAD points to, else main or master
+fn default_branch(path: &Path) -> Option<String> {
+    let from_remote = Command::new("git")
+        .current_dir(path)
+        .args(["symbolic-ref", "--short", "refs/remotes/origin/HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .and_then(|r| r.strip_prefix("origin/").map(String::from))
+        .filter(|b| local_branch_exists(path, b));
+
+    from_remote.or_else(|| {
+        ["main", "master"]
+            .into_iter()
+            .find(|b| local_branch_exists(path, b))
+            .map(String::from)
+    })
+}
+
+/// List local branches with no commits in the last `days` days, oldest first.
+/// The current and default branches are never included.
+#[tauri::command]
+pub fn find_stale_branches(project_path: String, days: i64) -> Result<Vec<StaleBranch>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+
+    let default = default_branch(path).ok_or("Could not determine the default branch")?;
+
+    let merged_output = Command::new("git")
+        .current_dir(path)
+        .args(["branch", "--merged", &default, "--format=%(refname:short)"])
+        .output()
+        .map_err(|e| format!("Failed to list merged branches: {}", e))?;
+    if !merged_output.status.success() {
+        return Err(String::from_utf8_lossy(&merged_output.stderr).to_string());
+    }
+    let merged: std::collections::HashSet<String> = String::from_utf8_lossy(&merged_output.stdout)
+        .lines()
+        .map(|l| l.trim().to_string())
+        .collect();
+
+    let output = Command::new("git")
+        .current_dir(path)
+        .args([
+            "for-each-ref",
+            "refs/heads",
+            "--sort=committerdate",
+            "--format=%(refname:short)%09%(committerdate:unix)%09%(committerdate:iso-strict)%09%(HEAD)",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to list branches: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let branches = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split('\t').collect();
+            if parts.len() < 4 || parts[3].trim() == "*" || parts[0] == default {
+                return None;
+            }
+
+            let committed: i64 = parts[1].parse().ok()?;
+            let days_since_commit = (now - committed) / (24 * 60 * 60);
+            if days_since_commit < days {
+                return None;
+            }
+
+            Some(StaleBranch {
+                name: parts[0].to_string(),
+                last_commit_date: parts[2].to_string(),
+                days_since_commit,
+                merged: merged.contains(parts[0]),
+            })
+        })
+        .collect();
+
+    Ok(branches)
+}
+
+/// Delete a local branch. Without `force`, git refuses if it has unmerged commits.
+#[tauri::command]
+pub fn git_delete_branch(
+    project_path: String,
+    branch: String,
+    force: Option<bool>,
+) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path)?;
+
+    if branch.starts_with('-') || !local_branch_exists(Path::new(&project_path), &branch) {
+        return Err(format!("Branch not found: {}", branch));
+    }
+
+    let flag = if force.unwrap_or(false) { "-D" } else { "-d" };
+    let output = Command::new("git")
+        .current_dir(&project_path)
+        .args(["branch", flag, &branch])
+        .output()
+        .map_err(|e| format!("Failed to delete branch: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    Ok(())
+}
+
+/// Minimum length and entropy (bits per char) for a token to look like a secret
+const SECRET_TOKEN_MIN_LEN: usize = 20;
+const SECRET_TOKEN_MIN_ENTROPY: f64 = 4.0;
+
+/// Shannon entropy of a string in bits per character
+fn shannon_entropy(s: &str) -> f64 {
+    let mut counts = std::collections::HashMap::new();
+    for c in s.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+    }
+    let len = s.chars().count() as f64;
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Find a random-looking (high-entropy, mixed letters and digits) token in a line
+fn find_high_entropy_token(line: &str) -> Option<&str> {
+    line.split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=' | '_' | '-')))
+        .filter(|token| token.len() >= SECRET_TOKEN_MIN_LEN && token.len() <= 200)
+        .filter(|token| {
+            token.chars().any(|c| c.is_ascii_digit()) && token.chars().any(|c| c.is_ascii_alphabetic())
+        })
+        .find(|token| shannon_entropy(token) >= SECRET_TOKEN_MIN_ENTROPY)
+}
+
+/// Whether a path is a package manager lockfile
+fn is_lockfile(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.ends_with(".lock") || name.ends_with("-lock.json") || name.ends_with("-lock.yaml")
+}
+
+/// Show only the start of a suspected secret
+fn redact(secret: &str) -> String {
+    format!("{}…", secret.chars().take(4).collect::<String>())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorySecretFinding {
+    pub commit: String,
+    pub author: String,
+    pub date: String,
+    pub subject: String,
+    pub file: String,
+    /// "sensitive_file" (a sensitive-pattern file was added) or "high_entropy" (suspicious content)
+    pub kind: String,
+    /// What matched: the filename, or a redacted prefix of the suspicious string
+    pub pattern: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorySecretScan {
+    pub commits_scanned: usize,
+    pub findings: Vec<HistorySecretFinding>,
+    pub warning: Option<String>,
+}
+
+/// Scan the most recent commits for sensitive files that were added or
+/// high-entropy strings that appeared in added lines
+#[tauri::command]
+pub fn scan_git_history_for_secrets(
+    project_path: String,
+    max_commits: Option<usize>,
+) -> Result<HistorySecretScan, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    const COMMIT_MARKER: char = '\u{1}';
+    let max_commits = max_commits.unwrap_or(200).clamp(1, 5000);
+    let overrides = load_sensitive_patterns(Path::new(&project_path));
+
+    let output = Command::new("git")
+        .current_dir(&project_path)
+        .args([
+            "log",
+            &format!("-n{}", max_commits),
+            "-p",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            "--diff-filter=AM",
+            &format!("--format={}%H%x09%an%x09%aI%x09%s", COMMIT_MARKER),
+        ])
+        .output()
+        .map_err(|e| format!("Failed to read git history: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let log = String::from_utf8_lossy(&output.stdout);
+    let mut findings: Vec<HistorySecretFinding> = Vec::new();
+    let mut commits_scanned = 0;
+
+    let mut commit: Vec<String> = Vec::new();
+    let mut file = String::new();
+    let mut file_flagged = false;
+
+    let mut push_finding = |commit: &[String], file: &str, kind: &str, pattern: String| {
+        findings.push(HistorySecretFinding {
+            commit: commit.first().cloned().unwrap_or_default(),
+            author: commit.get(1).cloned().unwrap_or_default(),
+            date: commit.get(2).cloned().unwrap_or_default(),
+            subject: commit.get(3).cloned().unwrap_or_default(),
+            file: file.to_string(),
+            kind: kind.to_string(),
+            pattern,
+        });
+    };
+
+    for line in log.lines() {
+        if let Some(header) = line.strip_prefix(COMMIT_MARKER) {
+            commit = header.splitn(4, '\t').map(String::from).collect();
+            commits_scanned += 1;
+            file.clear();
+        } else if let Some(paths) = line.strip_prefix("diff --git ") {
+            file = paths
+                .split_once(" b/")
+                .map(|(_, b)| b.to_string())
+                .unwrap_or_default();
+            // Lockfiles are full of integrity hashes
+            file_flagged = is_lockfile(&file);
+        } else if line.starts_with("new file mode") {
+            if is_sensitive_file(&file, &overrides) {
+                push_finding(&commit, &file, "sensitive_file", file.clone());
+                file_flagged = true;
+            }
+        } else if let Some(added) = line.strip_prefix('+') {
+            // One content finding per file per commit is enough
+            if file_flagged || line.starts_with("+++") {
+                continue;
+            }
+            if let Some(token) = find_high_entropy_token(added) {
+                push_finding(&commit, &file, "high_entropy", redact(token));
+                file_flagged = true;
+            }
+        }
+    }
+
+    let warning = if findings.is_empty() {
+        None
+    } else {
+        Some(
+            "Deleting these files now does not remove them from history. Rotate any exposed credentials; \
+             removing them from the repository requires rewriting history (e.g. git filter-repo) and force-pushing."
+                .to_string(),
+        )
+    };
+
+    Ok(HistorySecretScan {
+        commits_scanned,
+        findings,
+        warning,
+    })
+}
+
 /// Create a PR using gh CLI
 #[tauri::command]
 pub fn create_pr(
@@ -757,6 +2026,7 @@ pub fn create_pr(
     title: String,
     body: String,
 ) -> Result<PrResult, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     // Check if gh is available
     if !check_gh_cli() {
         return Err("GitHub CLI (gh) is not installed".to_string());
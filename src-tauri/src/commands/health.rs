@@ -0,0 +1,229 @@
+use crate::commands::github;
+use crate::config;
+use crate::db;
+use crate::git;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Days without a session before the activity factor reaches zero
+const ACTIVITY_WINDOW_DAYS: f64 = 30.0;
+
+/// Days since the last snapshot before the snapshot factor reaches zero
+const SNAPSHOT_WINDOW_DAYS: f64 = 14.0;
+
+/// Uncommitted files at which the git factor reaches zero
+const MAX_UNCOMMITTED_FILES: f64 = 20.0;
+
+/// Relative weight of each factor in the health score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HealthWeights {
+    pub claude_md: f64,
+    pub recent_activity: f64,
+    pub git_clean: f64,
+    pub no_sensitive_files: f64,
+    pub snapshot_recency: f64,
+    pub efficiency: f64,
+}
+
+impl Default for HealthWeights {
+    fn default() -> Self {
+        Self {
+            claude_md: 15.0,
+            recent_activity: 15.0,
+            git_clean: 20.0,
+            no_sensitive_files: 20.0,
+            snapshot_recency: 15.0,
+            efficiency: 15.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthFactor {
+    pub key: String,
+    /// 0-100, or None if the factor doesn't apply (e.g. not a git repo)
+    pub score: Option<u32>,
+    pub weight: f64,
+    /// Human-readable explanation of the score
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectHealth {
+    /// Weighted average of the applicable factors, 0-100
+    pub score: u32,
+    pub factors: Vec<HealthFactor>,
+}
+
+/// Linear decay from 1.0 at zero days to 0.0 at `window` days
+fn recency(days: f64, window: f64) -> f64 {
+    (1.0 - days / window).clamp(0.0, 1.0)
+}
+
+fn days_since(timestamp: &str) -> Option<f64> {
+    let then = chrono::DateTime::parse_from_rfc3339(timestamp).ok()?;
+    let elapsed = chrono::Utc::now().signed_duration_since(then);
+    Some(elapsed.num_seconds().max(0) as f64 / 86_400.0)
+}
+
+/// Score a project 0-100 from CLAUDE.md presence, recent activity, git cleanliness,
+/// sensitive files, snapshot recency and session efficiency, with a per-factor breakdown
+#[tauri::command]
+pub fn get_project_health(project_id: String) -> Result<ProjectHealth, String> {
+    let conn = db::get_connection()?;
+    let weights = config::load_config()?.health_weights;
+
+    let project_path: String = conn
+        .query_row(
+            "SELECT path FROM projects WHERE id = ?1",
+            [&project_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Project not found: {}", e))?;
+    let path = Path::new(&project_path);
+
+    let mut factors = Vec::new();
+    let mut factor = |key: &str, weight: f64, score: Option<f64>, detail: String| {
+        factors.push(HealthFactor {
+            key: key.to_string(),
+            score: score.map(|s| (s * 100.0).round() as u32),
+            weight,
+            detail,
+        });
+    };
+
+    let has_claude_md = std::fs::read_to_string(path.join("CLAUDE.md"))
+        .map(|c| !c.trim().is_empty())
+        .unwrap_or(false);
+    factor(
+        "claudeMd",
+        weights.claude_md,
+        Some(if has_claude_md { 1.0 } else { 0.0 }),
+        if has_claude_md { "CLAUDE.md present" } else { "No CLAUDE.md" }.to_string(),
+    );
+
+    let last_session: Option<String> = conn
+        .query_row(
+            "SELECT MAX(started_at) FROM sessions WHERE project_id = ?1",
+            [&project_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to query sessions: {}", e))?;
+    match last_session.as_deref().and_then(days_since) {
+        Some(days) => factor(
+            "recentActivity",
+            weights.recent_activity,
+            Some(recency(days, ACTIVITY_WINDOW_DAYS)),
+            format!("Last session {:.0} days ago", days.floor()),
+        ),
+        None => factor(
+            "recentActivity",
+            weights.recent_activity,
+            Some(0.0),
+            "No sessions yet".to_string(),
+        ),
+    }
+
+    match github::get_git_status(project_path.clone()) {
+        Ok(status) => {
+            let uncommitted = status.staged.len() + status.modified.len() + status.untracked.len();
+            factor(
+                "gitClean",
+                weights.git_clean,
+                Some(1.0 - (uncommitted as f64 / MAX_UNCOMMITTED_FILES).min(1.0)),
+                format!("{} uncommitted files", uncommitted),
+            );
+        }
+        Err(_) => factor("gitClean", weights.git_clean, None, "Not a git repository".to_string()),
+    }
+
+    let sensitive = github::find_sensitive_files(path);
+    factor(
+        "noSensitiveFiles",
+        weights.no_sensitive_files,
+        Some(if sensitive.is_empty() { 1.0 } else { 0.0 }),
+        if sensitive.is_empty() {
+            "No unignored sensitive files".to_string()
+        } else {
+            format!("{} sensitive files or hardcoded secrets found", sensitive.len())
+        },
+    );
+
+    let latest_snapshot = git::list_snapshots(path)?.into_iter().next();
+    match latest_snapshot.as_ref().and_then(|s| days_since(&s.timestamp)) {
+        Some(days) => factor(
+            "snapshotRecency",
+            weights.snapshot_recency,
+            Some(recency(days, SNAPSHOT_WINDOW_DAYS)),
+            format!("Last snapshot {:.0} days ago", days.floor()),
+        ),
+        None => factor(
+            "snapshotRecency",
+            weights.snapshot_recency,
+            Some(0.0),
+            "No snapshots yet".to_string(),
+        ),
+    }
+
+    let avg_efficiency: Option<f64> = conn
+        .query_row(
+            "SELECT AVG(efficiency_score) FROM sessions
+             WHERE project_id = ?1 AND efficiency_score IS NOT NULL",
+            [&project_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to query efficiency: {}", e))?;
+    match avg_efficiency {
+        Some(avg) => factor(
+            "efficiency",
+            weights.efficiency,
+            Some((avg / 100.0).clamp(0.0, 1.0)),
+            format!("Average session efficiency {:.0}", avg),
+        ),
+        None => factor("efficiency", weights.efficiency, None, "No finished sessions".to_string()),
+    }
+
+    // Factors that don't apply are left out rather than counted as zero
+    let (weighted, total_weight) = factors
+        .iter()
+        .filter_map(|f| f.score.map(|s| (s as f64 * f.weight, f.weight)))
+        .fold((0.0, 0.0), |(sum, total), (value, weight)| (sum + value, total + weight));
+    let score = if total_weight > 0.0 {
+        (weighted / total_weight).round() as u32
+    } else {
+        0
+    };
+
+    Ok(ProjectHealth { score, factors })
+}
+
+/// Get the weights used by the project health score
+#[tauri::command]
+pub fn get_health_weights() -> Result<HealthWeights, String> {
+    Ok(config::load_config()?.health_weights)
+}
+
+/// Set the weights used by the project health score
+#[tauri::command]
+pub fn set_health_weights(weights: HealthWeights) -> Result<HealthWeights, String> {
+    let values = [
+        weights.claude_md,
+        weights.recent_activity,
+        weights.git_clean,
+        weights.no_sensitive_files,
+        weights.snapshot_recency,
+        weights.efficiency,
+    ];
+    if values.iter().any(|w| !w.is_finite() || *w < 0.0) {
+        return Err("Weights must be zero or positive".to_string());
+    }
+
+    let mut config = config::load_config()?;
+    config.health_weights = weights;
+    config::save_config(&config)?;
+
+    Ok(config.health_weights)
+}
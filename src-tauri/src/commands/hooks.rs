@@ -1,6 +1,9 @@
+use crate::commands::{claude_code, projects, session_memory};
+use crate::db;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -49,6 +52,25 @@ struct HookCommand {
     timeout: Option<u32>,
 }
 
+/// Lumen's hooks: (event, script, timeout in seconds). A hook entry running one
+/// of these scripts is ours; anything else in the settings belongs to the user.
+const LUMEN_HOOKS: [(&str, &str, u32); 3] = [
+    ("Stop", "lumen-session-end", 30),
+    ("UserPromptSubmit", "lumen-inject-context", 10),
+    ("SessionStart", "lumen-session-start", 10),
+];
+
+/// How a project's Lumen hooks compare with what `install_hooks` would set up now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HookInstallState {
+    UpToDate,
+    /// No Lumen hooks are configured
+    Missing,
+    /// Some are missing, point at other scripts, or the scripts are stale
+    Outdated,
+}
+
 /// Get the path to the project's .claude directory
 fn get_claude_dir(project_path: &str) -> PathBuf {
     PathBuf::from(project_path).join(".claude")
@@ -62,6 +84,7 @@ fn get_settings_path(project_path: &str) -> PathBuf {
 /// Check if hooks are installed for a project
 #[tauri::command]
 pub fn get_hooks_status(project_path: String) -> Result<HooksStatus, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let settings_path = get_settings_path(&project_path);
 
     if !settings_path.exists() {
@@ -79,15 +102,16 @@ pub fn get_hooks_status(project_path: String) -> Result<HooksStatus, String> {
     let settings: ClaudeSettings = serde_json::from_str(&content)
         .unwrap_or(ClaudeSettings { hooks: None, other: serde_json::Value::Null });
 
-    let has_stop_hook = settings.hooks.as_ref()
-        .and_then(|h| h.stop.as_ref())
-        .map(|s| !s.is_empty())
-        .unwrap_or(false);
+    let has_lumen_hook = |entries: Option<&Vec<HookEntry>>| {
+        entries
+            .map(|entries| entries.iter().any(is_lumen_hook_entry))
+            .unwrap_or(false)
+    };
 
-    let has_session_start_hook = settings.hooks.as_ref()
-        .and_then(|h| h.session_start.as_ref())
-        .map(|s| !s.is_empty())
-        .unwrap_or(false);
+    let has_stop_hook = has_lumen_hook(settings.hooks.as_ref().and_then(|h| h.stop.as_ref()));
+
+    let has_session_start_hook =
+        has_lumen_hook(settings.hooks.as_ref().and_then(|h| h.session_start.as_ref()));
 
     Ok(HooksStatus {
         installed: has_stop_hook || has_session_start_hook,
@@ -97,77 +121,141 @@ pub fn get_hooks_status(project_path: String) -> Result<HooksStatus, String> {
     })
 }
 
-/// Install Lumen hooks into a project's .claude/settings.json
+/// Install Lumen hooks into a project's .claude/settings.json.
+/// Does nothing to the settings or scripts if they're already current.
 #[tauri::command]
 pub fn install_hooks(project_path: String) -> Result<HooksStatus, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let claude_dir = get_claude_dir(&project_path);
     let settings_path = get_settings_path(&project_path);
 
-    // Create .claude directory if it doesn't exist
-    fs::create_dir_all(&claude_dir)
-        .map_err(|e| format!("Failed to create .claude directory: {}", e))?;
+    if hooks_install_state(&project_path)? != HookInstallState::UpToDate {
+        // Create .claude directory if it doesn't exist
+        fs::create_dir_all(&claude_dir)
+            .map_err(|e| format!("Failed to create .claude directory: {}", e))?;
 
-    // Read existing settings or create new
-    let mut settings: serde_json::Value = if settings_path.exists() {
-        let content = fs::read_to_string(&settings_path)
-            .map_err(|e| format!("Failed to read settings: {}", e))?;
-        serde_json::from_str(&content).unwrap_or(serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
+        // Read existing settings or create new
+        let mut settings = read_settings_value(&settings_path)?;
 
-    // Get the path to our hook scripts
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    let bin_dir = home.join(".lumen").join("bin");
-    let session_end_script = bin_dir.join("lumen-session-end");
-    let inject_context_script = bin_dir.join("lumen-inject-context");
+        // Create the hooks configuration with absolute paths
+        let bin_dir = get_hooks_bin_dir()?;
+        let entries = LUMEN_HOOKS
+            .iter()
+            .map(|(event, script, timeout)| (*event, lumen_hook_entry(&bin_dir, script, *timeout)))
+            .collect();
+        add_lumen_hooks(&mut settings, entries);
 
-    // Create the hooks configuration with absolute paths
-    let stop_hook = serde_json::json!([{
-        "hooks": [{
-            "type": "command",
-            "command": session_end_script.to_string_lossy(),
-            "timeout": 30
-        }]
-    }]);
+        // Write back to file
+        let content = serde_json::to_string_pretty(&settings)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
 
-    let user_prompt_submit_hook = serde_json::json!([{
-        "hooks": [{
-            "type": "command",
-            "command": inject_context_script.to_string_lossy(),
-            "timeout": 10
-        }]
-    }]);
+        fs::write(&settings_path, content)
+            .map_err(|e| format!("Failed to write settings: {}", e))?;
 
-    // Update or create hooks section
-    if settings.get("hooks").is_none() {
-        settings["hooks"] = serde_json::json!({});
+        // Create the hook scripts in ~/.lumen/bin/
+        create_hook_scripts()?;
     }
 
-    settings["hooks"]["Stop"] = stop_hook;
-    settings["hooks"]["UserPromptSubmit"] = user_prompt_submit_hook;
-
-    // Write back to file
-    let content = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
-
-    fs::write(&settings_path, content)
-        .map_err(|e| format!("Failed to write settings: {}", e))?;
-
-    // Create the hook scripts in ~/.lumen/bin/
-    create_hook_scripts()?;
+    // Give the session-start hook the project's latest memory to load
+    let project_id: Option<String> = db::get_connection()?
+        .query_row(
+            "SELECT id FROM projects WHERE path = ?1",
+            [&project_path],
+            |row| row.get(0),
+        )
+        .ok();
+    if let Some(project_id) = project_id {
+        refresh_session_memory_cache(&project_id)?;
+    }
 
     Ok(HooksStatus {
         installed: true,
         hooks_path: settings_path.to_string_lossy().to_string(),
         has_stop_hook: true,
-        has_session_start_hook: false,
+        has_session_start_hook: true,
+    })
+}
+
+/// Check whether a project's Lumen hooks are installed and point at the
+/// current ~/.lumen/bin scripts
+#[tauri::command]
+pub fn verify_hooks(project_path: String) -> Result<HookInstallState, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    hooks_install_state(&project_path)
+}
+
+/// Read .claude/settings.json, treating a missing or unparseable file as empty
+fn read_settings_value(settings_path: &Path) -> Result<serde_json::Value, String> {
+    if !settings_path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = fs::read_to_string(settings_path)
+        .map_err(|e| format!("Failed to read settings: {}", e))?;
+    Ok(serde_json::from_str(&content).unwrap_or(serde_json::json!({})))
+}
+
+/// Compare the project's settings and the installed scripts with the current ones
+fn hooks_install_state(project_path: &str) -> Result<HookInstallState, String> {
+    let settings = read_settings_value(&get_settings_path(project_path))?;
+    let bin_dir = get_hooks_bin_dir()?;
+
+    let state = settings_hooks_state(&settings, &bin_dir);
+    if state != HookInstallState::UpToDate {
+        return Ok(state);
+    }
+
+    let scripts_current = hook_scripts().iter().all(|(name, content)| {
+        let path = hook_script_path(&bin_dir, name);
+        fs::read_to_string(path).ok().as_deref() == Some(*content)
+    });
+    Ok(if scripts_current {
+        HookInstallState::UpToDate
+    } else {
+        HookInstallState::Outdated
+    })
+}
+
+/// Compare the Lumen entries in a settings file with the ones `install_hooks` writes
+fn settings_hooks_state(settings: &serde_json::Value, bin_dir: &Path) -> HookInstallState {
+    let mut found_any = false;
+    let mut all_current = true;
+
+    for (event, script, timeout) in LUMEN_HOOKS {
+        let ours: Vec<&serde_json::Value> = settings
+            .get("hooks")
+            .and_then(|h| h.get(event))
+            .and_then(|e| e.as_array())
+            .map(|entries| entries.iter().filter(|e| is_lumen_hook_value(e)).collect())
+            .unwrap_or_default();
+
+        found_any |= !ours.is_empty();
+        all_current &= ours == [&lumen_hook_entry(bin_dir, script, timeout)];
+    }
+
+    if !found_any {
+        HookInstallState::Missing
+    } else if all_current {
+        HookInstallState::UpToDate
+    } else {
+        HookInstallState::Outdated
+    }
+}
+
+/// The settings.json entry that runs one of Lumen's hook scripts
+fn lumen_hook_entry(bin_dir: &Path, script: &str, timeout: u32) -> serde_json::Value {
+    serde_json::json!({
+        "hooks": [{
+            "type": "command",
+            "command": hook_command(&hook_script_path(bin_dir, script)),
+            "timeout": timeout
+        }]
     })
 }
 
 /// Remove Lumen hooks from a project
 #[tauri::command]
 pub fn uninstall_hooks(project_path: String) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let settings_path = get_settings_path(&project_path);
 
     if !settings_path.exists() {
@@ -180,13 +268,8 @@ pub fn uninstall_hooks(project_path: String) -> Result<(), String> {
     let mut settings: serde_json::Value = serde_json::from_str(&content)
         .map_err(|e| format!("Failed to parse settings: {}", e))?;
 
-    // Remove our hooks
-    if let Some(hooks) = settings.get_mut("hooks") {
-        if let Some(obj) = hooks.as_object_mut() {
-            obj.remove("Stop");
-            obj.remove("UserPromptSubmit");
-        }
-    }
+    // Remove our hooks, leaving the user's
+    remove_lumen_hooks(&mut settings);
 
     // Write back
     let content = serde_json::to_string_pretty(&settings)
@@ -198,16 +281,137 @@ pub fn uninstall_hooks(project_path: String) -> Result<(), String> {
     Ok(())
 }
 
-/// Create the hook scripts in ~/.lumen/bin/
-fn create_hook_scripts() -> Result<(), String> {
+/// Whether a hook entry runs one of Lumen's hook scripts
+fn is_lumen_hook_entry(entry: &HookEntry) -> bool {
+    entry.hooks.iter().any(|hook| {
+        hook.command
+            .as_deref()
+            .is_some_and(|command| LUMEN_HOOKS.iter().any(|(_, script, _)| command.contains(script)))
+    })
+}
+
+/// `is_lumen_hook_entry` for an entry still in raw settings JSON
+fn is_lumen_hook_value(entry: &serde_json::Value) -> bool {
+    serde_json::from_value::<HookEntry>(entry.clone())
+        .map(|entry| is_lumen_hook_entry(&entry))
+        .unwrap_or(false)
+}
+
+/// Add Lumen's entry for each hook event alongside any hooks already
+/// configured for it, replacing a previous Lumen entry
+fn add_lumen_hooks(settings: &mut serde_json::Value, entries: Vec<(&str, serde_json::Value)>) {
+    if !settings.is_object() {
+        *settings = serde_json::json!({});
+    }
+    if !settings.get("hooks").is_some_and(|h| h.is_object()) {
+        settings["hooks"] = serde_json::json!({});
+    }
+
+    for (event, entry) in entries {
+        let hooks = &mut settings["hooks"];
+        let mut existing = match hooks.get(event) {
+            Some(serde_json::Value::Array(existing)) => existing.clone(),
+            _ => Vec::new(),
+        };
+        existing.retain(|e| !is_lumen_hook_value(e));
+        existing.push(entry);
+        hooks[event] = serde_json::Value::Array(existing);
+    }
+}
+
+/// Remove Lumen's hook entries, dropping an event only once nothing else is left in it
+fn remove_lumen_hooks(settings: &mut serde_json::Value) {
+    let Some(hooks) = settings.get_mut("hooks").and_then(|h| h.as_object_mut()) else {
+        return;
+    };
+
+    for (event, _, _) in LUMEN_HOOKS {
+        let now_empty = match hooks.get_mut(event) {
+            Some(serde_json::Value::Array(entries)) => {
+                entries.retain(|e| !is_lumen_hook_value(e));
+                entries.is_empty()
+            }
+            _ => false,
+        };
+        if now_empty {
+            hooks.remove(event);
+        }
+    }
+}
+
+/// Path of a hook script. On Windows, where bash, jq and md5sum aren't available,
+/// the hooks are PowerShell scripts.
+fn hook_script_path(bin_dir: &Path, name: &str) -> PathBuf {
+    if cfg!(windows) {
+        bin_dir.join(format!("{}.ps1", name))
+    } else {
+        bin_dir.join(name)
+    }
+}
+
+/// The settings.json `command` that runs a hook script
+fn hook_command(script: &Path) -> String {
+    if cfg!(windows) {
+        format!(
+            "powershell -NoProfile -ExecutionPolicy Bypass -File \"{}\"",
+            script.display()
+        )
+    } else {
+        script.to_string_lossy().to_string()
+    }
+}
+
+/// Get the directory Lumen's hook scripts are installed in (~/.lumen/bin)
+fn get_hooks_bin_dir() -> Result<PathBuf, String> {
     let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    let bin_dir = home.join(".lumen").join("bin");
+    Ok(home.join(".lumen").join("bin"))
+}
+
+/// Lumen's hook scripts for this platform, as (name, content)
+fn hook_scripts() -> [(&'static str, &'static str); 3] {
+    if cfg!(windows) {
+        powershell_hook_scripts()
+    } else {
+        bash_hook_scripts()
+    }
+}
+
+/// Create the hook scripts in ~/.lumen/bin/, rewriting only those that changed
+fn create_hook_scripts() -> Result<(), String> {
+    let bin_dir = get_hooks_bin_dir()?;
 
     fs::create_dir_all(&bin_dir)
         .map_err(|e| format!("Failed to create bin directory: {}", e))?;
 
-    // Create lumen-session-end script
-    // Note: This just captures metadata. Claude Code already generates summaries
+    for (name, content) in hook_scripts() {
+        let path = hook_script_path(&bin_dir, name);
+        if fs::read_to_string(&path).ok().as_deref() != Some(content) {
+            fs::write(&path, content)
+                .map_err(|e| format!("Failed to write {} script: {}", name, e))?;
+        }
+
+        // Make executable on Unix
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let perms = fs::Permissions::from_mode(0o755);
+            fs::set_permissions(&path, perms)
+                .map_err(|e| format!("Failed to set permissions: {}", e))?;
+        }
+    }
+
+    // Add bin dir to PATH hint
+    println!("Hook scripts created in {:?}", bin_dir);
+    if !cfg!(windows) {
+        println!("Make sure {} is in your PATH", bin_dir.display());
+    }
+
+    Ok(())
+}
+
+/// The bash hook scripts
+fn bash_hook_scripts() -> [(&'static str, &'static str); 3] {
+    // lumen-session-end just captures metadata. Claude Code already generates summaries
     // in ~/.claude/projects/ which we read via get_claude_code_sessions.
     let session_end_script = r#"#!/bin/bash
 # Lumen Session End Hook
@@ -243,20 +447,7 @@ fi
 echo '{"continue": true}'
 "#;
 
-    let session_end_path = bin_dir.join("lumen-session-end");
-    fs::write(&session_end_path, session_end_script)
-        .map_err(|e| format!("Failed to write session-end script: {}", e))?;
-
-    // Make executable on Unix
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let perms = fs::Permissions::from_mode(0o755);
-        fs::set_permissions(&session_end_path, perms)
-            .map_err(|e| format!("Failed to set permissions: {}", e))?;
-    }
-
-    // Create lumen-inject-context script
+    // lumen-inject-context injects prompt prefix and project context into prompts
     let inject_context_script = r#"#!/bin/bash
 # Lumen Context Injection Hook
 # This script injects prompt prefix and project context into prompts
@@ -264,6 +455,8 @@ echo '{"continue": true}'
 # Read hook input from stdin
 INPUT=$(cat)
 CWD=$(echo "$INPUT" | jq -r '.cwd // empty')
+# Lumen keys the cache by the resolved project path; Claude Code reports the path it was started from
+[ -n "$CWD" ] && CWD=$(cd "$CWD" 2>/dev/null && pwd -P || echo "$CWD")
 
 LUMEN_DIR="$HOME/.lumen"
 OUTPUT=""
@@ -292,23 +485,137 @@ if [ -n "$OUTPUT" ]; then
 fi
 "#;
 
-    let inject_context_path = bin_dir.join("lumen-inject-context");
-    fs::write(&inject_context_path, inject_context_script)
-        .map_err(|e| format!("Failed to write inject-context script: {}", e))?;
+    // lumen-session-start prints the project's latest session memory
+    let session_start_script = r#"#!/bin/bash
+# Lumen Session Start Hook
+# Adds the project's latest session memory as context when a session starts
 
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let perms = fs::Permissions::from_mode(0o755);
-        fs::set_permissions(&inject_context_path, perms)
-            .map_err(|e| format!("Failed to set permissions: {}", e))?;
+# Read hook input from stdin
+INPUT=$(cat)
+CWD=$(echo "$INPUT" | jq -r '.cwd // empty')
+[ -n "$CWD" ] && CWD=$(cd "$CWD" 2>/dev/null && pwd -P || echo "$CWD")
+
+# Lumen keeps the latest memory for each project here
+MEMORY_FILE="$HOME/.lumen/session_memory_cache/$(echo "$CWD" | md5sum | cut -d' ' -f1).txt"
+if [ -f "$MEMORY_FILE" ]; then
+    cat "$MEMORY_FILE"
+fi
+"#;
+
+    [
+        ("lumen-session-end", session_end_script),
+        ("lumen-inject-context", inject_context_script),
+        ("lumen-session-start", session_start_script),
+    ]
+}
+
+/// The PowerShell versions of the hook scripts, for Windows
+fn powershell_hook_scripts() -> [(&'static str, &'static str); 3] {
+    // Same metadata as lumen-session-end. WriteAllText writes UTF-8 without
+    // a BOM, which Windows PowerShell's Set-Content would add.
+    let session_end_script = r#"# Lumen Session End Hook
+# Captures session metadata when Claude Code sessions end
+# Note: Claude Code already generates summaries - we just record metadata here
+
+[Console]::InputEncoding = New-Object System.Text.UTF8Encoding $false
+[Console]::OutputEncoding = New-Object System.Text.UTF8Encoding $false
+
+# Read hook input from stdin
+$HookInput = $null
+try { $HookInput = [Console]::In.ReadToEnd() | ConvertFrom-Json } catch { }
+
+# Extract relevant fields
+$SessionId = "$($HookInput.session_id)"
+$TranscriptPath = "$($HookInput.transcript_path)"
+$Cwd = "$($HookInput.cwd)"
+
+# Save session metadata for Lumen to process
+$SessionsDir = Join-Path $HOME ".lumen\session_metadata"
+New-Item -ItemType Directory -Force -Path $SessionsDir | Out-Null
+
+if ($SessionId) {
+    $Metadata = [ordered]@{
+        session_id = $SessionId
+        transcript_path = $TranscriptPath
+        cwd = $Cwd
+        ended_at = (Get-Date -Format "yyyy-MM-ddTHH:mm:sszzz")
     }
+    [System.IO.File]::WriteAllText((Join-Path $SessionsDir "$SessionId.json"), ($Metadata | ConvertTo-Json))
+}
 
-    // Add bin dir to PATH hint
-    println!("Hook scripts created in {:?}", bin_dir);
-    println!("Make sure {} is in your PATH", bin_dir.display());
+# Output JSON to continue (don't block)
+Write-Output '{"continue": true}'
+"#;
 
-    Ok(())
+    // Same output as lumen-inject-context, including the context cache file name:
+    // the md5 of the cwd plus a newline, as `echo "$CWD" | md5sum` hashes it
+    let inject_context_script = r#"# Lumen Context Injection Hook
+# This script injects prompt prefix and project context into prompts
+
+[Console]::InputEncoding = New-Object System.Text.UTF8Encoding $false
+[Console]::OutputEncoding = New-Object System.Text.UTF8Encoding $false
+
+# Read hook input from stdin
+$HookInput = $null
+try { $HookInput = [Console]::In.ReadToEnd() | ConvertFrom-Json } catch { }
+$Cwd = "$($HookInput.cwd)"
+
+$LumenDir = Join-Path $HOME ".lumen"
+$Output = ""
+
+# Check for prompt prefix (e.g., "Be concise")
+$PrefixFile = Join-Path $LumenDir "prompt_prefix.txt"
+if (Test-Path $PrefixFile) {
+    $Prefix = [System.IO.File]::ReadAllText($PrefixFile).TrimEnd("`r", "`n")
+    if ($Prefix) {
+        $Output = "$Prefix`n`n"
+    }
+}
+
+# Check for project-specific context
+$Md5 = [System.Security.Cryptography.MD5]::Create()
+$HashBytes = $Md5.ComputeHash([System.Text.Encoding]::UTF8.GetBytes("$Cwd`n"))
+$Hash = -join ($HashBytes | ForEach-Object { $_.ToString("x2") })
+$ContextFile = Join-Path $LumenDir "context_cache\$Hash.txt"
+if (Test-Path $ContextFile) {
+    $Context = [System.IO.File]::ReadAllText($ContextFile).TrimEnd("`r", "`n")
+    if ($Context) {
+        $Output = "$Output$Context"
+    }
+}
+
+# Output the combined prefix + context
+if ($Output) {
+    [Console]::Out.Write("$Output`n")
+}
+"#;
+
+    let session_start_script = r#"# Lumen Session Start Hook
+# Adds the project's latest session memory as context when a session starts
+
+[Console]::InputEncoding = New-Object System.Text.UTF8Encoding $false
+[Console]::OutputEncoding = New-Object System.Text.UTF8Encoding $false
+
+# Read hook input from stdin
+$HookInput = $null
+try { $HookInput = [Console]::In.ReadToEnd() | ConvertFrom-Json } catch { }
+$Cwd = "$($HookInput.cwd)"
+
+# Lumen keeps the latest memory for each project here
+$Md5 = [System.Security.Cryptography.MD5]::Create()
+$HashBytes = $Md5.ComputeHash([System.Text.Encoding]::UTF8.GetBytes("$Cwd`n"))
+$Hash = -join ($HashBytes | ForEach-Object { $_.ToString("x2") })
+$MemoryFile = Join-Path $HOME ".lumen\session_memory_cache\$Hash.txt"
+if (Test-Path $MemoryFile) {
+    [Console]::Out.Write([System.IO.File]::ReadAllText($MemoryFile))
+}
+"#;
+
+    [
+        ("lumen-session-end", session_end_script),
+        ("lumen-inject-context", inject_context_script),
+        ("lumen-session-start", session_start_script),
+    ]
 }
 
 /// Get session metadata captured by hooks
@@ -363,15 +670,33 @@ pub fn import_session_summary(session_id: String, project_id: String) -> Result<
         .unwrap_or("")
         .to_string();
 
-    // Create a simple memory entry linking the session
+    // Read what actually happened from the session's transcript
+    let project_dir = (!cwd.is_empty()).then(|| Path::new(&cwd));
+    let transcript = data
+        .get("transcript_path")
+        .and_then(|v| v.as_str())
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .filter(|p| p.is_file())
+        .and_then(|path| match claude_code::summarize_transcript(&path, project_dir) {
+            Ok(summary) => Some(summary),
+            Err(e) => {
+                eprintln!("Failed to summarize transcript: {}", e);
+                None
+            }
+        })
+        .unwrap_or_default();
+
     let input = crate::commands::session_memory::CreateSessionMemoryInput {
         project_id,
         claude_session_id: Some(session_id.clone()),
-        summary: format!("Session in {}", cwd),
+        summary: transcript
+            .summary
+            .unwrap_or_else(|| format!("Session in {}", cwd)),
         key_decisions: None,
         open_threads: None,
-        files_touched: None,
-        duration_minutes: None,
+        files_touched: Some(transcript.files_touched),
+        duration_minutes: transcript.duration_minutes,
     };
 
     crate::commands::session_memory::save_session_memory(input)?;
@@ -397,6 +722,33 @@ pub fn clear_pending_session(session_id: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Delete pending session metadata files last written more than `days` days ago.
+/// Returns how many were removed.
+pub fn prune_session_metadata(days: i64) -> Result<usize, String> {
+    let home = dirs::home_dir().ok_or("Could not find home directory")?;
+    let metadata_dir = home.join(".lumen").join("session_metadata");
+    let Ok(entries) = fs::read_dir(&metadata_dir) else {
+        return Ok(0);
+    };
+
+    let cutoff = std::time::SystemTime::now()
+        - std::time::Duration::from_secs(days.max(0) as u64 * 24 * 60 * 60);
+    let mut removed = 0;
+    for path in entries.flatten().map(|e| e.path()) {
+        let expired = path.extension().is_some_and(|e| e == "json")
+            && fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified < cutoff);
+        if expired {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove session metadata: {}", e))?;
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
 /// Set the prompt prefix that will be injected before each message
 #[tauri::command]
 pub fn set_prompt_prefix(prefix: Option<String>) -> Result<(), String> {
@@ -435,3 +787,338 @@ pub fn get_prompt_prefix() -> Result<Option<String>, String> {
         Ok(None)
     }
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextCacheEntry {
+    pub file_name: String,
+    /// The known project this cache belongs to, if any
+    pub project_path: Option<String>,
+    pub project_name: Option<String>,
+    pub size_bytes: u64,
+    pub modified_at: Option<String>,
+    pub age_minutes: Option<i64>,
+}
+
+/// Get the directory the inject-context hook reads cached context from
+fn get_context_cache_dir() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not find home directory")?;
+    Ok(home.join(".lumen").join("context_cache"))
+}
+
+/// Cache file name the hook uses for a cwd: md5 of `echo "$CWD"` (note the trailing newline)
+fn context_cache_file_name(cwd: &str) -> String {
+    format!("{:x}.txt", md5::compute(format!("{}\n", cwd)))
+}
+
+/// Get the directory the session-start hook reads the latest session memories from
+fn get_session_memory_cache_dir() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not find home directory")?;
+    Ok(home.join(".lumen").join("session_memory_cache"))
+}
+
+/// Render a session memory as the context the session-start hook prints
+fn format_session_memory(memory: &session_memory::SessionMemory) -> String {
+    let mut text = format!(
+        "## Last session ({})\n\n{}\n",
+        memory.session_date,
+        memory.summary.trim()
+    );
+    for (title, items) in [
+        ("Key decisions", &memory.key_decisions),
+        ("Open threads", &memory.open_threads),
+    ] {
+        if !items.is_empty() {
+            text.push_str(&format!("\n{}:\n", title));
+            for item in items {
+                text.push_str(&format!("- {}\n", item));
+            }
+        }
+    }
+    text
+}
+
+/// Write a project's latest session memory where the session-start hook looks
+/// for it, or remove the cached copy once the project has none
+pub fn refresh_session_memory_cache(project_id: &str) -> Result<(), String> {
+    let conn = db::get_connection()?;
+    let Ok(project_path) = conn.query_row(
+        "SELECT path FROM projects WHERE id = ?1",
+        [project_id],
+        |row| row.get::<_, String>(0),
+    ) else {
+        return Ok(());
+    };
+
+    let cache_dir = get_session_memory_cache_dir()?;
+    let cache_file = cache_dir.join(context_cache_file_name(&project_path));
+
+    match session_memory::get_latest_session_memory(project_id.to_string())? {
+        Some(memory) => {
+            fs::create_dir_all(&cache_dir)
+                .map_err(|e| format!("Failed to create session memory cache: {}", e))?;
+            fs::write(&cache_file, format_session_memory(&memory))
+                .map_err(|e| format!("Failed to write session memory cache: {}", e))
+        }
+        None => {
+            if cache_file.exists() {
+                fs::remove_file(&cache_file)
+                    .map_err(|e| format!("Failed to remove session memory cache: {}", e))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// List cached context files with the project each belongs to, size and age
+#[tauri::command]
+pub fn list_context_cache() -> Result<Vec<ContextCacheEntry>, String> {
+    let cache_dir = get_context_cache_dir()?;
+    if !cache_dir.exists() {
+        return Ok(vec![]);
+    }
+
+    // Map cache file names back to known projects
+    let projects: HashMap<String, (String, String)> = projects::list_projects()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| (context_cache_file_name(&p.path), (p.path, p.name)))
+        .collect();
+
+    let mut entries = vec![];
+
+    for entry in fs::read_dir(&cache_dir)
+        .map_err(|e| format!("Failed to read context cache: {}", e))?
+        .flatten()
+    {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let metadata = entry.metadata().ok();
+        let modified = metadata
+            .as_ref()
+            .and_then(|m| m.modified().ok())
+            .map(chrono::DateTime::<chrono::Utc>::from);
+        let project = projects.get(&file_name);
+
+        entries.push(ContextCacheEntry {
+            project_path: project.map(|(path, _)| path.clone()),
+            project_name: project.map(|(_, name)| name.clone()),
+            file_name,
+            size_bytes: metadata.map(|m| m.len()).unwrap_or(0),
+            modified_at: modified.map(|m| m.to_rfc3339()),
+            age_minutes: modified.map(|m| chrono::Utc::now().signed_duration_since(m).num_minutes()),
+        });
+    }
+
+    entries.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+
+    Ok(entries)
+}
+
+/// Clear cached context for one project, or all of it. Returns how many files were removed.
+#[tauri::command]
+pub fn clear_context_cache(project_path: Option<String>) -> Result<usize, String> {
+    let cache_dir = get_context_cache_dir()?;
+    if !cache_dir.exists() {
+        return Ok(0);
+    }
+
+    let targets: Vec<PathBuf> = match project_path {
+        Some(path) => {
+            // The hook hashes whatever cwd Claude reports, so try the path as given too
+            let mut names = vec![context_cache_file_name(&path)];
+            if let Ok(normalized) = projects::normalize_path(&path) {
+                names.push(context_cache_file_name(&normalized));
+            }
+            names.dedup();
+            names.into_iter().map(|n| cache_dir.join(n)).collect()
+        }
+        None => fs::read_dir(&cache_dir)
+            .map_err(|e| format!("Failed to read context cache: {}", e))?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_file())
+            .collect(),
+    };
+
+    let mut removed = 0;
+    for target in targets {
+        if target.exists() {
+            fs::remove_file(&target)
+                .map_err(|e| format!("Failed to remove cached context: {}", e))?;
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptPrefixPreview {
+    /// The prefix the preview was composed with
+    pub prefix: Option<String>,
+    /// Cached project context, if the project has any
+    pub context: Option<String>,
+    /// Exactly what the inject-context hook would output
+    pub injected: Option<String>,
+    /// The injected text followed by the sample prompt
+    pub full_prompt: String,
+}
+
+/// Compose prefix and context the way lumen-inject-context does. `$(cat ...)`
+/// drops trailing newlines and empty values are skipped.
+fn compose_injected_context(prefix: Option<&str>, context: Option<&str>) -> Option<String> {
+    let prefix = prefix.map(|p| p.trim_end_matches('\n')).filter(|p| !p.is_empty());
+    let context = context.map(|c| c.trim_end_matches('\n')).filter(|c| !c.is_empty());
+
+    let mut output = String::new();
+    if let Some(p) = prefix {
+        output.push_str(p);
+        output.push_str("\n\n");
+    }
+    if let Some(c) = context {
+        output.push_str(c);
+    }
+
+    if output.is_empty() {
+        None
+    } else {
+        Some(output)
+    }
+}
+
+/// Read the cached context the hook would pick up for a project
+fn read_cached_context(project_path: &str) -> Result<Option<String>, String> {
+    let cache_dir = get_context_cache_dir()?;
+
+    let mut names = vec![context_cache_file_name(project_path)];
+    if let Ok(normalized) = projects::normalize_path(project_path) {
+        names.push(context_cache_file_name(&normalized));
+    }
+    names.dedup();
+
+    for name in names {
+        let path = cache_dir.join(name);
+        if path.is_file() {
+            let content = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read cached context: {}", e))?;
+            return Ok(Some(content));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Preview what a prompt looks like once the hook has applied the prefix and
+/// project context. Uses the current prefix unless `proposed_prefix` is given;
+/// an empty proposed prefix previews clearing it.
+#[tauri::command]
+pub fn preview_prompt_prefix_effect(
+    sample_prompt: String,
+    proposed_prefix: Option<String>,
+    project_path: Option<String>,
+) -> Result<PromptPrefixPreview, String> {
+    let prefix = match proposed_prefix {
+        Some(p) => Some(p),
+        None => get_prompt_prefix()?,
+    };
+    let context = match project_path {
+        Some(path) => read_cached_context(&path)?,
+        None => None,
+    };
+
+    let injected = compose_injected_context(prefix.as_deref(), context.as_deref());
+    let full_prompt = match &injected {
+        Some(text) => format!("{}\n{}", text, sample_prompt),
+        None => sample_prompt,
+    };
+
+    Ok(PromptPrefixPreview {
+        prefix: prefix.filter(|p| !p.trim_end_matches('\n').is_empty()),
+        context,
+        injected,
+        full_prompt,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lumen_entry(script: &str) -> serde_json::Value {
+        serde_json::json!({
+            "hooks": [{
+                "type": "command",
+                "command": format!("/home/me/.lumen/bin/{}", script),
+                "timeout": 30
+            }]
+        })
+    }
+
+    #[test]
+    fn test_user_hooks_survive_install_and_uninstall() {
+        let user_stop = serde_json::json!({
+            "matcher": "",
+            "hooks": [{ "type": "command", "command": "notify-send done" }]
+        });
+        let original = serde_json::json!({
+            "model": "opus",
+            "hooks": { "Stop": [user_stop.clone()] }
+        });
+
+        let lumen_entries = || {
+            vec![
+                ("Stop", lumen_entry("lumen-session-end")),
+                ("UserPromptSubmit", lumen_entry("lumen-inject-context")),
+                ("SessionStart", lumen_entry("lumen-session-start")),
+            ]
+        };
+
+        let mut settings = original.clone();
+        add_lumen_hooks(&mut settings, lumen_entries());
+        // Installing twice doesn't duplicate our entries
+        add_lumen_hooks(&mut settings, lumen_entries());
+
+        let stop = settings["hooks"]["Stop"].as_array().unwrap();
+        assert_eq!(stop.len(), 2);
+        assert_eq!(stop[0], user_stop);
+        assert!(is_lumen_hook_value(&stop[1]));
+        assert_eq!(settings["hooks"]["UserPromptSubmit"].as_array().unwrap().len(), 1);
+
+        remove_lumen_hooks(&mut settings);
+        assert_eq!(settings, original);
+    }
+
+    #[test]
+    fn test_settings_hooks_state() {
+        let bin_dir = Path::new("/home/me/.lumen/bin");
+        let current = || {
+            LUMEN_HOOKS
+                .iter()
+                .map(|(event, script, timeout)| (*event, lumen_hook_entry(bin_dir, script, *timeout)))
+                .collect::<Vec<_>>()
+        };
+
+        let mut settings = serde_json::json!({});
+        assert_eq!(settings_hooks_state(&settings, bin_dir), HookInstallState::Missing);
+
+        add_lumen_hooks(&mut settings, current());
+        assert_eq!(settings_hooks_state(&settings, bin_dir), HookInstallState::UpToDate);
+
+        // Scripts moved since the hooks were installed
+        assert_eq!(
+            settings_hooks_state(&settings, Path::new("/opt/lumen/bin")),
+            HookInstallState::Outdated
+        );
+
+        // Installed before the SessionStart hook existed
+        let mut settings = serde_json::json!({});
+        add_lumen_hooks(&mut settings, current().into_iter().take(2).collect());
+        assert_eq!(settings_hooks_state(&settings, bin_dir), HookInstallState::Outdated);
+    }
+}
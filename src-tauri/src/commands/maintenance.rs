@@ -0,0 +1,196 @@
+use crate::commands::hooks;
+use crate::config;
+use crate::db;
+use crate::git;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Startup maintenance runs at most this often
+const STARTUP_INTERVAL_HOURS: i64 = 24;
+
+/// Retention settings applied by maintenance runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MaintenanceConfig {
+    /// Run maintenance automatically on startup
+    pub enabled: bool,
+    /// Keep only this many snapshots per project (None = keep all)
+    pub keep_last_snapshots: Option<usize>,
+    /// Delete session metadata older than this many days (None = keep forever)
+    pub session_retention_days: Option<i64>,
+    /// Vacuum the database when the last vacuum is older than this many days
+    pub vacuum_interval_days: i64,
+    pub last_run: Option<MaintenanceReport>,
+    pub last_vacuum_at: Option<String>,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            keep_last_snapshots: None,
+            session_retention_days: None,
+            vacuum_interval_days: 7,
+            last_run: None,
+            last_vacuum_at: None,
+        }
+    }
+}
+
+/// What a maintenance run did
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MaintenanceReport {
+    pub ran_at: String,
+    pub snapshots_pruned: usize,
+    pub session_metadata_pruned: usize,
+    pub vacuumed: bool,
+    pub errors: Vec<String>,
+}
+
+fn hours_since(timestamp: Option<&str>) -> Option<i64> {
+    let t = chrono::DateTime::parse_from_rfc3339(timestamp?).ok()?;
+    Some(chrono::Utc::now().signed_duration_since(t).num_hours())
+}
+
+/// Run the configured maintenance: snapshot pruning, session metadata cleanup and DB vacuum
+fn run_maintenance(settings: &MaintenanceConfig) -> MaintenanceReport {
+    let mut report = MaintenanceReport {
+        ran_at: chrono::Utc::now().to_rfc3339(),
+        ..Default::default()
+    };
+
+    if let Some(keep_last) = settings.keep_last_snapshots {
+        let paths: Result<Vec<String>, String> = db::get_connection().and_then(|conn| {
+            let mut stmt = conn
+                .prepare("SELECT path FROM projects")
+                .map_err(|e| format!("Failed to prepare query: {}", e))?;
+            let paths = stmt
+                .query_map([], |row| row.get(0))
+                .map_err(|e| format!("Failed to query projects: {}", e))?
+                .filter_map(|r| r.ok())
+                .collect();
+            Ok(paths)
+        });
+
+        match paths {
+            Ok(paths) => {
+                for path in paths {
+                    match git::prune_snapshots(Path::new(&path), keep_last, None) {
+                        Ok(pruned) => report.snapshots_pruned += pruned,
+                        Err(e) => report.errors.push(format!("{}: {}", path, e)),
+                    }
+                }
+            }
+            Err(e) => report.errors.push(e),
+        }
+    }
+
+    if let Some(days) = settings.session_retention_days {
+        match hooks::prune_session_metadata(days) {
+            Ok(pruned) => report.session_metadata_pruned = pruned,
+            Err(e) => report.errors.push(e),
+        }
+    }
+
+    let vacuum_due = hours_since(settings.last_vacuum_at.as_deref())
+        .map(|h| h >= settings.vacuum_interval_days * 24)
+        .unwrap_or(true);
+    if vacuum_due {
+        match db::get_connection().and_then(|conn| {
+            conn.execute_batch("VACUUM")
+                .map_err(|e| format!("Failed to vacuum database: {}", e))
+        }) {
+            Ok(()) => report.vacuumed = true,
+            Err(e) => report.errors.push(e),
+        }
+    }
+
+    report
+}
+
+/// Run maintenance and record the report (and vacuum time) in the config
+fn run_and_record() -> Result<MaintenanceReport, String> {
+    let settings = config::load_config()?.maintenance;
+    let report = run_maintenance(&settings);
+
+    // Reload in case settings changed while maintenance ran
+    let mut cfg = config::load_config()?;
+    if report.vacuumed {
+        cfg.maintenance.last_vacuum_at = Some(report.ran_at.clone());
+    }
+    cfg.maintenance.last_run = Some(report.clone());
+    config::save_config(&cfg)?;
+
+    Ok(report)
+}
+
+/// Run maintenance in the background on startup if it's enabled and hasn't run recently
+pub fn run_on_startup() {
+    std::thread::spawn(|| {
+        let Ok(cfg) = config::load_config() else {
+            return;
+        };
+        let settings = cfg.maintenance;
+        let recently_ran = hours_since(settings.last_run.as_ref().map(|r| r.ran_at.as_str()))
+            .map(|h| h < STARTUP_INTERVAL_HOURS)
+            .unwrap_or(false);
+        if !settings.enabled || recently_ran {
+            return;
+        }
+
+        // The report is kept in the config for `get_last_maintenance`
+        match run_and_record() {
+            Ok(report) if !report.errors.is_empty() => {
+                eprintln!("Maintenance errors: {}", report.errors.join("; "))
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Maintenance failed: {}", e),
+        }
+    });
+}
+
+/// Get the maintenance settings
+#[tauri::command]
+pub fn get_maintenance_config() -> Result<MaintenanceConfig, String> {
+    Ok(config::load_config()?.maintenance)
+}
+
+/// Update the retention settings used by maintenance
+#[tauri::command]
+pub fn set_maintenance_config(
+    enabled: bool,
+    keep_last_snapshots: Option<usize>,
+    session_retention_days: Option<i64>,
+    vacuum_interval_days: Option<i64>,
+) -> Result<MaintenanceConfig, String> {
+    if keep_last_snapshots == Some(0) {
+        return Err("Keep at least one snapshot".to_string());
+    }
+    if session_retention_days.map(|d| d < 1).unwrap_or(false) {
+        return Err("Session retention must be at least one day".to_string());
+    }
+
+    let mut cfg = config::load_config()?;
+    cfg.maintenance.enabled = enabled;
+    cfg.maintenance.keep_last_snapshots = keep_last_snapshots;
+    cfg.maintenance.session_retention_days = session_retention_days;
+    if let Some(days) = vacuum_interval_days {
+        cfg.maintenance.vacuum_interval_days = days.max(1);
+    }
+    config::save_config(&cfg)?;
+
+    Ok(cfg.maintenance)
+}
+
+/// Run maintenance now, regardless of when it last ran
+#[tauri::command]
+pub fn run_maintenance_now() -> Result<MaintenanceReport, String> {
+    run_and_record()
+}
+
+/// Get the report from the last maintenance run
+#[tauri::command]
+pub fn get_last_maintenance() -> Result<Option<MaintenanceReport>, String> {
+    Ok(config::load_config()?.maintenance.last_run)
+}
@@ -1,3 +1,4 @@
+use crate::commands::projects;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -14,10 +15,16 @@ pub struct MemoryItem {
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
 struct MemoryStore {
     items: HashMap<String, MemoryItem>,
 }
 
+/// Check that memory file content parses as a memory store
+pub(crate) fn check_memory_json(content: &str) -> Result<(), serde_json::Error> {
+    serde_json::from_str::<MemoryStore>(content).map(|_| ())
+}
+
 /// Get the memory file path for a project
 fn get_memory_path(project_path: &str) -> std::path::PathBuf {
     Path::new(project_path).join(".lumen").join("memory.json")
@@ -60,6 +67,7 @@ fn save_memory(project_path: &str, store: &MemoryStore) -> Result<(), String> {
 /// Get all memory items for a project
 #[tauri::command]
 pub fn get_memory(project_path: String) -> Result<Vec<MemoryItem>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let store = load_memory(&project_path)?;
     let mut items: Vec<MemoryItem> = store.items.into_values().collect();
 
@@ -77,6 +85,7 @@ pub fn set_memory(
     value: String,
     category: Option<String>,
 ) -> Result<MemoryItem, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let mut store = load_memory(&project_path)?;
 
     let now = chrono::Utc::now().to_rfc3339();
@@ -112,6 +121,7 @@ pub fn set_memory(
 /// Delete a memory item
 #[tauri::command]
 pub fn delete_memory(project_path: String, key: String) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let mut store = load_memory(&project_path)?;
 
     if store.items.remove(&key).is_none() {
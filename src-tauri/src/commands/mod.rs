@@ -1,13 +1,22 @@
 pub mod analytics;
+pub mod budgets;
 pub mod claude_code;
+pub mod commit_message;
+pub mod git_watch;
 pub mod github;
+pub mod health;
 pub mod hooks;
+pub mod maintenance;
 pub mod memory;
+pub mod operations;
 pub mod projects;
 pub mod prompts;
 pub mod pty;
 pub mod quick_commands;
+pub mod session_events;
 pub mod session_memory;
+pub mod session_notes;
 pub mod sessions;
 pub mod snapshots;
 pub mod sync;
+pub mod test_watch;
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static::lazy_static! {
+    /// Long-running operations by id, and whether cancellation has been requested
+    static ref OPERATIONS: Mutex<HashMap<String, bool>> = Mutex::new(HashMap::new());
+}
+
+/// Register a cancellable operation. Fails if the id is already in use.
+pub fn begin(operation_id: &str) -> Result<(), String> {
+    let mut operations = OPERATIONS.lock().map_err(|e| e.to_string())?;
+    if operations.contains_key(operation_id) {
+        return Err(format!("Operation {} is already running", operation_id));
+    }
+    operations.insert(operation_id.to_string(), false);
+    Ok(())
+}
+
+/// Whether cancellation has been requested for an operation
+pub fn is_cancelled(operation_id: &str) -> bool {
+    OPERATIONS
+        .lock()
+        .map(|operations| operations.get(operation_id).copied().unwrap_or(false))
+        .unwrap_or(false)
+}
+
+/// Remove a finished operation from the registry
+pub fn finish(operation_id: &str) {
+    if let Ok(mut operations) = OPERATIONS.lock() {
+        operations.remove(operation_id);
+    }
+}
+
+/// Request cancellation of a running operation. Returns false if no operation
+/// with that id is running.
+#[tauri::command]
+pub fn cancel_operation(operation_id: String) -> Result<bool, String> {
+    let mut operations = OPERATIONS.lock().map_err(|e| e.to_string())?;
+    match operations.get_mut(&operation_id) {
+        Some(cancelled) => {
+            *cancelled = true;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
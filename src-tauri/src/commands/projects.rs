@@ -1,3 +1,7 @@
+use crate::commands::budgets::{self, BudgetStatus};
+use crate::commands::github;
+use crate::commands::memory;
+use crate::config;
 use crate::db;
 use serde::{Deserialize, Serialize};
 use std::env;
@@ -40,6 +44,90 @@ pub fn list_projects() -> Result<Vec<Project>, String> {
     Ok(projects)
 }
 
+/// Canonical form of a project path: `~` expanded, resolved to an absolute path
+/// with symlinks and `..` removed, and no trailing separator. Used for every
+/// path-taking command so DB rows and Claude Code's directory names line up.
+pub fn normalize_path(path: &str) -> Result<String, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Path is empty".to_string());
+    }
+
+    let expanded = if trimmed == "~" {
+        dirs::home_dir().ok_or("Could not find home directory")?
+    } else if let Some(rest) = trimmed
+        .strip_prefix("~/")
+        .or_else(|| trimmed.strip_prefix("~\\"))
+    {
+        dirs::home_dir()
+            .ok_or("Could not find home directory")?
+            .join(rest)
+    } else {
+        std::path::PathBuf::from(trimmed)
+    };
+
+    let canonical = fs::canonicalize(&expanded)
+        .map_err(|_| format!("Path does not exist: {}", path))?;
+    let canonical = canonical.to_string_lossy().to_string();
+
+    // Windows canonical paths carry a verbatim prefix (\\?\C:\...) that nothing else uses
+    let canonical = match canonical.strip_prefix(r"\\?\") {
+        Some(rest) if !rest.starts_with("UNC") => rest.to_string(),
+        _ => canonical,
+    };
+
+    let stripped = canonical.trim_end_matches(['/', '\\']);
+    if stripped.is_empty() || stripped.ends_with(':') {
+        // Keep the root itself ("/" or "C:\")
+        return Ok(canonical);
+    }
+
+    Ok(stripped.to_string())
+}
+
+/// Normalize a project path (see `normalize_path`)
+#[tauri::command]
+pub fn normalize_project_path(path: String) -> Result<String, String> {
+    normalize_path(&path)
+}
+
+/// Rewrite stored project paths into canonical form, skipping rows whose
+/// directory no longer exists or whose canonical path is already taken
+pub fn normalize_stored_project_paths() -> Result<usize, String> {
+    let conn = db::get_connection()?;
+
+    let mut stmt = conn
+        .prepare("SELECT id, path FROM projects")
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let rows: Vec<(String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to query projects: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut updated = 0;
+    for (id, path) in rows {
+        let Ok(normalized) = normalize_path(&path) else {
+            continue;
+        };
+        if normalized != path
+            && conn
+                .execute(
+                    "UPDATE projects SET path = ?1 WHERE id = ?2
+                     AND NOT EXISTS (SELECT 1 FROM projects WHERE path = ?1)",
+                    [&normalized, &id],
+                )
+                .unwrap_or(0)
+                > 0
+        {
+            updated += 1;
+        }
+    }
+
+    Ok(updated)
+}
+
 /// Get project for current working directory, or the most recently opened project
 #[tauri::command]
 pub fn get_current_project() -> Result<Option<Project>, String> {
@@ -104,9 +192,67 @@ pub fn get_current_project() -> Result<Option<Project>, String> {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectOverview {
+    pub project: Project,
+    pub session_count: i32,
+    pub total_minutes: i32,
+    pub tokens_this_month: i64,
+    /// Budget status, including remaining tokens, if a budget is set
+    pub budget: Option<BudgetStatus>,
+}
+
+/// Get a summary of a project's activity and budget
+#[tauri::command]
+pub fn get_project_overview(project_id: String) -> Result<ProjectOverview, String> {
+    let conn = db::get_connection()?;
+
+    let project = conn
+        .query_row(
+            "SELECT id, name, path, last_opened_at, created_at FROM projects WHERE id = ?1",
+            [&project_id],
+            |row| {
+                Ok(Project {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    path: row.get(2)?,
+                    last_opened_at: row.get(3)?,
+                    created_at: row.get(4)?,
+                })
+            },
+        )
+        .map_err(|e| format!("Project not found: {}", e))?;
+
+    let (session_count, total_minutes): (i32, f64) = conn
+        .query_row(
+            "SELECT COUNT(*), COALESCE(SUM(
+                CASE WHEN ended_at IS NOT NULL
+                THEN (julianday(ended_at) - julianday(started_at)) * 24 * 60
+                ELSE 0 END
+            ), 0)
+             FROM sessions WHERE project_id = ?1",
+            [&project_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| format!("Failed to load session totals: {}", e))?;
+
+    let tokens_this_month = budgets::month_token_usage(&conn, &project_id)?;
+    let budget = budgets::budget_status(&conn, &project_id)?;
+
+    Ok(ProjectOverview {
+        project,
+        session_count,
+        total_minutes: total_minutes.round() as i32,
+        tokens_this_month,
+        budget,
+    })
+}
+
 /// Initialize a new project at the given path
 #[tauri::command]
 pub fn init_project(path: String) -> Result<Project, String> {
+    let path = normalize_path(&path)?;
     let project_path = Path::new(&path);
 
     // Validate path exists
@@ -114,31 +260,12 @@ pub fn init_project(path: String) -> Result<Project, String> {
         return Err(format!("Path does not exist: {}", path));
     }
 
-    // Check if already initialized
     let lumen_dir = project_path.join(".lumen");
-    if lumen_dir.exists() {
-        // Check if already in database
-        let conn = db::get_connection()?;
-        let mut stmt = conn
-            .prepare("SELECT id, name, path, last_opened_at, created_at FROM projects WHERE path = ?1")
-            .map_err(|e| format!("Failed to prepare query: {}", e))?;
-
-        if let Ok(project) = stmt.query_row([&path], |row| {
-            Ok(Project {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                path: row.get(2)?,
-                last_opened_at: row.get(3)?,
-                created_at: row.get(4)?,
-            })
-        }) {
-            // Update last_opened_at and return existing project
-            let _ = conn.execute(
-                "UPDATE projects SET last_opened_at = ?1 WHERE id = ?2",
-                [&chrono::Utc::now().to_rfc3339(), &project.id],
-            );
-            return Ok(project);
-        }
+
+    // Reuse the project if this directory is already registered, under any path form
+    let existing = find_colliding_project(&path)?.map(|c| c.existing_project);
+    if let Some(project) = existing.clone().filter(|_| lumen_dir.exists()) {
+        return touch_project(project);
     }
 
     // Extract project name from path
@@ -174,6 +301,10 @@ pub fn init_project(path: String) -> Result<Project, String> {
             .map_err(|e| format!("Failed to create memory.json: {}", e))?;
     }
 
+    if let Some(project) = existing {
+        return touch_project(project);
+    }
+
     // Generate project ID
     let id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
@@ -197,14 +328,178 @@ pub fn init_project(path: String) -> Result<Project, String> {
     Ok(project)
 }
 
+/// Update a project's last_opened_at and return it
+fn touch_project(mut project: Project) -> Result<Project, String> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let conn = db::get_connection()?;
+    conn.execute(
+        "UPDATE projects SET last_opened_at = ?1 WHERE id = ?2",
+        [&now, &project.id],
+    )
+    .map_err(|e| format!("Failed to update project: {}", e))?;
+
+    project.last_opened_at = Some(now);
+    Ok(project)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectPathCollision {
+    /// The path that was asked about, after normalization
+    pub requested_path: String,
+    /// The project already registered for that directory
+    pub existing_project: Project,
+    /// "samePath", "equivalentPath" (stored under another textual form, e.g. via a
+    /// symlink or with a trailing slash) or "sameDirectory" (e.g. differing case on
+    /// a case-insensitive filesystem)
+    pub kind: String,
+}
+
+/// Whether two existing paths point at the same directory
+#[cfg(unix)]
+fn is_same_directory(a: &str, b: &str) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+/// Whether two existing paths point at the same directory
+#[cfg(not(unix))]
+fn is_same_directory(a: &str, b: &str) -> bool {
+    // NTFS is case-insensitive by default
+    a.eq_ignore_ascii_case(b)
+}
+
+/// Find the registered project for a normalized path, however its path was stored
+fn find_colliding_project(path: &str) -> Result<Option<ProjectPathCollision>, String> {
+    let projects = list_projects()?;
+
+    let found = projects
+        .iter()
+        .find(|p| p.path == path)
+        .map(|p| (p, "samePath"))
+        .or_else(|| {
+            projects
+                .iter()
+                .find(|p| normalize_path(&p.path).is_ok_and(|n| n == path))
+                .map(|p| (p, "equivalentPath"))
+        })
+        .or_else(|| {
+            projects
+                .iter()
+                .find(|p| is_same_directory(&p.path, path))
+                .map(|p| (p, "sameDirectory"))
+        });
+
+    Ok(found.map(|(project, kind)| ProjectPathCollision {
+        requested_path: path.to_string(),
+        existing_project: project.clone(),
+        kind: kind.to_string(),
+    }))
+}
+
+/// Check whether a directory is already registered as a project, possibly under a
+/// different path (symlink, case, trailing slash). Call before `init_project` to
+/// offer reusing the existing project; `init_project` itself never creates a duplicate.
+#[tauri::command]
+pub fn check_project_path_collision(path: String) -> Result<Option<ProjectPathCollision>, String> {
+    let path = normalize_path(&path)?;
+    find_colliding_project(&path)
+}
+
+/// Bring a project's .lumen directory up to the current layout: create missing
+/// `sessions/` and `snapshots/` directories and `memory.json` (backing up a corrupt
+/// one first), and restrict the directories to the owner on Unix.
+/// Returns a description of each repair made.
+#[tauri::command]
+pub fn repair_project_layout(project_path: String) -> Result<Vec<String>, String> {
+    let project_path = normalize_path(&project_path)?;
+    let lumen_dir = Path::new(&project_path).join(".lumen");
+    let mut repairs = Vec::new();
+
+    let dirs = [
+        (lumen_dir.clone(), ".lumen"),
+        (lumen_dir.join("sessions"), ".lumen/sessions"),
+        (lumen_dir.join("snapshots"), ".lumen/snapshots"),
+    ];
+    for (dir, name) in &dirs {
+        if !dir.is_dir() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", name, e))?;
+            repairs.push(format!("Created {}/", name));
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(dir)
+                .map_err(|e| format!("Failed to read {}: {}", name, e))?
+                .permissions()
+                .mode()
+                & 0o777;
+            if mode != 0o700 {
+                fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+                    .map_err(|e| format!("Failed to set permissions on {}: {}", name, e))?;
+                repairs.push(format!("Set {}/ permissions to 700 (were {:o})", name, mode));
+            }
+        }
+    }
+
+    let memory_path = lumen_dir.join("memory.json");
+    let memory_status = if memory_path.exists() {
+        fs::read_to_string(&memory_path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| memory::check_memory_json(&content).map_err(|e| e.to_string()))
+            .err()
+    } else {
+        None
+    };
+    if let Some(error) = &memory_status {
+        let backup_name = format!(
+            "memory.json.corrupt-{}",
+            chrono::Utc::now().format("%Y%m%d%H%M%S")
+        );
+        fs::rename(&memory_path, lumen_dir.join(&backup_name))
+            .map_err(|e| format!("Failed to back up memory.json: {}", e))?;
+        repairs.push(format!(
+            "Moved corrupt memory.json to .lumen/{} ({})",
+            backup_name, error
+        ));
+    }
+    if !memory_path.exists() {
+        fs::write(&memory_path, r#"{"items":{}}"#)
+            .map_err(|e| format!("Failed to create memory.json: {}", e))?;
+        repairs.push("Created .lumen/memory.json".to_string());
+    }
+
+    Ok(repairs)
+}
+
 /// Delete a project from the database (does not delete files)
 #[tauri::command]
 pub fn delete_project(project_id: String) -> Result<(), String> {
     let conn = db::get_connection()?;
 
+    conn.execute(
+        "DELETE FROM session_events WHERE session_id IN (SELECT id FROM sessions WHERE project_id = ?1)",
+        [&project_id],
+    )
+    .map_err(|e| format!("Failed to delete session events: {}", e))?;
+
+    conn.execute(
+        "DELETE FROM session_notes WHERE session_id IN (SELECT id FROM sessions WHERE project_id = ?1)",
+        [&project_id],
+    )
+    .map_err(|e| format!("Failed to delete session notes: {}", e))?;
+
     conn.execute("DELETE FROM sessions WHERE project_id = ?1", [&project_id])
         .map_err(|e| format!("Failed to delete sessions: {}", e))?;
 
+    conn.execute("DELETE FROM project_budgets WHERE project_id = ?1", [&project_id])
+        .map_err(|e| format!("Failed to delete budget: {}", e))?;
+
     conn.execute("DELETE FROM projects WHERE id = ?1", [&project_id])
         .map_err(|e| format!("Failed to delete project: {}", e))?;
 
@@ -377,9 +672,82 @@ pub fn list_directory(path: String) -> Result<Vec<FileEntry>, String> {
     Ok(entries)
 }
 
+/// How much of a file is inspected when sniffing binary vs text (same as git)
+const BINARY_SNIFF_LEN: usize = 8000;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTypeInfo {
+    pub is_binary: bool,
+    /// "ascii", "utf-8", "utf-8-bom", "utf-16le", "utf-16be", "latin-1" or "binary"
+    pub encoding: String,
+}
+
+/// Guess the text encoding of a file prefix, or None if it looks binary.
+/// Uses libgit2's heuristic: any NUL byte, or more than 1 in 128 bytes
+/// non-printable, means binary (UTF-16 is recognized by its BOM first).
+pub fn detect_text_encoding(prefix: &[u8]) -> Option<&'static str> {
+    if prefix.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return Some("utf-8-bom");
+    }
+    if prefix.starts_with(&[0xFF, 0xFE]) {
+        return Some("utf-16le");
+    }
+    if prefix.starts_with(&[0xFE, 0xFF]) {
+        return Some("utf-16be");
+    }
+
+    let mut printable = 0usize;
+    let mut nonprintable = 0usize;
+    for &byte in prefix {
+        match byte {
+            0 => return None,
+            // DEL and control characters other than BS, TAB, LF, FF, CR, ESC
+            0x7F => nonprintable += 1,
+            b if b < 0x20 && !matches!(b, 0x08 | b'\t' | b'\n' | 0x0C | b'\r' | 0x1B) => nonprintable += 1,
+            _ => printable += 1,
+        }
+    }
+    if (printable >> 7) < nonprintable {
+        return None;
+    }
+
+    if prefix.is_ascii() {
+        return Some("ascii");
+    }
+
+    // A multi-byte character may be cut off at the end of the prefix
+    match std::str::from_utf8(prefix) {
+        Ok(_) => Some("utf-8"),
+        Err(e) if e.error_len().is_none() => Some("utf-8"),
+        Err(_) => Some("latin-1"),
+    }
+}
+
+/// Check whether a file is binary (unsafe to render as text) and guess its encoding
+#[tauri::command]
+pub fn is_binary_file(path: String) -> Result<FileTypeInfo, String> {
+    use std::io::Read;
+
+    let mut file = fs::File::open(&path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut prefix = Vec::with_capacity(BINARY_SNIFF_LEN);
+    file.by_ref()
+        .take(BINARY_SNIFF_LEN as u64)
+        .read_to_end(&mut prefix)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let encoding = detect_text_encoding(&prefix);
+
+    Ok(FileTypeInfo {
+        is_binary: encoding.is_none(),
+        encoding: encoding.unwrap_or("binary").to_string(),
+    })
+}
+
 /// Read CLAUDE.md from a project
 #[tauri::command]
 pub fn read_claude_md(project_path: String) -> Result<Option<String>, String> {
+    let project_path = normalize_path(&project_path)?;
     let path = Path::new(&project_path).join("CLAUDE.md");
 
     if !path.exists() {
@@ -392,9 +760,228 @@ pub fn read_claude_md(project_path: String) -> Result<Option<String>, String> {
     Ok(Some(content))
 }
 
+/// README file names in order of preference, with their format
+const README_CANDIDATES: &[(&str, &str)] = &[
+    ("README.md", "markdown"),
+    ("README.markdown", "markdown"),
+    ("README.rst", "rst"),
+    ("README.txt", "text"),
+    ("README", "text"),
+];
+
+/// Largest README returned to the overview; longer ones are truncated
+const README_MAX_BYTES: usize = 256 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectReadme {
+    pub file_name: String,
+    /// "markdown", "rst" or "text"
+    pub format: String,
+    pub content: String,
+    /// Whether the content was cut off at the size cap
+    pub truncated: bool,
+}
+
+/// Find the README at the project root (README.md, README.rst, README, ...,
+/// matched case-insensitively) and return its content, or None if there isn't one
+#[tauri::command]
+pub fn get_project_readme(project_path: String) -> Result<Option<ProjectReadme>, String> {
+    let project_path = normalize_path(&project_path)?;
+    let root = Path::new(&project_path);
+
+    let entries: Vec<String> = fs::read_dir(root)
+        .map_err(|e| format!("Failed to read project directory: {}", e))?
+        .flatten()
+        .filter(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+
+    let found = README_CANDIDATES.iter().find_map(|(candidate, format)| {
+        entries
+            .iter()
+            .find(|name| name.eq_ignore_ascii_case(candidate))
+            .map(|name| (name.clone(), *format))
+    });
+    let Some((file_name, format)) = found else {
+        return Ok(None);
+    };
+
+    let bytes = fs::read(root.join(&file_name))
+        .map_err(|e| format!("Failed to read {}: {}", file_name, e))?;
+    let truncated = bytes.len() > README_MAX_BYTES;
+    let bytes = &bytes[..bytes.len().min(README_MAX_BYTES)];
+
+    // The cap can split a multi-byte character; drop the partial one
+    let content = match std::str::from_utf8(bytes) {
+        Ok(content) => content.to_string(),
+        Err(e) if truncated && e.error_len().is_none() => {
+            String::from_utf8_lossy(&bytes[..e.valid_up_to()]).to_string()
+        }
+        Err(_) => String::from_utf8_lossy(bytes).to_string(),
+    };
+
+    Ok(Some(ProjectReadme {
+        file_name,
+        format: format.to_string(),
+        content,
+        truncated,
+    }))
+}
+
+/// Sections scaffolded by `generate_claude_md`, used as the lint checklist
+const CLAUDE_MD_SECTIONS: &[&str] = &[
+    "Tech Stack",
+    "Project Guidelines",
+    "Architecture",
+    "Key Decisions",
+    "File Structure",
+    "Development Notes",
+    "Conventions",
+];
+
+/// CLAUDE.md is loaded into every prompt; past this it's costing more than it helps
+const CLAUDE_MD_MAX_LINES: usize = 300;
+
+/// Token prefixes that suggest a credential was pasted into CLAUDE.md
+const CLAUDE_MD_SECRET_PREFIXES: &[&str] = &[
+    "-----BEGIN",
+    "sk-ant-",
+    "sk-proj-",
+    "ghp_",
+    "github_pat_",
+    "AKIA",
+    "xoxb-",
+];
+
+/// Assignments (compared lowercase, without spaces) that suggest a credential
+const CLAUDE_MD_SECRET_ASSIGNMENTS: &[&str] = &["password=", "api_key=", "apikey=", "secret=", "token="];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdSectionCheck {
+    pub name: String,
+    /// "present", "empty" or "missing"
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdLint {
+    pub exists: bool,
+    pub line_count: usize,
+    pub sections: Vec<ClaudeMdSectionCheck>,
+    pub warnings: Vec<String>,
+}
+
+/// Strip HTML comments (the scaffold's placeholders) and whitespace
+fn strip_placeholder_comments(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("<!--") {
+        result.push_str(&rest[..start]);
+        match rest[start..].find("-->") {
+            Some(end) => rest = &rest[start + end + 3..],
+            None => {
+                rest = "";
+                break;
+            }
+        }
+    }
+    result.push_str(rest);
+    result.trim().to_string()
+}
+
+/// Check CLAUDE.md for the recommended sections and common problems
+#[tauri::command]
+pub fn lint_claude_md(project_path: String) -> Result<ClaudeMdLint, String> {
+    let project_path = normalize_path(&project_path)?;
+    let path = Path::new(&project_path).join("CLAUDE.md");
+
+    if !path.exists() {
+        return Ok(ClaudeMdLint {
+            exists: false,
+            line_count: 0,
+            sections: CLAUDE_MD_SECTIONS
+                .iter()
+                .map(|name| ClaudeMdSectionCheck {
+                    name: name.to_string(),
+                    status: "missing".to_string(),
+                })
+                .collect(),
+            warnings: vec!["No CLAUDE.md found".to_string()],
+        });
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read CLAUDE.md: {}", e))?;
+
+    // Split into `## ` sections (deeper headings belong to their parent section)
+    let mut section_bodies: Vec<(String, String)> = Vec::new();
+    for line in content.lines() {
+        if let Some(title) = line.strip_prefix("## ") {
+            section_bodies.push((title.trim().to_lowercase(), String::new()));
+        } else if line.starts_with("# ") {
+            continue;
+        } else if let Some((_, body)) = section_bodies.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+
+    let sections = CLAUDE_MD_SECTIONS
+        .iter()
+        .map(|name| {
+            let status = match section_bodies
+                .iter()
+                .find(|(title, _)| *title == name.to_lowercase())
+            {
+                Some((_, body)) if strip_placeholder_comments(body).is_empty() => "empty",
+                Some(_) => "present",
+                None => "missing",
+            };
+            ClaudeMdSectionCheck {
+                name: name.to_string(),
+                status: status.to_string(),
+            }
+        })
+        .collect();
+
+    let line_count = content.lines().count();
+    let mut warnings = Vec::new();
+
+    if line_count > CLAUDE_MD_MAX_LINES {
+        warnings.push(format!(
+            "CLAUDE.md is {} lines; consider trimming it below {} lines to save context",
+            line_count, CLAUDE_MD_MAX_LINES
+        ));
+    }
+
+    for (i, line) in content.lines().enumerate() {
+        let compact: String = line.to_lowercase().split_whitespace().collect();
+        let has_prefix = line.split_whitespace().any(|word| {
+            let word = word.trim_matches(|c| c == '`' || c == '"' || c == '\'');
+            CLAUDE_MD_SECRET_PREFIXES.iter().any(|p| word.starts_with(p))
+        });
+        let has_assignment = CLAUDE_MD_SECRET_ASSIGNMENTS.iter().any(|a| compact.contains(a));
+
+        if has_prefix || has_assignment {
+            warnings.push(format!("Line {} looks like it contains a secret", i + 1));
+        }
+    }
+
+    Ok(ClaudeMdLint {
+        exists: true,
+        line_count,
+        sections,
+        warnings,
+    })
+}
+
 /// Write CLAUDE.md to a project
 #[tauri::command]
 pub fn write_claude_md(project_path: String, content: String) -> Result<(), String> {
+    let project_path = normalize_path(&project_path)?;
     let path = Path::new(&project_path).join("CLAUDE.md");
 
     fs::write(&path, content)
@@ -403,6 +990,72 @@ pub fn write_claude_md(project_path: String, content: String) -> Result<(), Stri
     Ok(())
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectFileStatus {
+    /// Path relative to the project
+    pub path: String,
+    /// "ok", "missing" or "invalid"
+    pub status: String,
+    pub error: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+/// Parse each JSON file Lumen and Claude Code keep in a project (memory, failed
+/// approaches, project config, sensitive patterns, Claude settings) and report which are malformed,
+/// with the line and column of the first error
+#[tauri::command]
+pub fn validate_project_files(project_path: String) -> Result<Vec<ProjectFileStatus>, String> {
+    let project_path = normalize_path(&project_path)?;
+    let root = Path::new(&project_path);
+
+    type Check = fn(&str) -> Result<(), serde_json::Error>;
+    let files: [(&str, Check); 6] = [
+        (".lumen/memory.json", memory::check_memory_json),
+        (".lumen/failed_approaches.json", |c| {
+            serde_json::from_str::<Vec<FailedApproach>>(c).map(|_| ())
+        }),
+        (".lumen/config.json", |c| {
+            serde_json::from_str::<config::ProjectConfig>(c).map(|_| ())
+        }),
+        (".lumen/sensitive_patterns.json", |c| {
+            serde_json::from_str::<github::SensitivePatterns>(c).map(|_| ())
+        }),
+        (".claude/settings.json", |c| {
+            serde_json::from_str::<serde_json::Value>(c).map(|_| ())
+        }),
+        (".claude/settings.local.json", |c| {
+            serde_json::from_str::<serde_json::Value>(c).map(|_| ())
+        }),
+    ];
+
+    Ok(files
+        .iter()
+        .map(|(relative, check)| {
+            let status = |status: &str, error: Option<String>, line, column| ProjectFileStatus {
+                path: relative.to_string(),
+                status: status.to_string(),
+                error,
+                line,
+                column,
+            };
+
+            let path = root.join(relative);
+            if !path.exists() {
+                return status("missing", None, None, None);
+            }
+            match fs::read_to_string(&path) {
+                Err(e) => status("invalid", Some(format!("Failed to read file: {}", e)), None, None),
+                Ok(content) => match check(&content) {
+                    Ok(()) => status("ok", None, None, None),
+                    Err(e) => status("invalid", Some(e.to_string()), Some(e.line()), Some(e.column())),
+                },
+            }
+        })
+        .collect())
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct FailedApproach {
@@ -415,6 +1068,7 @@ pub struct FailedApproach {
 /// Get failed approaches for a project
 #[tauri::command]
 pub fn get_failed_approaches(project_path: String) -> Result<Vec<FailedApproach>, String> {
+    let project_path = normalize_path(&project_path)?;
     let path = Path::new(&project_path)
         .join(".lumen")
         .join("failed_approaches.json");
@@ -439,6 +1093,7 @@ pub fn add_failed_approach(
     description: String,
     reason: Option<String>,
 ) -> Result<FailedApproach, String> {
+    let project_path = normalize_path(&project_path)?;
     let lumen_dir = Path::new(&project_path).join(".lumen");
     let path = lumen_dir.join("failed_approaches.json");
 
@@ -477,6 +1132,7 @@ pub fn add_failed_approach(
 /// Remove a failed approach
 #[tauri::command]
 pub fn remove_failed_approach(project_path: String, approach_id: String) -> Result<(), String> {
+    let project_path = normalize_path(&project_path)?;
     let path = Path::new(&project_path)
         .join(".lumen")
         .join("failed_approaches.json");
@@ -504,6 +1160,7 @@ pub fn remove_failed_approach(project_path: String, approach_id: String) -> Resu
 /// Clear all failed approaches
 #[tauri::command]
 pub fn clear_failed_approaches(project_path: String) -> Result<(), String> {
+    let project_path = normalize_path(&project_path)?;
     let path = Path::new(&project_path)
         .join(".lumen")
         .join("failed_approaches.json");
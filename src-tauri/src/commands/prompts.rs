@@ -1,5 +1,11 @@
 use crate::db;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Prompt files larger than this are skipped on import
+const MAX_PROMPT_FILE_BYTES: u64 = 64 * 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Prompt {
@@ -11,6 +17,9 @@ pub struct Prompt {
     pub usage_count: i32,
     pub last_used_at: Option<String>,
     pub created_at: String,
+    /// When the prompt was last edited; missing for prompts never edited since this was tracked
+    #[serde(default)]
+    pub updated_at: Option<String>,
 }
 
 /// List all prompts
@@ -20,27 +29,13 @@ pub fn list_prompts() -> Result<Vec<Prompt>, String> {
 
     let mut stmt = conn
         .prepare(
-            "SELECT id, name, content, tags, variables, usage_count, last_used_at, created_at
+            "SELECT id, name, content, tags, variables, usage_count, last_used_at, created_at, updated_at
              FROM prompts ORDER BY usage_count DESC, name ASC",
         )
         .map_err(|e| format!("Failed to prepare query: {}", e))?;
 
     let prompts = stmt
-        .query_map([], |row| {
-            let tags_str: String = row.get(3)?;
-            let variables_str: String = row.get(4)?;
-
-            Ok(Prompt {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                content: row.get(2)?,
-                tags: parse_json_array(&tags_str),
-                variables: parse_json_array(&variables_str),
-                usage_count: row.get(5)?,
-                last_used_at: row.get(6)?,
-                created_at: row.get(7)?,
-            })
-        })
+        .query_map([], prompt_from_row)
         .map_err(|e| format!("Failed to query prompts: {}", e))?
         .filter_map(|r| r.ok())
         .collect();
@@ -64,8 +59,8 @@ pub fn create_prompt(
     let variables_json = serde_json::to_string(&variables).unwrap_or_else(|_| "[]".to_string());
 
     conn.execute(
-        "INSERT INTO prompts (id, name, content, tags, variables, usage_count, created_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6)",
+        "INSERT INTO prompts (id, name, content, tags, variables, usage_count, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?6)",
         rusqlite::params![id, name, content, tags_json, variables_json, now],
     )
     .map_err(|e| format!("Failed to create prompt: {}", e))?;
@@ -78,7 +73,8 @@ pub fn create_prompt(
         variables,
         usage_count: 0,
         last_used_at: None,
-        created_at: now,
+        created_at: now.clone(),
+        updated_at: Some(now),
     })
 }
 
@@ -92,13 +88,14 @@ pub fn update_prompt(
     variables: Vec<String>,
 ) -> Result<Prompt, String> {
     let conn = db::get_connection()?;
+    let now = chrono::Utc::now().to_rfc3339();
 
     let tags_json = serde_json::to_string(&tags).unwrap_or_else(|_| "[]".to_string());
     let variables_json = serde_json::to_string(&variables).unwrap_or_else(|_| "[]".to_string());
 
     conn.execute(
-        "UPDATE prompts SET name = ?1, content = ?2, tags = ?3, variables = ?4 WHERE id = ?5",
-        rusqlite::params![name, content, tags_json, variables_json, id],
+        "UPDATE prompts SET name = ?1, content = ?2, tags = ?3, variables = ?4, updated_at = ?5 WHERE id = ?6",
+        rusqlite::params![name, content, tags_json, variables_json, now, id],
     )
     .map_err(|e| format!("Failed to update prompt: {}", e))?;
 
@@ -113,24 +110,10 @@ pub fn get_prompt(id: String) -> Result<Prompt, String> {
 
     let prompt = conn
         .query_row(
-            "SELECT id, name, content, tags, variables, usage_count, last_used_at, created_at
+            "SELECT id, name, content, tags, variables, usage_count, last_used_at, created_at, updated_at
              FROM prompts WHERE id = ?1",
             [&id],
-            |row| {
-                let tags_str: String = row.get(3)?;
-                let variables_str: String = row.get(4)?;
-
-                Ok(Prompt {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    content: row.get(2)?,
-                    tags: parse_json_array(&tags_str),
-                    variables: parse_json_array(&variables_str),
-                    usage_count: row.get(5)?,
-                    last_used_at: row.get(6)?,
-                    created_at: row.get(7)?,
-                })
-            },
+            prompt_from_row,
         )
         .map_err(|e| format!("Prompt not found: {}", e))?;
 
@@ -148,6 +131,70 @@ pub fn delete_prompt(id: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Map a row of `id, name, content, tags, variables, usage_count, last_used_at, created_at, updated_at`
+fn prompt_from_row(row: &rusqlite::Row) -> rusqlite::Result<Prompt> {
+    let tags_str: Option<String> = row.get(3)?;
+    let variables_str: Option<String> = row.get(4)?;
+
+    Ok(Prompt {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        content: row.get(2)?,
+        tags: parse_json_array(tags_str.as_deref().unwrap_or("[]")),
+        variables: parse_json_array(variables_str.as_deref().unwrap_or("[]")),
+        usage_count: row.get(5)?,
+        last_used_at: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+/// Prompts whose name, tags or content contain `query` (case-insensitive),
+/// name and tag matches first, then by usage
+fn search_prompts_in(conn: &rusqlite::Connection, query: &str) -> Result<Vec<Prompt>, String> {
+    let escaped = query
+        .to_lowercase()
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    let pattern = format!("%{}%", escaped);
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, content, tags, variables, usage_count, last_used_at, created_at, updated_at,
+                CASE
+                    WHEN lower(name) LIKE ?1 ESCAPE '\\' OR lower(COALESCE(tags, '')) LIKE ?1 ESCAPE '\\' THEN 0
+                    ELSE 1
+                END AS rank
+             FROM prompts
+             WHERE lower(name) LIKE ?1 ESCAPE '\\'
+                OR lower(COALESCE(tags, '')) LIKE ?1 ESCAPE '\\'
+                OR lower(content) LIKE ?1 ESCAPE '\\'
+             ORDER BY rank ASC, usage_count DESC, name ASC",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let prompts = stmt
+        .query_map([&pattern], prompt_from_row)
+        .map_err(|e| format!("Failed to search prompts: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(prompts)
+}
+
+/// Search prompts by name, tags and content. An empty query lists all prompts.
+#[tauri::command]
+pub fn search_prompts(query: String) -> Result<Vec<Prompt>, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return list_prompts();
+    }
+
+    let conn = db::get_connection()?;
+    search_prompts_in(&conn, query)
+}
+
 /// Record usage of a prompt (increment count and update last_used)
 #[tauri::command]
 pub fn use_prompt(id: String) -> Result<Prompt, String> {
@@ -163,7 +210,510 @@ pub fn use_prompt(id: String) -> Result<Prompt, String> {
     get_prompt(id)
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedPromptFile {
+    pub file: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptImportResult {
+    pub imported: usize,
+    pub updated: usize,
+    pub skipped: Vec<SkippedPromptFile>,
+}
+
+/// Find the distinct `{{variable}}` names in a prompt, in order of appearance
+fn extract_variables(content: &str) -> Vec<String> {
+    let mut variables: Vec<String> = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        if !name.is_empty() && !name.contains('\n') && !variables.iter().any(|v| v == name) {
+            variables.push(name.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+
+    variables
+}
+
+/// Replace `{{variable}}` placeholders that have a value; others are left as they are
+fn substitute_variables(content: &str, values: &HashMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        match values.get(after[..end].trim()) {
+            Some(value) => rendered.push_str(value),
+            None => rendered.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    rendered
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderedPrompt {
+    pub rendered: String,
+    /// Declared variables with no value given; their placeholders are left in
+    pub unfilled: Vec<String>,
+    /// Placeholders in the content that aren't in the prompt's variables
+    pub undeclared: Vec<String>,
+}
+
+/// Fill in placeholders in prompt content, noting declared `variables` left unfilled
+/// and placeholders that aren't declared
+fn render_content(
+    content: &str,
+    variables: &[String],
+    values: &HashMap<String, String>,
+) -> RenderedPrompt {
+    let unfilled = variables
+        .iter()
+        .filter(|v| !values.contains_key(v.as_str()))
+        .cloned()
+        .collect();
+    let undeclared = extract_variables(content)
+        .into_iter()
+        .filter(|p| !variables.contains(p))
+        .collect();
+
+    RenderedPrompt {
+        rendered: substitute_variables(content, values),
+        unfilled,
+        undeclared,
+    }
+}
+
+/// Fill in a prompt's `{{variable}}` placeholders and record its usage
+#[tauri::command]
+pub fn render_prompt(id: String, values: HashMap<String, String>) -> Result<RenderedPrompt, String> {
+    let prompt = get_prompt(id.clone())?;
+
+    let rendered = render_content(&prompt.content, &prompt.variables, &values);
+    use_prompt(id)?;
+
+    Ok(rendered)
+}
+
+/// A prompt as read from a Markdown file
+#[derive(Debug, PartialEq)]
+struct MarkdownPrompt {
+    name: Option<String>,
+    tags: Vec<String>,
+    content: String,
+}
+
+/// Read a front-matter value, unquoting JSON or single-quoted strings
+fn parse_front_matter_value(value: &str) -> String {
+    let value = value.trim();
+    if value.starts_with('"') {
+        if let Ok(parsed) = serde_json::from_str::<String>(value) {
+            return parsed;
+        }
+    }
+    if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        return value[1..value.len() - 1].to_string();
+    }
+    value.to_string()
+}
+
+/// Read `[a, b]`, `["a", "b"]` or `a, b` as a list of tags
+fn parse_front_matter_tags(value: &str) -> Vec<String> {
+    let value = value.trim();
+    if let Ok(tags) = serde_json::from_str::<Vec<String>>(value) {
+        return tags;
+    }
+    value
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(parse_front_matter_value)
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Split a Markdown prompt into its name, tags and content. The name comes from
+/// a `name:` front-matter field, or else a `# ` heading on the first line of the body.
+fn parse_prompt_markdown(text: &str) -> MarkdownPrompt {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut name = None;
+    let mut tags = Vec::new();
+    let mut body_start = 0;
+
+    let closing = match lines.first() {
+        Some(first) if first.trim_end() == "---" => {
+            lines[1..].iter().position(|l| l.trim_end() == "---")
+        }
+        _ => None,
+    };
+    if let Some(closing) = closing {
+        let mut in_tag_list = false;
+        for line in &lines[1..=closing] {
+            if in_tag_list {
+                if let Some(item) = line.trim().strip_prefix("- ") {
+                    tags.push(parse_front_matter_value(item));
+                    continue;
+                }
+                in_tag_list = false;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            match key.trim() {
+                "name" | "title" => name = Some(parse_front_matter_value(value)),
+                "tags" if value.trim().is_empty() => in_tag_list = true,
+                "tags" => tags = parse_front_matter_tags(value),
+                _ => {}
+            }
+        }
+        body_start = closing + 2;
+    }
+
+    let mut body = &lines[body_start..];
+    while body.first().is_some_and(|l| l.trim().is_empty()) {
+        body = &body[1..];
+    }
+    if name.is_none() {
+        if let Some(heading) = body.first().and_then(|l| l.strip_prefix("# ")) {
+            name = Some(heading.trim().to_string());
+            body = &body[1..];
+        }
+    }
+
+    MarkdownPrompt {
+        name: name.filter(|n| !n.is_empty()),
+        tags,
+        content: body.join("\n").trim().to_string(),
+    }
+}
+
+/// Render a prompt as Markdown with `name` and `tags` front-matter
+fn prompt_to_markdown(prompt: &Prompt) -> String {
+    format!(
+        "---\nname: {}\ntags: {}\n---\n\n{}\n",
+        serde_json::to_string(&prompt.name).unwrap_or_default(),
+        serde_json::to_string(&prompt.tags).unwrap_or_else(|_| "[]".to_string()),
+        prompt.content.trim_end()
+    )
+}
+
+/// A file name for a prompt, unique (ignoring case) among `taken`
+fn prompt_file_name(name: &str, taken: &mut HashSet<String>) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let stem = match stem.trim_matches(['-', ' ', '.']) {
+        "" => "prompt",
+        stem => stem,
+    };
+
+    let mut file = format!("{}.md", stem);
+    let mut n = 2;
+    while !taken.insert(file.to_lowercase()) {
+        file = format!("{}-{}.md", stem, n);
+        n += 1;
+    }
+    file
+}
+
+/// Import every .md/.txt file in a directory as a prompt. The name comes from
+/// front-matter or a leading heading, falling back to the file name; a prompt with
+/// the same name is updated instead of duplicated.
+/// Files that are too large, empty or not UTF-8 are skipped.
+#[tauri::command]
+pub fn import_prompts_from_dir(dir: String) -> Result<PromptImportResult, String> {
+    let entries = fs::read_dir(Path::new(&dir))
+        .map_err(|e| format!("Failed to read directory: {}", e))?;
+
+    let conn = db::get_connection()?;
+    let mut files: Vec<_> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            p.extension()
+                .map(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("txt"))
+                .unwrap_or(false)
+        })
+        .collect();
+    files.sort();
+
+    let mut result = PromptImportResult {
+        imported: 0,
+        updated: 0,
+        skipped: Vec::new(),
+    };
+
+    for path in files {
+        let file = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut skip = |reason: &str| {
+            result.skipped.push(SkippedPromptFile {
+                file: file.clone(),
+                reason: reason.to_string(),
+            })
+        };
+
+        if fs::metadata(&path).map(|m| m.len() > MAX_PROMPT_FILE_BYTES).unwrap_or(true) {
+            skip("File is too large");
+            continue;
+        }
+        let Ok(text) = fs::read_to_string(&path) else {
+            skip("File is not valid UTF-8 text");
+            continue;
+        };
+        let parsed = parse_prompt_markdown(&text);
+        if parsed.content.is_empty() {
+            skip("File is empty");
+            continue;
+        }
+
+        let name = parsed.name.unwrap_or_else(|| {
+            path.file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+        let existing = conn.query_row(
+            "SELECT id, tags FROM prompts WHERE name = ?1 ORDER BY created_at ASC LIMIT 1",
+            [&name],
+            |row| {
+                let tags: Option<String> = row.get(1)?;
+                Ok((
+                    row.get::<_, String>(0)?,
+                    parse_json_array(tags.as_deref().unwrap_or("[]")),
+                ))
+            },
+        );
+        let existing = match existing {
+            Ok(found) => Some(found),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(format!("Failed to check existing prompts: {}", e)),
+        };
+
+        let variables = extract_variables(&parsed.content);
+        match existing {
+            Some((id, existing_tags)) => {
+                // Keep the library's tags when the file doesn't set any
+                let tags = if parsed.tags.is_empty() {
+                    existing_tags
+                } else {
+                    parsed.tags
+                };
+                update_prompt(id, name, parsed.content, tags, variables)?;
+                result.updated += 1;
+            }
+            None => {
+                create_prompt(name, parsed.content, parsed.tags, variables)?;
+                result.imported += 1;
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Write every prompt to `dir` as a Markdown file with front-matter, in the
+/// format `import_prompts_from_dir` reads. Returns the number of prompts written.
+#[tauri::command]
+pub fn export_prompts_to_dir(dir: String) -> Result<usize, String> {
+    let dir = Path::new(&dir);
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let mut prompts = list_prompts()?;
+    prompts.sort_by(|a, b| a.name.cmp(&b.name).then(a.created_at.cmp(&b.created_at)));
+
+    let mut taken = HashSet::new();
+    for prompt in &prompts {
+        let file = prompt_file_name(&prompt.name, &mut taken);
+        fs::write(dir.join(&file), prompt_to_markdown(prompt))
+            .map_err(|e| format!("Failed to write {}: {}", file, e))?;
+    }
+
+    Ok(prompts.len())
+}
+
 /// Parse a JSON array string into a Vec<String>
 fn parse_json_array(json_str: &str) -> Vec<String> {
     serde_json::from_str(json_str).unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_db() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE prompts (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                content TEXT NOT NULL,
+                tags TEXT,
+                variables TEXT,
+                usage_count INTEGER DEFAULT 0,
+                last_used_at DATETIME,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME
+            );
+            INSERT INTO prompts (id, name, content, tags, variables, usage_count) VALUES
+                ('1', 'Write tests', 'Add unit tests for the REFACTOR', '[\"testing\"]', '[]', 9),
+                ('2', 'Refactor module', 'Clean up {{module}}', '[]', '[\"module\"]', 1),
+                ('3', 'Review', 'Review the diff', '[\"refactor\"]', '[]', 5),
+                ('4', 'Discount', 'Apply a 100% discount', NULL, NULL, 0);",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn ids(prompts: Vec<Prompt>) -> Vec<String> {
+        prompts.into_iter().map(|p| p.id).collect()
+    }
+
+    #[test]
+    fn test_search_ranks_name_and_tag_matches_first() {
+        let conn = test_db();
+        // Tag and name matches by usage, then the content match despite its higher usage
+        assert_eq!(
+            ids(search_prompts_in(&conn, "Refactor").unwrap()),
+            vec!["3", "2", "1"]
+        );
+        assert_eq!(ids(search_prompts_in(&conn, "TESTS").unwrap()), vec!["1"]);
+        assert!(search_prompts_in(&conn, "nothing like this")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_search_escapes_like_wildcards() {
+        let conn = test_db();
+        assert_eq!(ids(search_prompts_in(&conn, "100%").unwrap()), vec!["4"]);
+        assert_eq!(ids(search_prompts_in(&conn, "%").unwrap()), vec!["4"]);
+        assert!(search_prompts_in(&conn, "_").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_prompt_markdown_front_matter() {
+        let parsed = parse_prompt_markdown(
+            "---\nname: \"Fix: flaky test\"\ntags: [testing, 'ci']\n---\n\n# Steps\nRerun {{test}}\n",
+        );
+        assert_eq!(
+            parsed,
+            MarkdownPrompt {
+                name: Some("Fix: flaky test".to_string()),
+                tags: vec!["testing".to_string(), "ci".to_string()],
+                content: "# Steps\nRerun {{test}}".to_string(),
+            }
+        );
+
+        let parsed = parse_prompt_markdown("---\ntags:\n  - a\n  - b\n---\nBody");
+        assert_eq!(parsed.name, None);
+        assert_eq!(parsed.tags, vec!["a", "b"]);
+        assert_eq!(parsed.content, "Body");
+    }
+
+    #[test]
+    fn test_parse_prompt_markdown_heading_and_plain_text() {
+        let parsed = parse_prompt_markdown("\r\n# Review PR\r\n\r\nLook at the diff\r\n");
+        assert_eq!(parsed.name.as_deref(), Some("Review PR"));
+        assert_eq!(parsed.content, "Look at the diff");
+
+        // A heading further down is part of the content
+        let parsed = parse_prompt_markdown("Intro\n# Section");
+        assert_eq!(parsed.name, None);
+        assert_eq!(parsed.content, "Intro\n# Section");
+
+        // An unclosed fence isn't front-matter
+        assert_eq!(
+            parse_prompt_markdown("---\nname: x").content,
+            "---\nname: x"
+        );
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let prompt = Prompt {
+            id: "1".to_string(),
+            name: "Say \"hi\": now".to_string(),
+            content: "---\nHello {{name}}\n".to_string(),
+            tags: vec!["greeting, short".to_string()],
+            variables: vec!["name".to_string()],
+            usage_count: 0,
+            last_used_at: None,
+            created_at: String::new(),
+            updated_at: None,
+        };
+        let parsed = parse_prompt_markdown(&prompt_to_markdown(&prompt));
+        assert_eq!(parsed.name.as_deref(), Some(prompt.name.as_str()));
+        assert_eq!(parsed.tags, prompt.tags);
+        assert_eq!(parsed.content, prompt.content.trim());
+    }
+
+    #[test]
+    fn test_prompt_file_names_are_unique() {
+        let mut taken = HashSet::new();
+        assert_eq!(prompt_file_name("Fix bug", &mut taken), "Fix bug.md");
+        assert_eq!(prompt_file_name("fix bug", &mut taken), "fix bug-2.md");
+        assert_eq!(
+            prompt_file_name("../etc/passwd", &mut taken),
+            "etc-passwd.md"
+        );
+        assert_eq!(prompt_file_name("???", &mut taken), "prompt.md");
+    }
+
+    #[test]
+    fn test_render_content() {
+        let variables = vec!["file".to_string(), "goal".to_string()];
+        let values = HashMap::from([
+            ("file".to_string(), "main.rs".to_string()),
+            ("extra".to_string(), "unused".to_string()),
+        ]);
+
+        let rendered = render_content(
+            "Refactor {{ file }} to {{goal}}; see {{file}} and {{ticket}}",
+            &variables,
+            &values,
+        );
+        assert_eq!(
+            rendered.rendered,
+            "Refactor main.rs to {{goal}}; see main.rs and {{ticket}}"
+        );
+        assert_eq!(rendered.unfilled, vec!["goal"]);
+        assert_eq!(rendered.undeclared, vec!["ticket"]);
+    }
+
+    #[test]
+    fn test_substitute_unclosed_placeholder() {
+        let values = HashMap::from([("name".to_string(), "Ada".to_string())]);
+        assert_eq!(
+            substitute_variables("Hi {{name}}, {{name", &values),
+            "Hi Ada, {{name"
+        );
+        assert_eq!(substitute_variables("{{}} {{ }}", &values), "{{}} {{ }}");
+        assert_eq!(extract_variables("Hi {{name}}, {{name"), vec!["name"]);
+        assert!(extract_variables("{{ unclosed").is_empty());
+    }
+}
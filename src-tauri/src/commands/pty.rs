@@ -1,16 +1,111 @@
-use crate::pty::PTY_MANAGER;
+use crate::commands::projects;
+use crate::config;
+use crate::db;
+use crate::pty::semantic_parser::{BlockType, SemanticBlockPrefs};
+use crate::pty::capture::{self, CapturedOutput};
+use crate::pty::{self, recorder, replay, PtyStatus, ShellInfo, SpawnOptions, PTY_MANAGER};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
 use tauri::AppHandle;
 
+/// Spawn a terminal running `shell` with `args` (the platform's default shell when
+/// omitted), optionally typing the project's startup commands into it.
+/// `env` adds variables to the inherited environment; values (e.g. API keys) are
+/// only handed to the shell process and are not persisted anywhere.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn pty_spawn(
     app_handle: AppHandle,
     id: String,
     cols: u16,
     rows: u16,
     cwd: Option<String>,
+    run_startup_commands: Option<bool>,
+    shell: Option<String>,
+    args: Option<Vec<String>>,
+    env: Option<HashMap<String, String>>,
 ) -> Result<(), String> {
+    let shell = pty::resolve_shell(shell, args)?;
+    // Sessions are looked up by the project's normalized path
+    let cwd = cwd.map(|dir| projects::normalize_path(&dir)).transpose()?;
+
+    let env = env.unwrap_or_default();
+    if let Some(key) = env
+        .keys()
+        .find(|k| k.is_empty() || k.contains(['=', '\0']))
+    {
+        return Err(format!("Invalid environment variable name: {:?}", key));
+    }
+    if env.values().any(|v| v.contains('\0')) {
+        return Err("Environment variable values cannot contain NUL".to_string());
+    }
+
+    // Opt-in per spawn, and only for terminals opened in a project
+    let startup_commands = match cwd.as_deref() {
+        Some(dir) if run_startup_commands.unwrap_or(false) => {
+            startup_commands_for(Path::new(dir))
+        }
+        _ => Vec::new(),
+    };
+
     let mut manager = PTY_MANAGER.lock().map_err(|e| e.to_string())?;
-    manager.spawn(id, app_handle, cols, rows, cwd)
+    manager.spawn(
+        id,
+        app_handle,
+        cols,
+        rows,
+        SpawnOptions {
+            cwd,
+            shell: Some(shell),
+            startup_commands,
+            env,
+        },
+    )
+}
+
+/// The project's startup commands, skipping blank and multi-line entries
+/// (the config file may have been edited by hand)
+fn startup_commands_for(project_path: &Path) -> Vec<String> {
+    config::load_project_config(project_path)
+        .map(|c| c.pty_startup_commands)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty() && !c.contains(['\n', '\r']))
+        .collect()
+}
+
+/// Get the commands typed into new terminals for a project
+#[tauri::command]
+pub fn get_pty_startup_commands(project_path: String) -> Result<Vec<String>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    Ok(config::load_project_config(Path::new(&project_path))?.pty_startup_commands)
+}
+
+/// Set the commands typed into new terminals for a project (when `pty_spawn` is
+/// asked to run them). Each command must be a single line.
+#[tauri::command]
+pub fn set_pty_startup_commands(
+    project_path: String,
+    commands: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let commands: Vec<String> = commands
+        .into_iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect();
+    if commands.iter().any(|c| c.contains(['\n', '\r'])) {
+        return Err("Startup commands must be a single line each".to_string());
+    }
+
+    let path = Path::new(&project_path);
+    let mut config = config::load_project_config(path)?;
+    config.pty_startup_commands = commands;
+    config::save_project_config(path, &config)?;
+
+    Ok(config.pty_startup_commands)
 }
 
 #[tauri::command]
@@ -30,3 +125,129 @@ pub fn pty_kill(id: String) -> Result<(), String> {
     let mut manager = PTY_MANAGER.lock().map_err(|e| e.to_string())?;
     manager.kill(&id)
 }
+
+/// List the backend's terminals and whether each shell is still running, so the
+/// frontend can reconcile its tabs (e.g. after a reload)
+#[tauri::command]
+pub fn pty_list() -> Result<Vec<PtyStatus>, String> {
+    let manager = PTY_MANAGER.lock().map_err(|e| e.to_string())?;
+    Ok(manager.statuses())
+}
+
+/// Recent working directories of a terminal (from shell OSC 7 reports), most recent first
+#[tauri::command]
+pub fn pty_cwd_history(id: String) -> Result<Vec<String>, String> {
+    let manager = PTY_MANAGER.lock().map_err(|e| e.to_string())?;
+    manager.cwd_history(&id)
+}
+
+/// Run a command in a throwaway PTY and return its output and exit code once it finishes.
+/// The command is killed after `timeout_ms` (default 30s).
+#[tauri::command]
+pub fn pty_run_and_capture(
+    cwd: Option<String>,
+    command: String,
+    timeout_ms: Option<u64>,
+) -> Result<CapturedOutput, String> {
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(30_000));
+    capture::run_and_capture(cwd.as_deref(), &command, timeout)
+}
+
+/// Get the shell (and arguments) new terminals will launch
+#[tauri::command]
+pub fn detect_default_shell() -> ShellInfo {
+    pty::detect_default_shell()
+}
+
+/// Get the semantic block collapse preferences
+#[tauri::command]
+pub fn get_semantic_block_prefs() -> Result<SemanticBlockPrefs, String> {
+    Ok(config::load_config()?.semantic_blocks)
+}
+
+/// Set which semantic block types are collapsed by default (applies to newly spawned PTYs)
+#[tauri::command]
+pub fn set_semantic_block_prefs(
+    collapse_types: Vec<String>,
+    line_threshold: usize,
+) -> Result<SemanticBlockPrefs, String> {
+    if let Some(unknown) = collapse_types
+        .iter()
+        .find(|t| !BlockType::KINDS.contains(&t.as_str()))
+    {
+        return Err(format!("Unknown block type: {}", unknown));
+    }
+
+    let mut config = config::load_config()?;
+    config.semantic_blocks = SemanticBlockPrefs {
+        collapse_types,
+        line_threshold,
+    };
+    config::save_config(&config)?;
+
+    Ok(config.semantic_blocks)
+}
+
+/// Look up the project path a session belongs to
+fn session_project_path(session_id: &str) -> Result<String, String> {
+    let conn = db::get_connection()?;
+
+    conn.query_row(
+        "SELECT p.path FROM sessions s JOIN projects p ON s.project_id = p.id WHERE s.id = ?1",
+        [session_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| format!("Session not found: {}", e))
+}
+
+/// Replay a session's recorded terminal output at original (or scaled) timing.
+/// Returns a replay id; output arrives on `replay-output-{id}`.
+#[tauri::command]
+pub fn replay_session(
+    app_handle: AppHandle,
+    session_id: String,
+    speed: Option<f64>,
+) -> Result<String, String> {
+    let project_path = session_project_path(&session_id)?;
+
+    let recording = recorder::recording_path(Path::new(&project_path), &session_id);
+    if !recording.exists() {
+        return Err("No terminal recording found for this session".to_string());
+    }
+
+    replay::start_replay(app_handle, &recording, speed.unwrap_or(1.0))
+}
+
+/// Pause a running replay
+#[tauri::command]
+pub fn pause_replay(replay_id: String) -> Result<(), String> {
+    replay::set_paused(&replay_id, true)
+}
+
+/// Resume a paused replay
+#[tauri::command]
+pub fn resume_replay(replay_id: String) -> Result<(), String> {
+    replay::set_paused(&replay_id, false)
+}
+
+/// Stop a replay
+#[tauri::command]
+pub fn stop_replay(replay_id: String) -> Result<(), String> {
+    replay::stop(&replay_id)
+}
+
+/// Get the semantic blocks recorded during a session as a JSON array
+#[tauri::command]
+pub fn export_semantic_blocks(session_id: String) -> Result<serde_json::Value, String> {
+    let project_path = session_project_path(&session_id)?;
+
+    let path = recorder::blocks_path(Path::new(&project_path), &session_id);
+    if !path.exists() {
+        return Err("No semantic blocks recorded for this session".to_string());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read semantic blocks: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse semantic blocks: {}", e))
+}
@@ -1,7 +1,14 @@
+use crate::commands::projects;
+use crate::pty::capture;
+use crate::pty::semantic_parser::strip_ansi_codes;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How long a test run may take before it's killed
+const DEFAULT_TEST_TIMEOUT: Duration = Duration::from_secs(10 * 60);
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -20,14 +27,87 @@ pub struct QuickCommand {
     pub description: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCommand {
+    pub command: String,
+    /// Marker file the command was derived from, e.g. "package.json"
+    pub source: String,
+    /// "high" when the project explicitly configures tests, "medium" when inferred from its type
+    pub confidence: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestRunResult {
+    pub command: String,
+    pub success: bool,
+    /// Test counts, when the framework's summary could be parsed
+    pub passed: Option<u32>,
+    pub failed: Option<u32>,
+    /// "summary" if counts came from the output, "exitCode" if only the exit code is known
+    pub result_source: String,
+    pub exit_code: Option<u32>,
+    pub timed_out: bool,
+    pub duration_ms: u64,
+    pub output: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryPoint {
+    /// Entry file relative to the project root
+    pub path: String,
+    /// How to run it
+    pub command: String,
+    /// "high", "medium" or "low"
+    pub confidence: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct PackageJson {
+    main: Option<String>,
     scripts: Option<HashMap<String, String>>,
 }
 
+/// What `npm init` puts in the test script when none was configured
+const NPM_PLACEHOLDER_TEST: &str = "echo \"Error: no test specified\" && exit 1";
+
+/// Detect a project's types ("node", "rust", "python", "go") from its marker files
+fn detect_project_types(path: &Path) -> Vec<&'static str> {
+    let mut types = Vec::new();
+    if path.join("package.json").exists() {
+        types.push("node");
+    }
+    if path.join("Cargo.toml").exists() {
+        types.push("rust");
+    }
+    if path.join("requirements.txt").exists() || path.join("pyproject.toml").exists() {
+        types.push("python");
+    }
+    if path.join("go.mod").exists() {
+        types.push("go");
+    }
+    types
+}
+
+/// The package manager a Node project uses, judged by its lockfile
+fn node_package_manager(path: &Path) -> &'static str {
+    if path.join("pnpm-lock.yaml").exists() {
+        "pnpm"
+    } else if path.join("yarn.lock").exists() {
+        "yarn"
+    } else if path.join("bun.lockb").exists() {
+        "bun"
+    } else {
+        "npm"
+    }
+}
+
 /// Read npm scripts from package.json
 #[tauri::command]
 pub fn get_package_scripts(project_path: String) -> Result<Vec<PackageScript>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let pkg_path = Path::new(&project_path).join("package.json");
 
     if !pkg_path.exists() {
@@ -53,13 +133,13 @@ pub fn get_package_scripts(project_path: String) -> Result<Vec<PackageScript>, S
 /// Get suggested quick commands based on project type
 #[tauri::command]
 pub fn get_quick_commands(project_path: String) -> Result<Vec<QuickCommand>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
     let path = Path::new(&project_path);
+    let types = detect_project_types(path);
     let mut commands = Vec::new();
 
-    // Detect project type and suggest commands
-
     // Node.js / npm
-    if path.join("package.json").exists() {
+    if types.contains(&"node") {
         commands.push(QuickCommand {
             id: "npm-install".to_string(),
             name: "Install".to_string(),
@@ -78,7 +158,7 @@ pub fn get_quick_commands(project_path: String) -> Result<Vec<QuickCommand>, Str
     }
 
     // Cargo / Rust
-    if path.join("Cargo.toml").exists() {
+    if types.contains(&"rust") {
         commands.push(QuickCommand {
             id: "cargo-build".to_string(),
             name: "Build".to_string(),
@@ -130,7 +210,7 @@ pub fn get_quick_commands(project_path: String) -> Result<Vec<QuickCommand>, Str
     });
 
     // Python
-    if path.join("requirements.txt").exists() || path.join("pyproject.toml").exists() {
+    if types.contains(&"python") {
         commands.push(QuickCommand {
             id: "pip-install".to_string(),
             name: "Install".to_string(),
@@ -142,3 +222,352 @@ pub fn get_quick_commands(project_path: String) -> Result<Vec<QuickCommand>, Str
 
     Ok(commands)
 }
+
+/// Detect the most likely command for running a project's tests
+#[tauri::command]
+pub fn detect_test_command(project_path: String) -> Result<Option<TestCommand>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+
+    let test_command = |command: &str, source: &str, confidence: &str| TestCommand {
+        command: command.to_string(),
+        source: source.to_string(),
+        confidence: confidence.to_string(),
+    };
+
+    for project_type in detect_project_types(path) {
+        match project_type {
+            "node" => {
+                let has_test_script = fs::read_to_string(path.join("package.json"))
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<PackageJson>(&content).ok())
+                    .and_then(|pkg| pkg.scripts)
+                    .and_then(|scripts| scripts.get("test").cloned())
+                    .is_some_and(|script| script.trim() != NPM_PLACEHOLDER_TEST);
+                if has_test_script {
+                    let command = format!("{} test", node_package_manager(path));
+                    return Ok(Some(test_command(&command, "package.json", "high")));
+                }
+            }
+            "rust" => return Ok(Some(test_command("cargo test", "Cargo.toml", "high"))),
+            "go" => return Ok(Some(test_command("go test ./...", "go.mod", "high"))),
+            "python" => {
+                let pyproject = fs::read_to_string(path.join("pyproject.toml")).unwrap_or_default();
+                if path.join("pytest.ini").exists() {
+                    return Ok(Some(test_command("pytest", "pytest.ini", "high")));
+                }
+                if pyproject.contains("[tool.pytest") {
+                    return Ok(Some(test_command("pytest", "pyproject.toml", "high")));
+                }
+                if path.join("conftest.py").exists() {
+                    return Ok(Some(test_command("pytest", "conftest.py", "high")));
+                }
+                let source = if pyproject.is_empty() {
+                    "requirements.txt"
+                } else {
+                    "pyproject.toml"
+                };
+                return Ok(Some(test_command("pytest", source, "medium")));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(None)
+}
+
+/// Names of the immediate subdirectories (or files with `ext`, by stem) of a directory, sorted
+fn list_names(dir: &Path, ext: Option<&str>) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| match ext {
+                    Some(ext) => p.is_file() && p.extension().is_some_and(|e| e == ext),
+                    None => p.is_dir(),
+                })
+                .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Find the likely entry points of a project and how to run them, most likely first
+#[tauri::command]
+pub fn detect_entry_point(project_path: String) -> Result<Vec<EntryPoint>, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+
+    let mut candidates = Vec::new();
+    let mut add = |file: &str, command: String, confidence: &str| {
+        candidates.push(EntryPoint {
+            path: file.to_string(),
+            command,
+            confidence: confidence.to_string(),
+        });
+    };
+
+    for project_type in detect_project_types(path) {
+        match project_type {
+            "node" => {
+                let pkg = fs::read_to_string(path.join("package.json"))
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<PackageJson>(&content).ok());
+                let scripts = pkg.as_ref().and_then(|p| p.scripts.clone()).unwrap_or_default();
+                let manager = node_package_manager(path);
+
+                if scripts.contains_key("start") {
+                    add("package.json", format!("{} start", manager), "high");
+                }
+                if scripts.contains_key("dev") {
+                    add("package.json", format!("{} run dev", manager), "medium");
+                }
+                if let Some(main) = pkg.and_then(|p| p.main).filter(|m| path.join(m).is_file()) {
+                    add(&main, format!("node {}", main), "high");
+                } else if path.join("index.js").is_file() {
+                    add("index.js", "node index.js".to_string(), "low");
+                }
+            }
+            "rust" => {
+                if path.join("src/main.rs").is_file() {
+                    add("src/main.rs", "cargo run".to_string(), "high");
+                }
+                for bin in list_names(&path.join("src/bin"), Some("rs")) {
+                    add(
+                        &format!("src/bin/{}.rs", bin),
+                        format!("cargo run --bin {}", bin),
+                        "medium",
+                    );
+                }
+            }
+            "python" => {
+                let scripts = [("main.py", "high"), ("app.py", "medium"), ("__main__.py", "medium")];
+                for (file, confidence) in scripts {
+                    if path.join(file).is_file() {
+                        add(file, format!("python {}", file), confidence);
+                    }
+                }
+                if path.join("manage.py").is_file() {
+                    add("manage.py", "python manage.py runserver".to_string(), "medium");
+                }
+                for package in list_names(&path.join("src"), None)
+                    .into_iter()
+                    .filter(|p| path.join("src").join(p).join("__main__.py").is_file())
+                {
+                    add(
+                        &format!("src/{}/__main__.py", package),
+                        format!("python -m {}", package),
+                        "medium",
+                    );
+                }
+            }
+            "go" => {
+                if path.join("main.go").is_file() {
+                    add("main.go", "go run .".to_string(), "high");
+                }
+                for cmd in list_names(&path.join("cmd"), None)
+                    .into_iter()
+                    .filter(|c| path.join("cmd").join(c).join("main.go").is_file())
+                {
+                    add(&format!("cmd/{}/main.go", cmd), format!("go run ./cmd/{}", cmd), "medium");
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Stable sort keeps each project type's own ordering within a confidence level
+    let rank = |confidence: &str| match confidence {
+        "high" => 0,
+        "medium" => 1,
+        _ => 2,
+    };
+    candidates.sort_by_key(|c| rank(&c.confidence));
+
+    Ok(candidates)
+}
+
+/// Sum the "<n> passed" / "<n> failed" counts in a summary line
+fn summary_counts(line: &str) -> (Option<u32>, Option<u32>) {
+    let words: Vec<&str> = line
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '|' | '(' | ')' | '='))
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut passed = None;
+    let mut failed = None;
+    for pair in words.windows(2) {
+        let Ok(count) = pair[0].parse::<u32>() else {
+            continue;
+        };
+        match pair[1].trim_end_matches(['.', ':']) {
+            "passed" | "passing" => *passed.get_or_insert(0) += count,
+            "failed" | "failing" => *failed.get_or_insert(0) += count,
+            _ => {}
+        }
+    }
+    (passed, failed)
+}
+
+/// Find pass/fail counts in test output using per-framework summary lines:
+/// cargo's "test result:" lines (one per test binary, summed), jest/vitest's
+/// "Tests:" line and pytest's "=== N passed in Xs ===" line
+fn parse_test_summary(output: &str) -> Option<(u32, u32)> {
+    // Spinners (npm's `\|/-`) redraw with carriage returns and can leave stray
+    // characters at the start of a line; keep what a terminal would show
+    let lines: Vec<&str> = output
+        .lines()
+        .map(|l| l.rsplit('\r').find(|s| !s.trim().is_empty()).unwrap_or(""))
+        .map(|l| l.trim().trim_start_matches(['\\', '|', '/', '-']).trim_start())
+        .collect();
+
+    let cargo: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|l| l.starts_with("test result:"))
+        .collect();
+    if !cargo.is_empty() {
+        return Some(cargo.iter().fold((0, 0), |(p, f), line| {
+            let (passed, failed) = summary_counts(line);
+            (p + passed.unwrap_or(0), f + failed.unwrap_or(0))
+        }));
+    }
+
+    let summary = lines
+        .iter()
+        .rev()
+        .find(|l| l.starts_with("Tests:") || l.starts_with("Tests "))
+        .or_else(|| {
+            lines.iter().rev().find(|l| {
+                l.starts_with('=')
+                    && l.contains(" in ")
+                    && (l.contains("passed") || l.contains("failed"))
+            })
+        })?;
+
+    match summary_counts(summary) {
+        (None, None) => None,
+        (passed, failed) => Some((passed.unwrap_or(0), failed.unwrap_or(0))),
+    }
+}
+
+/// Run the project's detected test command and summarize the result
+#[tauri::command]
+pub async fn run_tests(
+    project_path: String,
+    timeout_ms: Option<u64>,
+) -> Result<TestRunResult, String> {
+    // Test runs can take minutes; keep them off the main thread
+    tauri::async_runtime::spawn_blocking(move || run_tests_blocking(&project_path, timeout_ms))
+        .await
+        .map_err(|e| format!("Failed to run tests: {}", e))
+        .and_then(|r| r)
+}
+
+/// `run_tests` on the calling thread, for callers already off the main thread
+pub fn run_tests_blocking(project_path: &str, timeout_ms: Option<u64>) -> Result<TestRunResult, String> {
+    let project_path = projects::normalize_path(project_path)?;
+    let test_command = detect_test_command(project_path.clone())?
+        .ok_or("Could not detect a test command for this project")?;
+
+    let timeout = timeout_ms.map(Duration::from_millis).unwrap_or(DEFAULT_TEST_TIMEOUT);
+    let started = Instant::now();
+    let captured = capture::run_and_capture(Some(&project_path), &test_command.command, timeout)?;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    let output = strip_ansi_codes(&captured.output);
+    let exit_ok = captured.exit_code == Some(0);
+
+    let (passed, failed, result_source, success) = match parse_test_summary(&output) {
+        Some((passed, failed)) => (Some(passed), Some(failed), "summary", exit_ok && failed == 0),
+        None => (None, None, "exitCode", exit_ok),
+    };
+
+    Ok(TestRunResult {
+        command: test_command.command,
+        success,
+        passed,
+        failed,
+        result_source: result_source.to_string(),
+        exit_code: captured.exit_code,
+        timed_out: captured.timed_out,
+        duration_ms,
+        output,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_project_types_from_manifests() {
+        let root = std::env::temp_dir().join(format!("lumen-types-test-{}", uuid::Uuid::new_v4()));
+        let cases: [(&str, &str); 5] = [
+            ("package.json", "node"),
+            ("Cargo.toml", "rust"),
+            ("requirements.txt", "python"),
+            ("pyproject.toml", "python"),
+            ("go.mod", "go"),
+        ];
+
+        for (manifest, expected) in cases {
+            let dir = root.join(manifest);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join(manifest), "").unwrap();
+            assert_eq!(detect_project_types(&dir), vec![expected], "{}", manifest);
+        }
+
+        let mixed = root.join("mixed");
+        fs::create_dir_all(&mixed).unwrap();
+        fs::write(mixed.join("package.json"), "{}").unwrap();
+        fs::write(mixed.join("Cargo.toml"), "").unwrap();
+        assert_eq!(detect_project_types(&mixed), vec!["node", "rust"]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_parse_test_summary() {
+        let cases = [
+            (
+                "cargo, one line per test binary",
+                "running 3 tests\n\
+                 test result: ok. 3 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out\n\
+                 running 5 tests\n\
+                 test result: FAILED. 4 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out\n",
+                Some((7, 1)),
+            ),
+            (
+                "jest",
+                "Test Suites: 1 failed, 1 total\nTests:       1 failed, 3 passed, 4 total\nTime: 1.2 s\n",
+                Some((3, 1)),
+            ),
+            (
+                "pytest",
+                "collected 3 items\n\ntest_app.py .F.\n=== 1 failed, 2 passed in 0.12s ===\n",
+                Some((2, 1)),
+            ),
+            (
+                "ansi colors",
+                "\x1b[1m\x1b[32mtest result: ok.\x1b[0m 2 passed; 0 failed; 0 ignored\n",
+                Some((2, 0)),
+            ),
+            (
+                "npm spinner",
+                "\r\\\r|\r/\r-Tests:       5 passed, 5 total\n",
+                Some((5, 0)),
+            ),
+            ("no summary", "Compiling app v0.1.0\nerror: could not compile `app`\n", None),
+        ];
+
+        for (name, output, expected) in cases {
+            // run_tests strips ANSI codes before parsing
+            let output = strip_ansi_codes(output);
+            assert_eq!(parse_test_summary(&output), expected, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionEvent {
+    pub id: String,
+    pub session_id: String,
+    pub kind: String,
+    pub data: serde_json::Value,
+    pub created_at: String,
+}
+
+/// Insert an event for a session
+pub fn insert_event(
+    conn: &rusqlite::Connection,
+    session_id: &str,
+    kind: &str,
+    data: &serde_json::Value,
+) -> Result<SessionEvent, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let data_json = serde_json::to_string(data)
+        .map_err(|e| format!("Failed to serialize event data: {}", e))?;
+
+    conn.execute(
+        "INSERT INTO session_events (id, session_id, kind, data, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        (&id, session_id, kind, &data_json, &created_at),
+    )
+    .map_err(|e| format!("Failed to record session event: {}", e))?;
+
+    Ok(SessionEvent {
+        id,
+        session_id: session_id.to_string(),
+        kind: kind.to_string(),
+        data: data.clone(),
+        created_at,
+    })
+}
+
+/// Find the active (not yet ended) session for a project path, if any
+pub fn active_session_for_path(project_path: &str) -> Option<String> {
+    let conn = db::get_connection().ok()?;
+    find_active_session(&conn, project_path)
+}
+
+/// Find the active (not yet ended) session for a project path on an open connection
+pub fn find_active_session(conn: &rusqlite::Connection, project_path: &str) -> Option<String> {
+    conn.query_row(
+        "SELECT s.id FROM sessions s
+         JOIN projects p ON s.project_id = p.id
+         WHERE p.path = ?1 AND s.ended_at IS NULL
+         ORDER BY s.started_at DESC
+         LIMIT 1",
+        [project_path],
+        |row| row.get(0),
+    )
+    .ok()
+}
+
+/// Find the active (not yet ended) session for a project id, if any
+pub fn active_session_for_project(project_id: &str) -> Option<String> {
+    let conn = db::get_connection().ok()?;
+
+    conn.query_row(
+        "SELECT id FROM sessions
+         WHERE project_id = ?1 AND ended_at IS NULL
+         ORDER BY started_at DESC
+         LIMIT 1",
+        [project_id],
+        |row| row.get(0),
+    )
+    .ok()
+}
+
+/// Record an event against the project's active session (no-op when none is active)
+pub fn record_for_project(project_id: &str, kind: &str, data: serde_json::Value) {
+    if let Some(session_id) = active_session_for_project(project_id) {
+        if let Ok(conn) = db::get_connection() {
+            let _ = insert_event(&conn, &session_id, kind, &data);
+        }
+    }
+}
+
+/// Record an event for a session
+#[tauri::command]
+pub fn record_session_event(
+    session_id: String,
+    kind: String,
+    data: Option<serde_json::Value>,
+) -> Result<SessionEvent, String> {
+    let conn = db::get_connection()?;
+    insert_event(&conn, &session_id, &kind, &data.unwrap_or(serde_json::Value::Null))
+}
+
+/// Get all events for a session, oldest first. Manual session notes are
+/// included as `note` events.
+#[tauri::command]
+pub fn get_session_timeline(session_id: String) -> Result<Vec<SessionEvent>, String> {
+    let conn = db::get_connection()?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, session_id, kind, data, created_at, rowid AS seq
+             FROM session_events
+             WHERE session_id = ?1
+             UNION ALL
+             SELECT id, session_id, 'note', json_object('note', note), created_at, rowid
+             FROM session_notes
+             WHERE session_id = ?1
+             ORDER BY created_at ASC, seq ASC",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let events = stmt
+        .query_map([&session_id], |row| {
+            let data_json: Option<String> = row.get(3)?;
+
+            Ok(SessionEvent {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                kind: row.get(2)?,
+                data: data_json
+                    .and_then(|d| serde_json::from_str(&d).ok())
+                    .unwrap_or(serde_json::Value::Null),
+                created_at: row.get(4)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query session events: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to collect session events: {}", e))?;
+
+    Ok(events)
+}
@@ -1,3 +1,4 @@
+use crate::commands::hooks;
 use crate::db;
 use serde::{Deserialize, Serialize};
 
@@ -66,6 +67,10 @@ pub fn save_session_memory(input: CreateSessionMemoryInput) -> Result<SessionMem
     )
     .map_err(|e| format!("Failed to save session memory: {}", e))?;
 
+    if let Err(e) = hooks::refresh_session_memory_cache(&input.project_id) {
+        eprintln!("Failed to update session memory cache: {}", e);
+    }
+
     Ok(SessionMemory {
         id,
         project_id: input.project_id,
@@ -165,8 +170,22 @@ pub fn get_latest_session_memory(project_id: String) -> Result<Option<SessionMem
 pub fn delete_session_memory(memory_id: String) -> Result<(), String> {
     let conn = db::get_connection()?;
 
+    let project_id: Option<String> = conn
+        .query_row(
+            "SELECT project_id FROM session_memories WHERE id = ?1",
+            [&memory_id],
+            |row| row.get(0),
+        )
+        .ok();
+
     conn.execute("DELETE FROM session_memories WHERE id = ?1", [&memory_id])
         .map_err(|e| format!("Failed to delete session memory: {}", e))?;
 
+    if let Some(project_id) = project_id {
+        if let Err(e) = hooks::refresh_session_memory_cache(&project_id) {
+            eprintln!("Failed to update session memory cache: {}", e);
+        }
+    }
+
     Ok(())
 }
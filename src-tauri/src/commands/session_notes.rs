@@ -0,0 +1,90 @@
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionNote {
+    pub id: String,
+    pub session_id: String,
+    pub note: String,
+    pub created_at: String,
+}
+
+/// Add a manual note to a session
+#[tauri::command]
+pub fn add_session_note(session_id: String, note: String) -> Result<SessionNote, String> {
+    let note = note.trim().to_string();
+    if note.is_empty() {
+        return Err("Note cannot be empty".to_string());
+    }
+
+    let conn = db::get_connection()?;
+
+    let exists: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM sessions WHERE id = ?1)",
+            [&session_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to look up session: {}", e))?;
+    if !exists {
+        return Err("Session not found".to_string());
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let created_at = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO session_notes (id, session_id, note, created_at) VALUES (?1, ?2, ?3, ?4)",
+        (&id, &session_id, &note, &created_at),
+    )
+    .map_err(|e| format!("Failed to add session note: {}", e))?;
+
+    Ok(SessionNote {
+        id,
+        session_id,
+        note,
+        created_at,
+    })
+}
+
+/// Get all notes for a session, oldest first
+#[tauri::command]
+pub fn get_session_notes(session_id: String) -> Result<Vec<SessionNote>, String> {
+    let conn = db::get_connection()?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, session_id, note, created_at
+             FROM session_notes
+             WHERE session_id = ?1
+             ORDER BY created_at ASC, rowid ASC",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let notes = stmt
+        .query_map([&session_id], |row| {
+            Ok(SessionNote {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                note: row.get(2)?,
+                created_at: row.get(3)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query session notes: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to collect session notes: {}", e))?;
+
+    Ok(notes)
+}
+
+/// Delete a session note
+#[tauri::command]
+pub fn delete_session_note(id: String) -> Result<(), String> {
+    let conn = db::get_connection()?;
+
+    conn.execute("DELETE FROM session_notes WHERE id = ?1", [&id])
+        .map_err(|e| format!("Failed to delete session note: {}", e))?;
+
+    Ok(())
+}
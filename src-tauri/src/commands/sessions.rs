@@ -1,7 +1,15 @@
+use crate::commands::budgets;
+use crate::commands::claude_code;
+use crate::commands::session_events::{self, SessionEvent};
+use crate::commands::snapshots;
 use crate::db;
+use crate::git;
+use crate::pty::recorder;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::AppHandle;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Session {
     pub id: String,
@@ -14,13 +22,72 @@ pub struct Session {
     pub retry_count: i32,
     pub efficiency_score: Option<i32>,
     pub log_path: String,
+    /// "completed", "errored" or "abandoned" once classified
+    pub outcome: Option<String>,
 }
 
+/// Sessions left open longer than this are considered abandoned
+const ABANDON_TIMEOUT_HOURS: i64 = 12;
+
 /// Calculate efficiency score: max(10, 100 - (retries * 15))
 fn calculate_efficiency(retries: i32) -> i32 {
     std::cmp::max(10, 100 - retries * 15)
 }
 
+/// Whether the last Claude state recorded for the session was an error
+fn last_state_was_error(conn: &rusqlite::Connection, session_id: &str) -> bool {
+    conn.query_row(
+        "SELECT data FROM session_events
+         WHERE session_id = ?1 AND kind = 'claude_state'
+         ORDER BY created_at DESC, rowid DESC
+         LIMIT 1",
+        [session_id],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .ok()
+    .flatten()
+    .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+    .map(|data| data.get("state").and_then(|s| s.as_str()) == Some("error"))
+    .unwrap_or(false)
+}
+
+/// Infer a session's outcome from its end time and recorded Claude states.
+/// An open session is abandoned once `active_since` (its start, or when it was
+/// last resumed) is older than the timeout.
+fn determine_outcome(
+    conn: &rusqlite::Connection,
+    session_id: &str,
+    active_since: &str,
+    ended: bool,
+) -> Option<String> {
+    if ended {
+        if last_state_was_error(conn, session_id) {
+            return Some("errored".to_string());
+        }
+        return Some("completed".to_string());
+    }
+
+    let since = chrono::DateTime::parse_from_rfc3339(active_since).ok()?;
+    let open_for = chrono::Utc::now().signed_duration_since(since);
+    if open_for > chrono::Duration::hours(ABANDON_TIMEOUT_HOURS) {
+        return Some("abandoned".to_string());
+    }
+
+    None
+}
+
+/// Mark sessions that were never ended within the timeout of starting (or of
+/// being resumed) as abandoned
+pub fn mark_abandoned_sessions(conn: &rusqlite::Connection) -> Result<usize, String> {
+    conn.execute(
+        "UPDATE sessions SET outcome = 'abandoned'
+         WHERE ended_at IS NULL AND outcome IS NULL
+         AND julianday('now') - julianday(COALESCE(resumed_at, started_at)) > ?1 / 24.0",
+        [ABANDON_TIMEOUT_HOURS],
+    )
+    .map_err(|e| format!("Failed to mark abandoned sessions: {}", e))
+}
+
 #[tauri::command]
 pub fn create_session(project_id: String, task: String) -> Result<Session, String> {
     let conn = db::get_connection()?;
@@ -35,6 +102,13 @@ pub fn create_session(project_id: String, task: String) -> Result<Session, Strin
         (&id, &project_id, &task, &started_at, &log_path),
     ).map_err(|e| format!("Failed to create session: {}", e))?;
 
+    // A failed snapshot shouldn't stop the session from starting
+    if let Err(e) =
+        snapshots::create_auto_snapshot(&project_id, &format!("auto: session start — {}", task))
+    {
+        eprintln!("Failed to create session start snapshot: {}", e);
+    }
+
     Ok(Session {
         id,
         project_id,
@@ -46,9 +120,37 @@ pub fn create_session(project_id: String, task: String) -> Result<Session, Strin
         retry_count: 0,
         efficiency_score: None,
         log_path,
+        outcome: None,
     })
 }
 
+/// Update a session with its end time, efficiency (from the stored retry count) and outcome.
+/// Returns the values written.
+fn record_session_end(
+    conn: &rusqlite::Connection,
+    session_id: &str,
+) -> Result<(String, i32, Option<String>), String> {
+    let (started_at, retry_count): (String, i32) = conn
+        .query_row(
+            "SELECT started_at, COALESCE(retry_count, 0) FROM sessions WHERE id = ?1",
+            [session_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| format!("Session not found: {}", e))?;
+
+    let ended_at = chrono::Utc::now().to_rfc3339();
+    let efficiency_score = calculate_efficiency(retry_count);
+    let outcome = determine_outcome(conn, session_id, &started_at, true);
+
+    conn.execute(
+        "UPDATE sessions SET ended_at = ?1, efficiency_score = ?2, outcome = ?3 WHERE id = ?4",
+        (&ended_at, efficiency_score, &outcome, session_id),
+    )
+    .map_err(|e| format!("Failed to end session: {}", e))?;
+
+    Ok((ended_at, efficiency_score, outcome))
+}
+
 #[tauri::command]
 pub fn end_session(session_id: String) -> Result<Session, String> {
     let conn = db::get_connection()?;
@@ -56,7 +158,7 @@ pub fn end_session(session_id: String) -> Result<Session, String> {
     // Get the session first
     let mut stmt = conn
         .prepare(
-            "SELECT id, project_id, task_description, started_at, ended_at, retry_count, efficiency_score, log_path FROM sessions WHERE id = ?1",
+            "SELECT id, project_id, task_description, started_at, ended_at, retry_count, efficiency_score, log_path, outcome, files_modified FROM sessions WHERE id = ?1",
         )
         .map_err(|e| format!("Failed to prepare query: {}", e))?;
 
@@ -68,24 +170,24 @@ pub fn end_session(session_id: String) -> Result<Session, String> {
                 task_description: row.get(2)?,
                 started_at: row.get(3)?,
                 ended_at: row.get(4)?,
-                files_modified: vec![],
+                files_modified: parse_files_modified(row.get(9)?),
                 tokens_estimate: None,
                 retry_count: row.get::<_, Option<i32>>(5)?.unwrap_or(0),
                 efficiency_score: row.get(6)?,
                 log_path: row.get(7)?,
+                outcome: row.get(8)?,
             })
         })
         .map_err(|e| format!("Session not found: {}", e))?;
 
-    // Update the session with end time and efficiency
-    let ended_at = chrono::Utc::now().to_rfc3339();
-    let efficiency_score = calculate_efficiency(session.retry_count);
+    let (ended_at, efficiency_score, outcome) = record_session_end(&conn, &session_id)?;
 
-    conn.execute(
-        "UPDATE sessions SET ended_at = ?1, efficiency_score = ?2 WHERE id = ?3",
-        (&ended_at, efficiency_score, &session_id),
-    )
-    .map_err(|e| format!("Failed to end session: {}", e))?;
+    if let Err(e) = snapshots::create_auto_snapshot(
+        &session.project_id,
+        &format!("auto: session end — {}", session.task_description),
+    ) {
+        eprintln!("Failed to create session end snapshot: {}", e);
+    }
 
     Ok(Session {
         id: session.id,
@@ -98,6 +200,7 @@ pub fn end_session(session_id: String) -> Result<Session, String> {
         retry_count: session.retry_count,
         efficiency_score: Some(efficiency_score),
         log_path: session.log_path,
+        outcome,
     })
 }
 
@@ -110,11 +213,23 @@ pub struct TodayStats {
     pub estimated_cost: f64,
 }
 
+/// Rough token count and cost for minutes of session time, for when there are no
+/// transcripts: ~15 tokens per second of active coding at ~$0.01 per 1K tokens
+pub fn estimate_token_usage(minutes: f64) -> (u64, f64) {
+    let tokens = (minutes * 60.0 * 15.0) as u64;
+    (tokens, (tokens as f64 / 1000.0) * 0.01)
+}
+
 /// Get today's usage statistics across all projects
 #[tauri::command]
-pub fn get_today_stats() -> Result<TodayStats, String> {
+pub fn get_today_stats(app_handle: AppHandle) -> Result<TodayStats, String> {
     let conn = db::get_connection()?;
 
+    // Piggyback on the stats poll to raise budget warnings
+    if let Err(e) = budgets::check_budgets(&app_handle) {
+        eprintln!("Failed to check token budgets: {}", e);
+    }
+
     // Get all sessions from today (using local time)
     let mut stmt = conn
         .prepare(
@@ -134,11 +249,12 @@ pub fn get_today_stats() -> Result<TodayStats, String> {
         .query_row([], |row| Ok((row.get(0)?, row.get(1)?)))
         .unwrap_or((0, 0.0));
 
-    // Estimate tokens (rough: ~15 tokens per second of active coding)
-    let estimated_tokens = (total_minutes * 60.0 * 15.0) as u64;
-
-    // Estimate cost: ~$0.01 per 1K tokens (blended average)
-    let estimated_cost = (estimated_tokens as f64 / 1000.0) * 0.01;
+    // Real usage from Claude Code's transcripts, or an estimate if there are none
+    let (estimated_tokens, estimated_cost) =
+        match claude_code::token_usage_for_date(chrono::Local::now().date_naive()) {
+            Some(usage) => (usage.total_tokens, usage.cost_usd),
+            None => estimate_token_usage(total_minutes),
+        };
 
     Ok(TodayStats {
         session_count: session_count as u32,
@@ -154,7 +270,7 @@ pub fn list_sessions(project_id: String) -> Result<Vec<Session>, String> {
 
     let mut stmt = conn
         .prepare(
-            "SELECT id, project_id, task_description, started_at, ended_at, retry_count, efficiency_score, log_path FROM sessions WHERE project_id = ?1 ORDER BY started_at DESC LIMIT 50",
+            "SELECT id, project_id, task_description, started_at, ended_at, retry_count, efficiency_score, log_path, outcome, files_modified FROM sessions WHERE project_id = ?1 ORDER BY started_at DESC LIMIT 50",
         )
         .map_err(|e| format!("Failed to prepare query: {}", e))?;
 
@@ -166,11 +282,12 @@ pub fn list_sessions(project_id: String) -> Result<Vec<Session>, String> {
                 task_description: row.get(2)?,
                 started_at: row.get(3)?,
                 ended_at: row.get(4)?,
-                files_modified: vec![],
+                files_modified: parse_files_modified(row.get(9)?),
                 tokens_estimate: None,
                 retry_count: row.get::<_, Option<i32>>(5)?.unwrap_or(0),
                 efficiency_score: row.get(6)?,
                 log_path: row.get(7)?,
+                outcome: row.get(8)?,
             })
         })
         .map_err(|e| format!("Failed to query sessions: {}", e))?
@@ -179,3 +296,622 @@ pub fn list_sessions(project_id: String) -> Result<Vec<Session>, String> {
 
     Ok(sessions)
 }
+
+/// Bump a session's retry count, returning the new count
+pub fn increment_retry(conn: &rusqlite::Connection, session_id: &str) -> Result<i32, String> {
+    conn.query_row(
+        "UPDATE sessions SET retry_count = COALESCE(retry_count, 0) + 1 WHERE id = ?1
+         RETURNING retry_count",
+        [session_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => "Session not found".to_string(),
+        e => format!("Failed to update retry count: {}", e),
+    })
+}
+
+/// Count a retry against a session, lowering its efficiency score
+#[tauri::command]
+pub fn increment_session_retry(session_id: String) -> Result<i32, String> {
+    let conn = db::get_connection()?;
+    increment_retry(&conn, &session_id)
+}
+
+/// Read the `files_modified` column, a JSON array of paths
+fn parse_files_modified(value: Option<String>) -> Vec<String> {
+    value
+        .and_then(|f| serde_json::from_str(&f).ok())
+        .unwrap_or_default()
+}
+
+/// Record the files modified during a session, replacing the previous list
+#[tauri::command]
+pub fn update_session_files(session_id: String, files: Vec<String>) -> Result<Vec<String>, String> {
+    let mut unique: Vec<String> = Vec::new();
+    for file in files {
+        let file = file.trim().to_string();
+        if !file.is_empty() && !unique.contains(&file) {
+            unique.push(file);
+        }
+    }
+
+    let files_json = serde_json::to_string(&unique)
+        .map_err(|e| format!("Failed to serialize files_modified: {}", e))?;
+
+    let conn = db::get_connection()?;
+    let updated = conn
+        .execute(
+            "UPDATE sessions SET files_modified = ?1 WHERE id = ?2",
+            (&files_json, &session_id),
+        )
+        .map_err(|e| format!("Failed to update session files: {}", e))?;
+    if updated == 0 {
+        return Err("Session not found".to_string());
+    }
+
+    Ok(unique)
+}
+
+/// Classify (or re-classify) a session's outcome and store it
+#[tauri::command]
+pub fn classify_session(session_id: String) -> Result<Option<String>, String> {
+    let conn = db::get_connection()?;
+
+    let (active_since, ended_at): (String, Option<String>) = conn
+        .query_row(
+            "SELECT COALESCE(resumed_at, started_at), ended_at FROM sessions WHERE id = ?1",
+            [&session_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| format!("Session not found: {}", e))?;
+
+    let outcome = determine_outcome(&conn, &session_id, &active_since, ended_at.is_some());
+
+    conn.execute(
+        "UPDATE sessions SET outcome = ?1 WHERE id = ?2",
+        (&outcome, &session_id),
+    )
+    .map_err(|e| format!("Failed to update session outcome: {}", e))?;
+
+    Ok(outcome)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedSession {
+    pub session: Session,
+    pub events: Vec<SessionEvent>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionArchive {
+    pub exported_at: String,
+    pub before: String,
+    pub sessions: Vec<ArchivedSession>,
+}
+
+/// Columns read by `session_with_path_from_row` (sessions aliased `s`, projects `p`)
+const SESSION_WITH_PATH_COLUMNS: &str =
+    "s.id, s.project_id, s.task_description, s.started_at, s.ended_at, s.files_modified,
+     s.tokens_estimate, s.retry_count, s.efficiency_score, s.log_path, s.outcome, p.path";
+
+/// Map a row selected with `SESSION_WITH_PATH_COLUMNS` to a session and its project path
+fn session_with_path_from_row(row: &rusqlite::Row) -> rusqlite::Result<(Session, Option<String>)> {
+    let files_modified: Option<String> = row.get(5)?;
+
+    Ok((
+        Session {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            task_description: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+            started_at: row.get(3)?,
+            ended_at: row.get(4)?,
+            files_modified: parse_files_modified(files_modified),
+            tokens_estimate: row.get(6)?,
+            retry_count: row.get::<_, Option<i32>>(7)?.unwrap_or(0),
+            efficiency_score: row.get(8)?,
+            log_path: row.get::<_, Option<String>>(9)?.unwrap_or_default(),
+            outcome: row.get(10)?,
+        },
+        row.get(11)?,
+    ))
+}
+
+/// Load a single session along with its project path
+fn load_session(
+    conn: &rusqlite::Connection,
+    session_id: &str,
+) -> Result<(Session, Option<String>), String> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM sessions s LEFT JOIN projects p ON s.project_id = p.id WHERE s.id = ?1",
+            SESSION_WITH_PATH_COLUMNS
+        ),
+        [session_id],
+        session_with_path_from_row,
+    )
+    .map_err(|e| format!("Session not found: {}", e))
+}
+
+/// Parse a cutoff given as a date (`YYYY-MM-DD`, midnight UTC) or an RFC 3339 timestamp
+fn parse_cutoff(before: &str) -> Result<String, String> {
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(before) {
+        return Ok(time.with_timezone(&chrono::Utc).to_rfc3339());
+    }
+    chrono::NaiveDate::parse_from_str(before, "%Y-%m-%d")
+        .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc().to_rfc3339())
+        .map_err(|_| format!("Invalid cutoff date: {}", before))
+}
+
+/// Sessions (with their project path) started before a cutoff
+fn sessions_before(
+    conn: &rusqlite::Connection,
+    before: &str,
+    project_id: Option<&str>,
+) -> Result<Vec<(Session, Option<String>)>, String> {
+    let before = parse_cutoff(before)?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {}
+             FROM sessions s
+             LEFT JOIN projects p ON s.project_id = p.id
+             WHERE julianday(s.started_at) < julianday(?1) AND (?2 IS NULL OR s.project_id = ?2)
+             ORDER BY s.started_at ASC",
+            SESSION_WITH_PATH_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let sessions = stmt
+        .query_map(rusqlite::params![&before, project_id], session_with_path_from_row)
+        .map_err(|e| format!("Failed to query sessions: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to collect sessions: {}", e))?;
+
+    Ok(sessions)
+}
+
+/// Delete the given sessions in one transaction, along with their events, notes and recordings
+fn delete_sessions(
+    conn: &mut rusqlite::Connection,
+    sessions: &[(Session, Option<String>)],
+) -> Result<usize, String> {
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    for (session, _) in sessions {
+        tx.execute("DELETE FROM session_events WHERE session_id = ?1", [&session.id])
+            .map_err(|e| format!("Failed to delete session events: {}", e))?;
+        tx.execute("DELETE FROM session_notes WHERE session_id = ?1", [&session.id])
+            .map_err(|e| format!("Failed to delete session notes: {}", e))?;
+        tx.execute("DELETE FROM sessions WHERE id = ?1", [&session.id])
+            .map_err(|e| format!("Failed to delete session: {}", e))?;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    // Recordings live in the project; remove them once the rows are gone
+    for (session, project_path) in sessions {
+        if let Some(path) = project_path {
+            let _ = std::fs::remove_file(recorder::recording_path(Path::new(path), &session.id));
+            let _ = std::fs::remove_file(recorder::blocks_path(Path::new(path), &session.id));
+            let _ = std::fs::remove_file(recorder::cwd_history_path(Path::new(path), &session.id));
+        }
+    }
+
+    Ok(sessions.len())
+}
+
+/// Delete all sessions started before `before` (a date or RFC 3339 timestamp).
+/// Returns the number of sessions deleted.
+#[tauri::command]
+pub fn delete_sessions_before(before: String, project_id: Option<String>) -> Result<usize, String> {
+    let mut conn = db::get_connection()?;
+
+    let sessions = sessions_before(&conn, &before, project_id.as_deref())?;
+    delete_sessions(&mut conn, &sessions)
+}
+
+/// Export sessions started before `before` (with their events) to a JSON file at `dest`,
+/// then delete them. Returns the number of sessions archived.
+#[tauri::command]
+pub fn archive_sessions_before(
+    before: String,
+    dest: String,
+    project_id: Option<String>,
+) -> Result<usize, String> {
+    let mut conn = db::get_connection()?;
+
+    let sessions = sessions_before(&conn, &before, project_id.as_deref())?;
+
+    let archive = SessionArchive {
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        before: before.clone(),
+        sessions: sessions
+            .iter()
+            .map(|(session, _)| {
+                Ok(ArchivedSession {
+                    session: session.clone(),
+                    events: session_events::get_session_timeline(session.id.clone())?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?,
+    };
+
+    let content = serde_json::to_string_pretty(&archive)
+        .map_err(|e| format!("Failed to serialize archive: {}", e))?;
+
+    if let Some(parent) = Path::new(&dest).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create archive directory: {}", e))?;
+    }
+    std::fs::write(&dest, content).map_err(|e| format!("Failed to write archive: {}", e))?;
+
+    delete_sessions(&mut conn, &sessions)
+}
+
+/// Sessions for a project that were never ended (e.g. the app crashed mid-session)
+#[tauri::command]
+pub fn get_incomplete_sessions(project_id: String) -> Result<Vec<Session>, String> {
+    let conn = db::get_connection()?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {}
+             FROM sessions s
+             LEFT JOIN projects p ON s.project_id = p.id
+             WHERE s.project_id = ?1 AND s.ended_at IS NULL
+             ORDER BY s.started_at DESC",
+            SESSION_WITH_PATH_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let sessions = stmt
+        .query_map([&project_id], session_with_path_from_row)
+        .map_err(|e| format!("Failed to query sessions: {}", e))?
+        .map(|r| r.map(|(session, _)| session))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to collect sessions: {}", e))?;
+
+    Ok(sessions)
+}
+
+/// Mark a dangling session as active again so its duration keeps counting.
+/// The abandon timeout restarts from now.
+#[tauri::command]
+pub fn resume_session_by_id(session_id: String) -> Result<Session, String> {
+    let conn = db::get_connection()?;
+    let (session, _) = load_session(&conn, &session_id)?;
+
+    if session.ended_at.is_some() {
+        return Err("Session has already ended".to_string());
+    }
+
+    // Clear an "abandoned" classification, and keep `mark_abandoned_sessions`
+    // from applying it again until the session has been idle past the timeout
+    conn.execute(
+        "UPDATE sessions SET outcome = NULL, resumed_at = ?1 WHERE id = ?2",
+        (chrono::Utc::now().to_rfc3339(), &session_id),
+    )
+    .map_err(|e| format!("Failed to resume session: {}", e))?;
+
+    Ok(Session {
+        outcome: None,
+        ..session
+    })
+}
+
+/// Time of the last recorded activity in a session: its latest event or
+/// recorded output, falling back to the start time
+fn last_activity(
+    conn: &rusqlite::Connection,
+    session: &Session,
+    project_path: Option<&str>,
+) -> chrono::DateTime<chrono::Utc> {
+    let parse = |t: &str| {
+        chrono::DateTime::parse_from_rfc3339(t)
+            .ok()
+            .map(|t| t.with_timezone(&chrono::Utc))
+    };
+
+    let mut last = parse(&session.started_at).unwrap_or_else(chrono::Utc::now);
+
+    let last_event: Option<String> = conn
+        .query_row(
+            "SELECT MAX(created_at) FROM session_events WHERE session_id = ?1",
+            [&session.id],
+            |row| row.get(0),
+        )
+        .ok()
+        .flatten();
+    if let Some(event_time) = last_event.as_deref().and_then(parse) {
+        last = last.max(event_time);
+    }
+
+    let recording_modified = project_path
+        .and_then(|p| std::fs::metadata(recorder::recording_path(Path::new(p), &session.id)).ok())
+        .and_then(|m| m.modified().ok())
+        .map(chrono::DateTime::<chrono::Utc>::from);
+    if let Some(modified) = recording_modified {
+        last = last.max(modified);
+    }
+
+    last
+}
+
+/// End a dangling session at its last recorded activity and mark it abandoned
+#[tauri::command]
+pub fn abandon_session(session_id: String) -> Result<Session, String> {
+    let conn = db::get_connection()?;
+    let (session, project_path) = load_session(&conn, &session_id)?;
+
+    if session.ended_at.is_some() {
+        return Err("Session has already ended".to_string());
+    }
+
+    let ended_at = last_activity(&conn, &session, project_path.as_deref()).to_rfc3339();
+    let efficiency_score = calculate_efficiency(session.retry_count);
+    let outcome = "abandoned".to_string();
+
+    conn.execute(
+        "UPDATE sessions SET ended_at = ?1, efficiency_score = ?2, outcome = ?3 WHERE id = ?4",
+        (&ended_at, efficiency_score, &outcome, &session_id),
+    )
+    .map_err(|e| format!("Failed to abandon session: {}", e))?;
+
+    Ok(Session {
+        ended_at: Some(ended_at),
+        efficiency_score: Some(efficiency_score),
+        outcome: Some(outcome),
+        ..session
+    })
+}
+
+/// Format a duration in minutes as e.g. "1h 5m"
+fn format_duration(minutes: i64) -> String {
+    if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Generate a shareable markdown writeup of a session: task, duration, files,
+/// snapshots taken during it, decisions/open threads from its session memory, and outcome
+#[tauri::command]
+pub fn generate_session_summary_md(session_id: String) -> Result<String, String> {
+    let conn = db::get_connection()?;
+    let (session, project_path) = load_session(&conn, &session_id)?;
+
+    let project_name: String = conn
+        .query_row(
+            "SELECT name FROM projects WHERE id = ?1",
+            [&session.project_id],
+            |row| row.get(0),
+        )
+        .unwrap_or_else(|_| "Unknown project".to_string());
+
+    let started = chrono::DateTime::parse_from_rfc3339(&session.started_at)
+        .map_err(|e| format!("Invalid session start time: {}", e))?
+        .with_timezone(&chrono::Utc);
+    let ended = session
+        .ended_at
+        .as_deref()
+        .and_then(|e| chrono::DateTime::parse_from_rfc3339(e).ok())
+        .map(|e| e.with_timezone(&chrono::Utc));
+    let window_end = ended.unwrap_or_else(chrono::Utc::now);
+    let duration_minutes = window_end.signed_duration_since(started).num_minutes();
+
+    // Snapshots taken during the session
+    let snapshots: Vec<git::SnapshotInfo> = project_path
+        .as_deref()
+        .and_then(|p| git::list_snapshots(Path::new(p)).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|s| {
+            chrono::DateTime::parse_from_rfc3339(&s.timestamp)
+                .map(|t| t >= started && t <= window_end)
+                .unwrap_or(false)
+        })
+        .collect();
+
+    // Session memory saved during (or shortly after) the session
+    let memory = conn
+        .query_row(
+            "SELECT summary, key_decisions, open_threads FROM session_memories
+             WHERE project_id = ?1
+             AND julianday(created_at) >= julianday(?2)
+             AND julianday(created_at) <= julianday(?3) + 1.0 / 24
+             ORDER BY created_at DESC
+             LIMIT 1",
+            rusqlite::params![session.project_id, session.started_at, window_end.to_rfc3339()],
+            |row| {
+                let decisions: Option<String> = row.get(1)?;
+                let threads: Option<String> = row.get(2)?;
+                Ok((
+                    row.get::<_, String>(0)?,
+                    decisions
+                        .and_then(|d| serde_json::from_str::<Vec<String>>(&d).ok())
+                        .unwrap_or_default(),
+                    threads
+                        .and_then(|t| serde_json::from_str::<Vec<String>>(&t).ok())
+                        .unwrap_or_default(),
+                ))
+            },
+        )
+        .ok();
+
+    let task = if session.task_description.is_empty() {
+        "Untitled session"
+    } else {
+        &session.task_description
+    };
+
+    let mut md = format!("# {}\n\n", task);
+    md.push_str(&format!("- **Project:** {}\n", project_name));
+    md.push_str(&format!(
+        "- **Started:** {}\n",
+        started.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+    ));
+    md.push_str(&format!(
+        "- **Duration:** {}{}\n",
+        format_duration(duration_minutes),
+        if ended.is_none() { " (in progress)" } else { "" }
+    ));
+    if let Some(outcome) = &session.outcome {
+        md.push_str(&format!("- **Outcome:** {}\n", outcome));
+    }
+
+    if let Some((summary, _, _)) = &memory {
+        md.push_str(&format!("\n## Summary\n\n{}\n", summary));
+    }
+
+    if !session.files_modified.is_empty() {
+        md.push_str("\n## Files Modified\n\n");
+        for file in &session.files_modified {
+            md.push_str(&format!("- `{}`\n", file));
+        }
+    }
+
+    if !snapshots.is_empty() {
+        md.push_str("\n## Snapshots\n\n");
+        // list_snapshots is newest first; show them in the order they were taken
+        for snapshot in snapshots.iter().rev() {
+            let time = chrono::DateTime::parse_from_rfc3339(&snapshot.timestamp)
+                .map(|t| t.with_timezone(&chrono::Local).format("%H:%M").to_string())
+                .unwrap_or_default();
+            md.push_str(&format!(
+                "- `{}` {} ({})\n",
+                &snapshot.id[..7.min(snapshot.id.len())],
+                snapshot.name,
+                time
+            ));
+        }
+    }
+
+    if let Some((_, decisions, threads)) = &memory {
+        if !decisions.is_empty() {
+            md.push_str("\n## Key Decisions\n\n");
+            for decision in decisions {
+                md.push_str(&format!("- {}\n", decision));
+            }
+        }
+        if !threads.is_empty() {
+            md.push_str("\n## Open Threads\n\n");
+            for thread in threads {
+                md.push_str(&format!("- {}\n", thread));
+            }
+        }
+    }
+
+    Ok(md)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_efficiency_drops_with_retries() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sessions (
+                id TEXT PRIMARY KEY,
+                started_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                ended_at DATETIME,
+                retry_count INTEGER DEFAULT 0,
+                efficiency_score INTEGER,
+                outcome TEXT
+            );
+            CREATE TABLE session_events (session_id TEXT, kind TEXT, data TEXT, created_at TEXT);
+            INSERT INTO sessions (id) VALUES ('clean'), ('s1'), ('s3');
+            INSERT INTO sessions (id, retry_count) VALUES ('s2', NULL);",
+        )
+        .unwrap();
+        let stored_score = |id: &str| -> Option<i32> {
+            conn.query_row(
+                "SELECT efficiency_score FROM sessions WHERE id = ?1",
+                [id],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(record_session_end(&conn, "clean").unwrap().1, 100);
+        assert_eq!(stored_score("clean"), Some(100));
+
+        assert_eq!(increment_retry(&conn, "s1").unwrap(), 1);
+        assert_eq!(increment_retry(&conn, "s1").unwrap(), 2);
+        record_session_end(&conn, "s1").unwrap();
+        assert_eq!(stored_score("s1"), Some(70));
+
+        // A NULL count starts from zero
+        assert_eq!(increment_retry(&conn, "s2").unwrap(), 1);
+        record_session_end(&conn, "s2").unwrap();
+        assert_eq!(stored_score("s2"), Some(85));
+
+        // The score bottoms out at 10
+        for _ in 0..12 {
+            increment_retry(&conn, "s3").unwrap();
+        }
+        record_session_end(&conn, "s3").unwrap();
+        assert_eq!(stored_score("s3"), Some(10));
+
+        assert_eq!(
+            increment_retry(&conn, "missing").unwrap_err(),
+            "Session not found"
+        );
+        assert!(record_session_end(&conn, "missing").is_err());
+    }
+
+    #[test]
+    fn test_resumed_sessions_are_not_abandoned_again() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sessions (
+                id TEXT PRIMARY KEY,
+                started_at DATETIME NOT NULL,
+                ended_at DATETIME,
+                outcome TEXT,
+                resumed_at DATETIME
+            );",
+        )
+        .unwrap();
+        let old =
+            (chrono::Utc::now() - chrono::Duration::hours(ABANDON_TIMEOUT_HOURS + 1)).to_rfc3339();
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO sessions (id, started_at, resumed_at) VALUES ('stale', ?1, NULL), ('resumed', ?1, ?2)",
+            [&old, &now],
+        )
+        .unwrap();
+
+        assert_eq!(mark_abandoned_sessions(&conn).unwrap(), 1);
+        let outcome = |id: &str| -> Option<String> {
+            conn.query_row("SELECT outcome FROM sessions WHERE id = ?1", [id], |row| {
+                row.get(0)
+            })
+            .unwrap()
+        };
+        assert_eq!(outcome("stale").as_deref(), Some("abandoned"));
+        assert_eq!(outcome("resumed"), None);
+    }
+
+    #[test]
+    fn test_parse_cutoff() {
+        assert_eq!(
+            parse_cutoff("2024-03-01").unwrap(),
+            "2024-03-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_cutoff("2024-03-01T12:00:00+02:00").unwrap(),
+            "2024-03-01T10:00:00+00:00"
+        );
+        assert!(parse_cutoff("2024-13-01").is_err());
+        assert!(parse_cutoff("last week").is_err());
+    }
+}
@@ -1,7 +1,13 @@
+use crate::commands::github;
+use crate::commands::operations;
+use crate::commands::projects;
+use crate::commands::session_events;
+use crate::config;
 use crate::db;
 use crate::git;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use tauri::{AppHandle, Emitter};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,6 +18,7 @@ pub struct Snapshot {
     pub timestamp: String,
     pub files_changed: i32,
     pub snapshot_type: String,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -57,6 +64,12 @@ pub fn create_snapshot(
 
     let info = git::create_snapshot(path, &name, description.as_deref())?;
 
+    session_events::record_for_project(
+        &project_id,
+        "snapshot_created",
+        serde_json::json!({ "snapshotId": info.id, "name": info.name }),
+    );
+
     Ok(Snapshot {
         id: info.id,
         name: info.name,
@@ -64,9 +77,64 @@ pub fn create_snapshot(
         timestamp: info.timestamp,
         files_changed: info.files_changed,
         snapshot_type: info.snapshot_type,
+        tags: info.tags,
     })
 }
 
+/// Take an "auto" snapshot of a project, if automatic session snapshots are enabled
+pub fn create_auto_snapshot(project_id: &str, name: &str) -> Result<Option<git::SnapshotInfo>, String> {
+    if !config::load_config()?.auto_snapshot_sessions {
+        return Ok(None);
+    }
+
+    let project_path = get_project_path(project_id)?;
+    let info = git::create_snapshot_of_type(Path::new(&project_path), name, None, "auto")?;
+
+    session_events::record_for_project(
+        project_id,
+        "snapshot_created",
+        serde_json::json!({ "snapshotId": info.id, "name": info.name }),
+    );
+
+    Ok(Some(info))
+}
+
+/// Drop old snapshots, keeping the `keep_last` most recent and any from the last
+/// `keep_days` days (auto snapshots get half of each). Kept snapshots get new ids.
+/// Returns how many snapshots were removed.
+#[tauri::command]
+pub fn prune_snapshots(
+    project_id: String,
+    keep_last: usize,
+    keep_days: Option<i64>,
+) -> Result<usize, String> {
+    if keep_last == 0 {
+        return Err("Keep at least one snapshot".to_string());
+    }
+    if keep_days.is_some_and(|days| days < 0) {
+        return Err("Retention days cannot be negative".to_string());
+    }
+
+    let project_path = get_project_path(&project_id)?;
+    git::prune_snapshots(Path::new(&project_path), keep_last, keep_days)
+}
+
+/// Whether sessions take an automatic snapshot when they start and end
+#[tauri::command]
+pub fn get_auto_snapshot_sessions() -> Result<bool, String> {
+    Ok(config::load_config()?.auto_snapshot_sessions)
+}
+
+/// Enable or disable automatic snapshots on session start and end
+#[tauri::command]
+pub fn set_auto_snapshot_sessions(enabled: bool) -> Result<bool, String> {
+    let mut config = config::load_config()?;
+    config.auto_snapshot_sessions = enabled;
+    config::save_config(&config)?;
+
+    Ok(config.auto_snapshot_sessions)
+}
+
 #[tauri::command]
 pub fn list_snapshots(project_id: String) -> Result<Vec<Snapshot>, String> {
     let project_path = get_project_path(&project_id)?;
@@ -83,16 +151,392 @@ pub fn list_snapshots(project_id: String) -> Result<Vec<Snapshot>, String> {
             timestamp: info.timestamp,
             files_changed: info.files_changed,
             snapshot_type: info.snapshot_type,
+            tags: info.tags,
         })
         .collect())
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotSize {
+    pub id: String,
+    pub name: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotStats {
+    pub total_bytes: u64,
+    pub snapshot_count: usize,
+    pub largest_snapshot: Option<SnapshotSize>,
+}
+
+/// Get how much disk space a project's snapshots use, and which snapshot added the most
+#[tauri::command]
+pub fn get_snapshot_stats(project_id: String) -> Result<SnapshotStats, String> {
+    let project_path = get_project_path(&project_id)?;
+    let size = git::get_snapshot_repo_size(Path::new(&project_path))?;
+
+    let largest_snapshot = size
+        .snapshots
+        .iter()
+        .max_by_key(|s| s.bytes)
+        .map(|s| SnapshotSize {
+            id: s.id.clone(),
+            name: s.name.clone(),
+            bytes: s.bytes,
+        });
+
+    Ok(SnapshotStats {
+        total_bytes: size.total_bytes,
+        snapshot_count: size.snapshots.len(),
+        largest_snapshot,
+    })
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Write every snapshot's metadata to a CSV file at `dest`, newest first.
+/// Returns the number of snapshots written.
+#[tauri::command]
+pub fn export_snapshots_csv(project_id: String, dest: String) -> Result<usize, String> {
+    let project_path = get_project_path(&project_id)?;
+    let snapshots = git::list_snapshots(Path::new(&project_path))?;
+
+    let mut csv = String::from("id,name,description,timestamp,files_changed,type,tags\n");
+    for snapshot in &snapshots {
+        let row = [
+            csv_field(&snapshot.id),
+            csv_field(&snapshot.name),
+            csv_field(snapshot.description.as_deref().unwrap_or("")),
+            csv_field(&snapshot.timestamp),
+            snapshot.files_changed.to_string(),
+            csv_field(&snapshot.snapshot_type),
+            csv_field(&snapshot.tags.join(";")),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    std::fs::write(&dest, csv).map_err(|e| format!("Failed to write CSV: {}", e))?;
+
+    Ok(snapshots.len())
+}
+
+/// Export a snapshot's files as a zip archive at `dest`. Returns the output path.
+#[tauri::command]
+pub fn export_snapshot(
+    project_id: String,
+    snapshot_id: String,
+    dest: String,
+) -> Result<String, String> {
+    let project_path = get_project_path(&project_id)?;
+    git::export_snapshot(Path::new(&project_path), &snapshot_id, Path::new(&dest))?;
+    Ok(dest)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotIdMapping {
+    pub old_id: String,
+    pub new_id: String,
+}
+
+/// Tag every snapshot taken between `from` and `to` (RFC 3339).
+/// Returns the old -> new id of every snapshot whose id changed.
+#[tauri::command]
+pub fn tag_snapshots_in_range(
+    project_id: String,
+    from: String,
+    to: String,
+    tag: String,
+) -> Result<Vec<SnapshotIdMapping>, String> {
+    let tag = tag.trim();
+    if tag.is_empty() || tag.contains(',') || tag.contains('\n') {
+        return Err("Tag must be non-empty and cannot contain commas or newlines".to_string());
+    }
+
+    let from = chrono::DateTime::parse_from_rfc3339(&from)
+        .map_err(|e| format!("Invalid start time: {}", e))?;
+    let to = chrono::DateTime::parse_from_rfc3339(&to)
+        .map_err(|e| format!("Invalid end time: {}", e))?;
+    if from > to {
+        return Err("Start time must be before end time".to_string());
+    }
+
+    let project_path = get_project_path(&project_id)?;
+    let path = Path::new(&project_path);
+
+    let mappings = git::tag_snapshots_in_range(path, from.timestamp(), to.timestamp(), tag)?;
+
+    Ok(mappings
+        .into_iter()
+        .map(|m| SnapshotIdMapping {
+            old_id: m.old_id,
+            new_id: m.new_id,
+        })
+        .collect())
+}
+
+/// Change a snapshot's name and description. Rewrites the snapshot and every later
+/// one, so returns the snapshot's new id.
+#[tauri::command]
+pub fn rename_snapshot(
+    project_id: String,
+    snapshot_id: String,
+    new_name: String,
+    new_description: Option<String>,
+) -> Result<String, String> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err("Snapshot name cannot be empty".to_string());
+    }
+
+    let project_path = get_project_path(&project_id)?;
+    let path = Path::new(&project_path);
+
+    let new_id = git::rename_snapshot(path, &snapshot_id, new_name, new_description.as_deref())?;
+
+    session_events::record_for_project(
+        &project_id,
+        "snapshot_renamed",
+        serde_json::json!({ "snapshotId": snapshot_id, "newId": new_id, "name": new_name }),
+    );
+
+    Ok(new_id)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotBenchmark {
+    pub file_count: i32,
+    pub total_bytes: u64,
+    pub duration_ms: u64,
+    pub files_per_sec: f64,
+    pub mb_per_sec: f64,
+}
+
+/// Diagnostic: measure how fast a full snapshot copy of the project runs,
+/// using a temp directory so no snapshot is created
+#[tauri::command]
+pub fn benchmark_snapshot(project_path: String) -> Result<SnapshotBenchmark, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let bench = git::benchmark_snapshot_copy(Path::new(&project_path))?;
+
+    let seconds = (bench.duration_ms as f64 / 1000.0).max(0.001);
+
+    Ok(SnapshotBenchmark {
+        file_count: bench.file_count,
+        total_bytes: bench.total_bytes,
+        duration_ms: bench.duration_ms,
+        files_per_sec: bench.file_count as f64 / seconds,
+        mb_per_sec: bench.total_bytes as f64 / (1024.0 * 1024.0) / seconds,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExcludedFile {
+    /// Relative path; directories end with "/" and cover everything inside them
+    pub path: String,
+    /// "default", "gitignore" or "lumenignore"
+    pub source: String,
+    pub pattern: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotContentsPreview {
+    pub included: Vec<String>,
+    pub excluded: Vec<ExcludedFile>,
+}
+
+/// Show which files a snapshot would include and which are excluded (and why),
+/// without creating one
+#[tauri::command]
+pub fn preview_snapshot_contents(project_path: String) -> Result<SnapshotContentsPreview, String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    let path = Path::new(&project_path);
+    if !path.is_dir() {
+        return Err("Project path does not exist".to_string());
+    }
+
+    let preview = git::preview_snapshot_contents(path)?;
+
+    Ok(SnapshotContentsPreview {
+        included: preview.included,
+        excluded: preview
+            .excluded
+            .into_iter()
+            .map(|e| ExcludedFile {
+                path: e.path,
+                source: e.reason.source,
+                pattern: e.reason.pattern,
+            })
+            .collect(),
+    })
+}
+
 #[tauri::command]
 pub fn restore_snapshot(project_id: String, snapshot_id: String) -> Result<(), String> {
     let project_path = get_project_path(&project_id)?;
     let path = Path::new(&project_path);
 
-    git::restore_snapshot(path, &snapshot_id)
+    git::restore_snapshot(path, &snapshot_id)?;
+
+    session_events::record_for_project(
+        &project_id,
+        "snapshot_restored",
+        serde_json::json!({ "snapshotId": snapshot_id }),
+    );
+
+    Ok(())
+}
+
+/// Emit a `restore-progress` event every this many files (and for the last one)
+const RESTORE_PROGRESS_EVERY: usize = 25;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreProgressEvent {
+    pub operation_id: String,
+    pub project_id: String,
+    /// "restoring", "rollingBack", "completed", "cancelled" or "failed"
+    pub status: String,
+    pub files_written: usize,
+    pub total_files: usize,
+    /// Relative path of the file just written, while restoring
+    pub path: Option<String>,
+    /// Snapshot taken before the restore, which a cancelled or failed restore rolls back to
+    pub safety_snapshot_id: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Restore a snapshot, emitting `restore-progress` events as files are written.
+/// A snapshot of the current files is taken first; if the operation is cancelled
+/// through `cancel_operation` (or fails) partway, the project is rolled back to it.
+/// Returns the number of files restored.
+#[tauri::command]
+pub async fn restore_snapshot_with_progress(
+    app_handle: AppHandle,
+    project_id: String,
+    snapshot_id: String,
+    operation_id: String,
+) -> Result<usize, String> {
+    operations::begin(&operation_id)?;
+
+    let id = operation_id.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        restore_with_progress(&app_handle, &project_id, &snapshot_id, &id)
+    })
+    .await
+    .map_err(|e| format!("Failed to run restore: {}", e))
+    .and_then(|r| r);
+
+    operations::finish(&operation_id);
+    result
+}
+
+fn restore_with_progress(
+    app_handle: &AppHandle,
+    project_id: &str,
+    snapshot_id: &str,
+    operation_id: &str,
+) -> Result<usize, String> {
+    let project_path = get_project_path(project_id)?;
+    let path = Path::new(&project_path);
+
+    let short_id: String = snapshot_id.chars().take(7).collect();
+    let safety = git::create_snapshot_of_type(
+        path,
+        "Before restore",
+        Some(&format!("Automatic snapshot taken before restoring {}", short_id)),
+        "auto",
+    )?;
+
+    let emit = |status: &str, files_written: usize, total_files: usize, file: Option<String>, error: Option<String>| {
+        let _ = app_handle.emit(
+            "restore-progress",
+            RestoreProgressEvent {
+                operation_id: operation_id.to_string(),
+                project_id: project_id.to_string(),
+                status: status.to_string(),
+                files_written,
+                total_files,
+                path: file,
+                safety_snapshot_id: Some(safety.id.clone()),
+                error,
+            },
+        );
+    };
+
+    let mut total_files = 0;
+    let outcome = git::restore_snapshot_with_progress(path, snapshot_id, |progress| {
+        total_files = progress.total_files;
+        if progress.files_written % RESTORE_PROGRESS_EVERY == 0
+            || progress.files_written == progress.total_files
+        {
+            emit(
+                "restoring",
+                progress.files_written,
+                progress.total_files,
+                Some(progress.path.clone()),
+                None,
+            );
+        }
+        !operations::is_cancelled(operation_id)
+    });
+
+    let (files_written, created, error) = match outcome {
+        Ok(git::RestoreOutcome::Completed { files_written }) => {
+            session_events::record_for_project(
+                project_id,
+                "snapshot_restored",
+                serde_json::json!({ "snapshotId": snapshot_id, "safetySnapshotId": safety.id }),
+            );
+            emit("completed", files_written, total_files, None, None);
+            return Ok(files_written);
+        }
+        Ok(git::RestoreOutcome::Cancelled {
+            files_written,
+            created,
+        }) => (files_written, created, None),
+        Err(e) => (0, Vec::new(), Some(e)),
+    };
+
+    // Roll back: drop files the restore added, then put back the pre-restore contents
+    emit("rollingBack", files_written, total_files, None, error.clone());
+    for file in &created {
+        let _ = std::fs::remove_file(file);
+    }
+    if let Err(e) = git::restore_snapshot(path, &safety.id) {
+        let message = format!(
+            "Failed to roll back to snapshot {}: {}",
+            &safety.id[..7.min(safety.id.len())],
+            e
+        );
+        emit("failed", files_written, total_files, None, Some(message.clone()));
+        return Err(message);
+    }
+
+    match error {
+        Some(e) => {
+            emit("failed", files_written, total_files, None, Some(e.clone()));
+            Err(format!("Failed to restore snapshot, changes were rolled back: {}", e))
+        }
+        None => {
+            emit("cancelled", files_written, total_files, None, None);
+            Err("Restore cancelled, changes were rolled back".to_string())
+        }
+    }
 }
 
 #[tauri::command]
@@ -118,6 +562,75 @@ pub fn get_snapshot_diff(project_id: String, snapshot_id: String) -> Result<Snap
     })
 }
 
+/// Most-churned files returned by `get_churn_stats`
+const CHURN_MAX_FILES: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChurn {
+    pub path: String,
+    pub change_count: i32,
+    pub last_changed: String,
+}
+
+/// Rank files by how many of the last `last_n_snapshots` snapshots changed them
+/// (default 50), to find hotspots
+#[tauri::command]
+pub fn get_churn_stats(
+    project_id: String,
+    last_n_snapshots: Option<usize>,
+) -> Result<Vec<FileChurn>, String> {
+    let project_path = get_project_path(&project_id)?;
+    let last_n = last_n_snapshots.unwrap_or(50);
+    if last_n == 0 {
+        return Err("Include at least one snapshot".to_string());
+    }
+
+    let churn = git::get_churn_stats(Path::new(&project_path), last_n)?;
+
+    Ok(churn
+        .into_iter()
+        .take(CHURN_MAX_FILES)
+        .map(|f| FileChurn {
+            path: f.path,
+            change_count: f.change_count,
+            last_changed: f.last_changed,
+        })
+        .collect())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryDiffStats {
+    pub directory: String,
+    pub files_changed: i32,
+    pub additions: i32,
+    pub deletions: i32,
+}
+
+/// Get a snapshot's changes totalled per directory, grouped `depth` levels deep (default 1)
+#[tauri::command]
+pub fn get_snapshot_diff_by_directory(
+    project_id: String,
+    snapshot_id: String,
+    depth: Option<usize>,
+) -> Result<Vec<DirectoryDiffStats>, String> {
+    let project_path = get_project_path(&project_id)?;
+    let path = Path::new(&project_path);
+
+    let diff = git::get_snapshot_diff(path, &snapshot_id)?;
+
+    Ok(git::group_diff_by_directory(&diff, depth.unwrap_or(1))
+        .into_iter()
+        .map(|d| DirectoryDiffStats {
+            directory: d.directory,
+            files_changed: d.files_changed,
+            additions: d.additions,
+            deletions: d.deletions,
+        })
+        .collect())
+}
+
 #[tauri::command]
 pub fn get_file_at_snapshot(
     project_id: String,
@@ -130,6 +643,113 @@ pub fn get_file_at_snapshot(
     git::get_file_at_snapshot(path, &snapshot_id, &file_path)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdVersion {
+    pub snapshot_id: String,
+    pub snapshot_name: String,
+    pub timestamp: String,
+    /// "added", "modified" or "deleted" relative to the previous version
+    pub status: String,
+    /// Unified diff against the previous version
+    pub diff: String,
+    pub additions: i32,
+    pub deletions: i32,
+}
+
+/// Every version of CLAUDE.md captured in the project's snapshots, newest first,
+/// each with a diff against the version before it. Snapshots where the file was
+/// unchanged are skipped.
+#[tauri::command]
+pub fn claude_md_history(project_id: String) -> Result<Vec<ClaudeMdVersion>, String> {
+    let project_path = get_project_path(&project_id)?;
+    let path = Path::new(&project_path);
+
+    let mut versions = Vec::new();
+    let mut previous: Option<String> = None;
+
+    // list_snapshots is newest first; walk oldest first to diff against the prior version
+    for snapshot in git::list_snapshots(path)?.into_iter().rev() {
+        let content = git::get_file_at_snapshot(path, &snapshot.id, "CLAUDE.md")?;
+        if content == previous {
+            continue;
+        }
+
+        let status = match (&previous, &content) {
+            (None, Some(_)) => "added",
+            (Some(_), None) => "deleted",
+            _ => "modified",
+        };
+        let diff = git::diff_text(
+            previous.as_deref().unwrap_or(""),
+            content.as_deref().unwrap_or(""),
+            "CLAUDE.md",
+        )?;
+
+        versions.push(ClaudeMdVersion {
+            snapshot_id: snapshot.id,
+            snapshot_name: snapshot.name,
+            timestamp: snapshot.timestamp,
+            status: status.to_string(),
+            diff: diff.patch,
+            additions: diff.additions,
+            deletions: diff.deletions,
+        });
+        previous = content;
+    }
+
+    versions.reverse();
+    Ok(versions)
+}
+
+/// Diff the project's current files against a snapshot (the latest if none is given)
+#[tauri::command]
+pub fn diff_working_tree(
+    project_id: String,
+    snapshot_id: Option<String>,
+) -> Result<SnapshotDiff, String> {
+    let project_path = get_project_path(&project_id)?;
+    let path = Path::new(&project_path);
+
+    let diff = git::diff_snapshot_to_working(path, snapshot_id.as_deref())?;
+
+    Ok(SnapshotDiff {
+        files: diff
+            .files
+            .into_iter()
+            .map(|f| FileChange {
+                path: f.path,
+                status: f.status,
+                additions: f.additions,
+                deletions: f.deletions,
+            })
+            .collect(),
+        total_additions: diff.total_additions,
+        total_deletions: diff.total_deletions,
+    })
+}
+
+/// Restore one file from a snapshot without touching anything else in the project
+#[tauri::command]
+pub fn restore_file_from_snapshot(
+    project_id: String,
+    snapshot_id: String,
+    file_path: String,
+) -> Result<(), String> {
+    let project_path = get_project_path(&project_id)?;
+    let path = Path::new(&project_path);
+
+    git::restore_file_from_snapshot(path, &snapshot_id, &file_path)?;
+
+    session_events::record_for_project(
+        &project_id,
+        "snapshot_restored",
+        serde_json::json!({ "snapshotId": snapshot_id, "filePath": file_path }),
+    );
+
+    Ok(())
+}
+
 #[tauri::command]
 pub fn compare_snapshots(
     project_id: String,
@@ -156,3 +776,71 @@ pub fn compare_snapshots(
         total_deletions: diff.total_deletions,
     })
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsavedWorkWarning {
+    pub safe_to_switch: bool,
+    /// Files changed since the latest snapshot
+    pub unsnapshotted_changes: usize,
+    pub has_git_repo: bool,
+    /// Staged, modified and untracked files in the project's own git repo
+    pub uncommitted_changes: usize,
+    /// Whether taking a snapshot now would capture all of the pending work
+    pub auto_snapshot_would_capture: bool,
+    pub message: String,
+}
+
+/// Check for unsnapshotted / uncommitted work before navigating away from a project
+#[tauri::command]
+pub fn get_unsaved_work_warning(project_id: String) -> Result<UnsavedWorkWarning, String> {
+    let project_path = get_project_path(&project_id)?;
+    let path = Path::new(&project_path);
+
+    let unsnapshotted_changes = git::get_working_tree_status(path)?.total();
+
+    let has_git_repo = path.join(".git").exists();
+    let uncommitted: Vec<String> = if has_git_repo {
+        github::get_git_status(project_path.clone())
+            .map(|s| {
+                let mut files = s.staged;
+                files.extend(s.modified);
+                files.extend(s.untracked);
+                files.sort();
+                files.dedup();
+                files
+            })
+            .unwrap_or_default()
+    } else {
+        vec![]
+    };
+
+    // Snapshots skip excluded files (secrets, build output), so those can't be captured
+    let auto_snapshot_would_capture = uncommitted
+        .iter()
+        .all(|f| !git::should_exclude(&path.join(f), path));
+
+    let safe_to_switch = unsnapshotted_changes == 0 && uncommitted.is_empty();
+
+    let message = if safe_to_switch {
+        "All work is saved".to_string()
+    } else {
+        let mut parts = Vec::new();
+        if unsnapshotted_changes > 0 {
+            parts.push(format!("{} file(s) changed since the last snapshot", unsnapshotted_changes));
+        }
+        if !uncommitted.is_empty() {
+            parts.push(format!("{} uncommitted file(s)", uncommitted.len()));
+        }
+        parts.join(", ")
+    };
+
+    Ok(UnsavedWorkWarning {
+        safe_to_switch,
+        unsnapshotted_changes,
+        has_git_repo,
+        uncommitted_changes: uncommitted.len(),
+        auto_snapshot_would_capture,
+        message,
+    })
+}
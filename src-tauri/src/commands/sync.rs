@@ -1,16 +1,41 @@
+use crate::commands::prompts::{self, Prompt};
+use crate::commands::session_memory::SessionMemory;
+use crate::config;
+use crate::db;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::process::Command;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
 
-#[derive(Debug, Serialize, Deserialize)]
+/// How often the auto-sync task re-reads its settings
+const AUTO_SYNC_POLL_SECS: u64 = 30;
+
+lazy_static::lazy_static! {
+    /// Held while the sync repo is changed, so manual and automatic syncs don't
+    /// run git in it at the same time
+    static ref SYNC_LOCK: Mutex<()> = Mutex::new(());
+}
+
+fn lock_sync() -> MutexGuard<'static, ()> {
+    SYNC_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SyncRepoResult {
     pub success: bool,
     pub url: Option<String>,
     pub message: String,
+    /// Files changed both locally and on the remote, when sync stopped on a conflict
+    #[serde(default)]
+    pub conflicts: Vec<String>,
 }
 
 /// Create a new lumen-sync private repository
 #[tauri::command]
 pub fn create_sync_repo() -> Result<SyncRepoResult, String> {
+    let _guard = lock_sync();
     // Check if gh is authenticated
     let auth_check = Command::new("gh")
         .args(["auth", "status"])
@@ -22,6 +47,7 @@ pub fn create_sync_repo() -> Result<SyncRepoResult, String> {
             success: false,
             url: None,
             message: "GitHub CLI not authenticated. Run 'gh auth login' first.".to_string(),
+            ..Default::default()
         });
     }
 
@@ -40,6 +66,7 @@ pub fn create_sync_repo() -> Result<SyncRepoResult, String> {
             success: false,
             url: None,
             message: "Could not determine GitHub username".to_string(),
+            ..Default::default()
         });
     }
 
@@ -55,6 +82,7 @@ pub fn create_sync_repo() -> Result<SyncRepoResult, String> {
                 success: true,
                 url: Some(format!("github.com/{}/lumen-sync", username)),
                 message: "Connected to existing lumen-sync repository".to_string(),
+                ..Default::default()
             });
         }
     }
@@ -74,7 +102,7 @@ pub fn create_sync_repo() -> Result<SyncRepoResult, String> {
 
     if create_output.status.success() {
         // Clone it to ~/.lumen/sync/
-        let sync_dir = get_sync_dir()?;
+        let sync_dir = use_default_sync_dir()?;
 
         // Remove existing sync dir if it exists
         if sync_dir.exists() {
@@ -100,6 +128,7 @@ pub fn create_sync_repo() -> Result<SyncRepoResult, String> {
                 success: true,
                 url: Some(format!("github.com/{}/lumen-sync", username)),
                 message: "Created and configured lumen-sync repository".to_string(),
+                ..Default::default()
             })
         } else {
             Ok(SyncRepoResult {
@@ -109,6 +138,7 @@ pub fn create_sync_repo() -> Result<SyncRepoResult, String> {
                     "Failed to clone repo: {}",
                     String::from_utf8_lossy(&clone_output.stderr)
                 ),
+                ..Default::default()
             })
         }
     } else {
@@ -119,6 +149,7 @@ pub fn create_sync_repo() -> Result<SyncRepoResult, String> {
                 "Failed to create repo: {}",
                 String::from_utf8_lossy(&create_output.stderr)
             ),
+            ..Default::default()
         })
     }
 }
@@ -126,6 +157,7 @@ pub fn create_sync_repo() -> Result<SyncRepoResult, String> {
 /// Connect to an existing lumen-sync repository
 #[tauri::command]
 pub fn connect_sync_repo() -> Result<SyncRepoResult, String> {
+    let _guard = lock_sync();
     // Check if gh is authenticated
     let auth_check = Command::new("gh")
         .args(["auth", "status"])
@@ -137,6 +169,7 @@ pub fn connect_sync_repo() -> Result<SyncRepoResult, String> {
             success: false,
             url: None,
             message: "GitHub CLI not authenticated. Run 'gh auth login' first.".to_string(),
+            ..Default::default()
         });
     }
 
@@ -155,6 +188,7 @@ pub fn connect_sync_repo() -> Result<SyncRepoResult, String> {
             success: false,
             url: None,
             message: "Could not determine GitHub username".to_string(),
+            ..Default::default()
         });
     }
 
@@ -169,11 +203,12 @@ pub fn connect_sync_repo() -> Result<SyncRepoResult, String> {
             success: false,
             url: None,
             message: "No lumen-sync repository found. Create one first.".to_string(),
+            ..Default::default()
         });
     }
 
     // Clone it to ~/.lumen/sync/
-    let sync_dir = get_sync_dir()?;
+    let sync_dir = use_default_sync_dir()?;
 
     // Remove existing sync dir if it exists
     if sync_dir.exists() {
@@ -196,6 +231,7 @@ pub fn connect_sync_repo() -> Result<SyncRepoResult, String> {
             success: true,
             url: Some(format!("github.com/{}/lumen-sync", username)),
             message: "Connected to existing lumen-sync repository".to_string(),
+            ..Default::default()
         })
     } else {
         Ok(SyncRepoResult {
@@ -205,18 +241,148 @@ pub fn connect_sync_repo() -> Result<SyncRepoResult, String> {
                 "Failed to clone repo: {}",
                 String::from_utf8_lossy(&clone_output.stderr)
             ),
+            ..Default::default()
         })
     }
 }
 
-/// Get the sync directory path
-fn get_sync_dir() -> Result<std::path::PathBuf, String> {
+/// Connect sync to any git remote (GitLab, Gitea, a private server, ...) by
+/// cloning it with plain git, for users who don't sync through GitHub.
+/// The clone replaces the default sync folder only once it succeeds; a folder with
+/// commits that were never pushed is kept unless `confirm` is true.
+#[tauri::command]
+pub fn connect_sync_remote(url: String, confirm: bool) -> Result<SyncRepoResult, String> {
+    let _guard = lock_sync();
+    let url = url.trim().to_string();
+    if url.is_empty() {
+        return Err("Remote URL cannot be empty".to_string());
+    }
+
+    let sync_dir = default_sync_dir()?;
+    let unpushed = unpushed_commit_count(&sync_dir);
+    if unpushed > 0 && !confirm {
+        return Ok(SyncRepoResult {
+            success: false,
+            url: None,
+            message: format!(
+                "The current sync folder has {} unpushed commit(s) that connecting to a new remote would discard. Confirm to continue.",
+                unpushed
+            ),
+            ..Default::default()
+        });
+    }
+
+    let parent = sync_dir.parent().ok_or("Invalid sync directory")?;
+    std::fs::create_dir_all(parent)
+        .map_err(|e| format!("Failed to create sync directory: {}", e))?;
+
+    // Clone next to the sync dir, so a bad URL or failed auth leaves it untouched
+    let clone_dir = parent.join(format!("sync-clone-{}", uuid::Uuid::new_v4()));
+    let clone_output = Command::new("git")
+        .args(["clone", "--", &url, clone_dir.to_string_lossy().as_ref()])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| format!("Failed to clone repo: {}", e))?;
+
+    if !clone_output.status.success() {
+        let _ = std::fs::remove_dir_all(&clone_dir);
+        return Ok(SyncRepoResult {
+            success: false,
+            url: None,
+            message: format!(
+                "Failed to clone repo: {}",
+                String::from_utf8_lossy(&clone_output.stderr)
+            ),
+            ..Default::default()
+        });
+    }
+
+    // A new, empty remote gets the same scaffolding as a freshly created repo
+    if let Err(e) = init_sync_structure(&clone_dir) {
+        let _ = std::fs::remove_dir_all(&clone_dir);
+        return Err(e);
+    }
+
+    if sync_dir.exists() {
+        std::fs::remove_dir_all(&sync_dir)
+            .map_err(|e| format!("Failed to replace sync directory: {}", e))?;
+    }
+    std::fs::rename(&clone_dir, &sync_dir)
+        .map_err(|e| format!("Failed to move clone into place: {}", e))?;
+    use_default_sync_dir()?;
+
+    Ok(SyncRepoResult {
+        success: true,
+        url: Some(redact_url_credentials(&url)),
+        message: "Connected to sync remote".to_string(),
+        ..Default::default()
+    })
+}
+
+/// Commits on local branches that aren't on any remote (all of them for a local-only repo)
+fn unpushed_commit_count(sync_dir: &std::path::Path) -> usize {
+    if !sync_dir.join(".git").exists() {
+        return 0;
+    }
+    Command::new("git")
+        .current_dir(sync_dir)
+        .args(["rev-list", "--count", "--branches", "--not", "--remotes"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Drop any `user:password@` part of a remote URL before showing it
+fn redact_url_credentials(url: &str) -> String {
+    match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+            match authority.rsplit_once('@') {
+                Some((_, host)) => format!("{}://{}{}", scheme, host, path),
+                None => url.to_string(),
+            }
+        }
+        None => url.to_string(),
+    }
+}
+
+/// Get the default sync directory path (~/.lumen/sync)
+fn default_sync_dir() -> Result<std::path::PathBuf, String> {
     let home = dirs::home_dir().ok_or("Could not find home directory")?;
     Ok(home.join(".lumen").join("sync"))
 }
 
-/// Initialize the sync directory structure
-fn init_sync_structure(sync_dir: &std::path::Path) -> Result<(), String> {
+/// Get the sync directory path: the configured local sync dir, or the default
+fn get_sync_dir() -> Result<std::path::PathBuf, String> {
+    match config::load_config()?.sync_dir {
+        Some(dir) => Ok(std::path::PathBuf::from(dir)),
+        None => default_sync_dir(),
+    }
+}
+
+/// Point sync back at the default directory (remote sync always lives there)
+fn use_default_sync_dir() -> Result<std::path::PathBuf, String> {
+    let mut cfg = config::load_config()?;
+    if cfg.sync_dir.take().is_some() {
+        config::save_config(&cfg)?;
+    }
+    default_sync_dir()
+}
+
+/// Whether the sync repo has a remote to pull from / push to
+fn has_remote(sync_dir: &std::path::Path) -> bool {
+    Command::new("git")
+        .current_dir(sync_dir)
+        .args(["remote"])
+        .output()
+        .map(|o| !String::from_utf8_lossy(&o.stdout).trim().is_empty())
+        .unwrap_or(false)
+}
+
+/// Create the prompts/projects/settings layout in a sync directory
+fn create_sync_structure(sync_dir: &std::path::Path) -> Result<(), String> {
     // Create directories
     std::fs::create_dir_all(sync_dir.join("prompts"))
         .map_err(|e| format!("Failed to create prompts dir: {}", e))?;
@@ -240,6 +406,13 @@ fn init_sync_structure(sync_dir: &std::path::Path) -> Result<(), String> {
         .map_err(|e| format!("Failed to create README: {}", e))?;
     }
 
+    Ok(())
+}
+
+/// Initialize the sync directory structure
+fn init_sync_structure(sync_dir: &std::path::Path) -> Result<(), String> {
+    create_sync_structure(sync_dir)?;
+
     // Commit the initial structure
     let _ = Command::new("git")
         .current_dir(sync_dir)
@@ -251,17 +424,69 @@ fn init_sync_structure(sync_dir: &std::path::Path) -> Result<(), String> {
         .args(["commit", "-m", "Initialize Lumen sync structure"])
         .output();
 
-    let _ = Command::new("git")
-        .current_dir(sync_dir)
-        .args(["push"])
-        .output();
+    if has_remote(sync_dir) {
+        let _ = Command::new("git")
+            .current_dir(sync_dir)
+            .args(["push"])
+            .output();
+    }
 
     Ok(())
 }
 
-/// Pull latest from sync repo
+/// Set up sync in a local directory (e.g. inside a Dropbox folder) as a plain
+/// git repo with no remote, and point sync at it
+#[tauri::command]
+pub fn init_local_sync(dir: String) -> Result<SyncRepoResult, String> {
+    let _guard = lock_sync();
+    let sync_dir = std::path::PathBuf::from(&dir);
+    if !sync_dir.is_absolute() {
+        return Err("Sync directory must be an absolute path".to_string());
+    }
+
+    std::fs::create_dir_all(&sync_dir)
+        .map_err(|e| format!("Failed to create sync directory: {}", e))?;
+
+    if !sync_dir.join(".git").exists() {
+        let output = Command::new("git")
+            .current_dir(&sync_dir)
+            .args(["init"])
+            .output()
+            .map_err(|e| format!("Failed to init git repo: {}", e))?;
+
+        if !output.status.success() {
+            return Ok(SyncRepoResult {
+                success: false,
+                url: None,
+                message: String::from_utf8_lossy(&output.stderr).to_string(),
+                ..Default::default()
+            });
+        }
+    }
+
+    init_sync_structure(&sync_dir)?;
+
+    let mut cfg = config::load_config()?;
+    cfg.sync_dir = Some(sync_dir.to_string_lossy().to_string());
+    config::save_config(&cfg)?;
+
+    Ok(SyncRepoResult {
+        success: true,
+        url: None,
+        message: format!("Local sync set up in {}", sync_dir.display()),
+        ..Default::default()
+    })
+}
+
+/// Pull latest from sync repo and apply its prompts and memories to the
+/// database, keeping whichever copy of each is newer
 #[tauri::command]
 pub fn sync_pull() -> Result<SyncRepoResult, String> {
+    let _guard = lock_sync();
+    pull_changes()
+}
+
+fn pull_changes() -> Result<SyncRepoResult, String> {
     let sync_dir = get_sync_dir()?;
 
     if !sync_dir.exists() {
@@ -269,33 +494,39 @@ pub fn sync_pull() -> Result<SyncRepoResult, String> {
             success: false,
             url: None,
             message: "Sync not configured".to_string(),
+            ..Default::default()
         });
     }
 
-    let output = Command::new("git")
-        .current_dir(&sync_dir)
-        .args(["pull", "--rebase"])
-        .output()
-        .map_err(|e| format!("Failed to pull: {}", e))?;
-
-    if output.status.success() {
-        Ok(SyncRepoResult {
-            success: true,
-            url: None,
-            message: "Pulled latest changes".to_string(),
-        })
-    } else {
-        Ok(SyncRepoResult {
-            success: false,
-            url: None,
-            message: String::from_utf8_lossy(&output.stderr).to_string(),
-        })
+    // A local sync folder may be shared some other way (e.g. Dropbox), so its
+    // files are still applied
+    if has_remote(&sync_dir) {
+        if let Err(result) = pull_rebase(&sync_dir)? {
+            return Ok(result);
+        }
     }
+
+    let (prompts, memories) = import_sync_data(&sync_dir, true)?;
+
+    Ok(SyncRepoResult {
+        success: true,
+        url: None,
+        message: format!(
+            "Pulled latest changes ({} prompts, {} memories updated)",
+            prompts, memories
+        ),
+        ..Default::default()
+    })
 }
 
-/// Push changes to sync repo
+/// Write the database's prompts and memories to the sync repo, then commit and push
 #[tauri::command]
 pub fn sync_push() -> Result<SyncRepoResult, String> {
+    let _guard = lock_sync();
+    push_changes()
+}
+
+fn push_changes() -> Result<SyncRepoResult, String> {
     let sync_dir = get_sync_dir()?;
 
     if !sync_dir.exists() {
@@ -303,9 +534,13 @@ pub fn sync_push() -> Result<SyncRepoResult, String> {
             success: false,
             url: None,
             message: "Sync not configured".to_string(),
+            ..Default::default()
         });
     }
 
+    let (prompts, memories) = export_sync_data(&sync_dir, false)?;
+    let summary = format!("{} prompts, {} memories written", prompts, memories);
+
     // Add all changes
     let _ = Command::new("git")
         .current_dir(&sync_dir)
@@ -318,9 +553,67 @@ pub fn sync_push() -> Result<SyncRepoResult, String> {
         .args(["commit", "-m", "Sync from Lumen"])
         .output();
 
-    // Push
-    let output = Command::new("git")
+    if !has_remote(&sync_dir) {
+        return Ok(SyncRepoResult {
+            success: true,
+            url: None,
+            message: format!("Committed changes locally ({})", summary),
+            ..Default::default()
+        });
+    }
+
+    // Check the remote first, so a diverged history is reported instead of a failed push
+    let fetch = Command::new("git")
         .current_dir(&sync_dir)
+        .args(["fetch", "--quiet"])
+        .output()
+        .map_err(|e| format!("Failed to fetch: {}", e))?;
+
+    if !fetch.status.success() {
+        return Ok(SyncRepoResult {
+            success: false,
+            url: None,
+            message: String::from_utf8_lossy(&fetch.stderr).to_string(),
+            ..Default::default()
+        });
+    }
+
+    if let Some(divergence) = get_divergence(&sync_dir) {
+        if divergence.ahead == 0 {
+            let message = if divergence.behind == 0 {
+                "Nothing to push; already up to date"
+            } else {
+                "Nothing to push; the remote has newer changes, pull to apply them"
+            };
+            return Ok(SyncRepoResult {
+                success: true,
+                url: None,
+                message: message.to_string(),
+                ..Default::default()
+            });
+        }
+        if divergence.behind > 0 {
+            if !divergence.conflicts.is_empty() {
+                return Ok(conflict_result(
+                    "The sync repo and its remote both changed the same files",
+                    divergence.conflicts,
+                ));
+            }
+            // Changes to different files can simply be replayed on top of the remote
+            if let Err(result) = pull_rebase(&sync_dir)? {
+                return Ok(result);
+            }
+            import_sync_data(&sync_dir, true)?;
+        }
+    }
+
+    push(&sync_dir, format!("Pushed changes ({})", summary))
+}
+
+/// Push the sync repo, reporting git's error output on failure
+fn push(sync_dir: &std::path::Path, message: String) -> Result<SyncRepoResult, String> {
+    let output = Command::new("git")
+        .current_dir(sync_dir)
         .args(["push"])
         .output()
         .map_err(|e| format!("Failed to push: {}", e))?;
@@ -329,13 +622,782 @@ pub fn sync_push() -> Result<SyncRepoResult, String> {
         Ok(SyncRepoResult {
             success: true,
             url: None,
-            message: "Pushed changes".to_string(),
+            message,
+            ..Default::default()
         })
     } else {
         Ok(SyncRepoResult {
             success: false,
             url: None,
             message: String::from_utf8_lossy(&output.stderr).to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+/// A failed sync that needs `sync_resolve`
+fn conflict_result(reason: &str, conflicts: Vec<String>) -> SyncRepoResult {
+    SyncRepoResult {
+        success: false,
+        url: None,
+        message: format!(
+            "{}. Resolve by keeping your copies (\"ours\"), the remote's (\"theirs\"), or rebasing your changes onto the remote (\"rebase\").",
+            reason
+        ),
+        conflicts,
+    }
+}
+
+/// How the local sync branch and its upstream have diverged
+struct Divergence {
+    ahead: usize,
+    behind: usize,
+    /// Files changed on both sides since they split
+    conflicts: Vec<String>,
+}
+
+/// Compare the sync repo with its upstream (as of the last fetch).
+/// None if there's no upstream branch.
+fn get_divergence(sync_dir: &std::path::Path) -> Option<Divergence> {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git").current_dir(sync_dir).args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let counts = git(&["rev-list", "--left-right", "--count", "HEAD...@{upstream}"])?;
+    let (ahead, behind) = counts.split_once('\t')?;
+    let ahead: usize = ahead.parse().ok()?;
+    let behind: usize = behind.parse().ok()?;
+
+    let mut conflicts = Vec::new();
+    if ahead > 0 && behind > 0 {
+        let base = git(&["merge-base", "HEAD", "@{upstream}"])?;
+        let changed = |tip: &str| -> Vec<String> {
+            git(&["diff", "--name-only", &format!("{}..{}", base, tip)])
+                .map(|out| out.lines().map(String::from).collect())
+                .unwrap_or_default()
+        };
+        let theirs = changed("@{upstream}");
+        conflicts = changed("HEAD")
+            .into_iter()
+            .filter(|file| theirs.contains(file))
+            .collect();
+    }
+
+    Some(Divergence {
+        ahead,
+        behind,
+        conflicts,
+    })
+}
+
+/// Files left with merge conflicts
+fn conflicted_files(sync_dir: &std::path::Path) -> Vec<String> {
+    Command::new("git")
+        .current_dir(sync_dir)
+        .args(["diff", "--name-only", "--diff-filter=U"])
+        .output()
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .filter(|l| !l.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `git pull --rebase`. If it stops on conflicts the rebase is aborted, leaving the
+/// repo as it was, and `Err` carries a result listing the conflicting files.
+fn pull_rebase(sync_dir: &std::path::Path) -> Result<Result<(), SyncRepoResult>, String> {
+    let output = Command::new("git")
+        .current_dir(sync_dir)
+        .args(["pull", "--rebase"])
+        .output()
+        .map_err(|e| format!("Failed to pull: {}", e))?;
+
+    if output.status.success() {
+        return Ok(Ok(()));
+    }
+
+    let conflicts = conflicted_files(sync_dir);
+    if conflicts.is_empty() {
+        return Ok(Err(SyncRepoResult {
+            success: false,
+            url: None,
+            message: String::from_utf8_lossy(&output.stderr).to_string(),
+            ..Default::default()
+        }));
+    }
+
+    let _ = Command::new("git")
+        .current_dir(sync_dir)
+        .args(["rebase", "--abort"])
+        .output();
+
+    Ok(Err(conflict_result(
+        "Your sync changes conflict with the remote's",
+        conflicts,
+    )))
+}
+
+/// Resolve a sync conflict reported by `sync_push` or `sync_pull`, then push:
+/// - "ours": merge the remote, keeping local changes where both changed the same lines
+/// - "theirs": merge the remote, keeping its changes where both changed the same lines,
+///   and apply them over the database
+/// - "rebase": replay local commits on top of the remote; reports conflicts if that fails
+#[tauri::command]
+pub fn sync_resolve(strategy: String) -> Result<SyncRepoResult, String> {
+    let _guard = lock_sync();
+    let sync_dir = get_sync_dir()?;
+
+    if !sync_dir.join(".git").exists() || !has_remote(&sync_dir) {
+        return Ok(SyncRepoResult {
+            success: false,
+            url: None,
+            message: "Sync has no remote to resolve against".to_string(),
+            ..Default::default()
+        });
+    }
+
+    let fetch = Command::new("git")
+        .current_dir(&sync_dir)
+        .args(["fetch", "--quiet"])
+        .output()
+        .map_err(|e| format!("Failed to fetch: {}", e))?;
+
+    if !fetch.status.success() {
+        return Ok(SyncRepoResult {
+            success: false,
+            url: None,
+            message: String::from_utf8_lossy(&fetch.stderr).to_string(),
+            ..Default::default()
+        });
+    }
+
+    match strategy.as_str() {
+        "ours" | "theirs" => {
+            let output = Command::new("git")
+                .current_dir(&sync_dir)
+                .args(["merge", "--no-edit", "-X", &strategy, "@{upstream}"])
+                .output()
+                .map_err(|e| format!("Failed to merge: {}", e))?;
+
+            if !output.status.success() {
+                let conflicts = conflicted_files(&sync_dir);
+                let _ = Command::new("git")
+                    .current_dir(&sync_dir)
+                    .args(["merge", "--abort"])
+                    .output();
+                return Ok(SyncRepoResult {
+                    success: false,
+                    url: None,
+                    message: String::from_utf8_lossy(&output.stderr).to_string(),
+                    conflicts,
+                });
+            }
+
+            // With "theirs" the remote's copies win in the database too
+            let (prompts, memories) = import_sync_data(&sync_dir, strategy == "ours")?;
+            push(
+                &sync_dir,
+                format!(
+                    "Merged remote changes keeping {} ({} prompts, {} memories applied)",
+                    if strategy == "ours" { "yours" } else { "the remote's" },
+                    prompts,
+                    memories
+                ),
+            )
+        }
+        "rebase" => {
+            if let Err(result) = pull_rebase(&sync_dir)? {
+                return Ok(result);
+            }
+            let (prompts, memories) = import_sync_data(&sync_dir, true)?;
+            push(
+                &sync_dir,
+                format!(
+                    "Rebased onto remote changes ({} prompts, {} memories applied)",
+                    prompts, memories
+                ),
+            )
+        }
+        _ => Err(format!(
+            "Unknown sync resolution strategy: {} (expected \"ours\", \"theirs\" or \"rebase\")",
+            strategy
+        )),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncCommit {
+    pub sha: String,
+    pub message: String,
+    pub date: String,
+}
+
+/// List recent commits in the sync repo, newest first
+#[tauri::command]
+pub fn list_sync_history(limit: Option<usize>) -> Result<Vec<SyncCommit>, String> {
+    let sync_dir = get_sync_dir()?;
+    if !sync_dir.join(".git").exists() {
+        return Err("Sync not configured".to_string());
+    }
+
+    let output = Command::new("git")
+        .current_dir(&sync_dir)
+        .args([
+            "log",
+            &format!("-n{}", limit.unwrap_or(50)),
+            "--format=%H%x09%aI%x09%s",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to read sync history: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.splitn(3, '\t').collect();
+            if parts.len() < 3 {
+                return None;
+            }
+            Some(SyncCommit {
+                sha: parts[0].to_string(),
+                date: parts[1].to_string(),
+                message: parts[2].to_string(),
+            })
         })
+        .collect())
+}
+
+/// Parse a stored timestamp: RFC 3339, or SQLite's `CURRENT_TIMESTAMP` format (UTC)
+fn parse_timestamp(value: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&chrono::Utc))
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|t| t.and_utc())
+        })
+}
+
+/// Whether timestamp `a` is later than `b`. Unparseable timestamps count as oldest.
+fn is_later(a: &str, b: &str) -> bool {
+    parse_timestamp(a) > parse_timestamp(b)
+}
+
+/// When a prompt last changed: the latest of its creation, last edit and last use
+fn prompt_updated_at(prompt: &Prompt) -> &str {
+    [&prompt.updated_at, &prompt.last_used_at]
+        .into_iter()
+        .flatten()
+        .fold(prompt.created_at.as_str(), |latest, t| {
+            if is_later(t, latest) {
+                t
+            } else {
+                latest
+            }
+        })
+}
+
+/// Ids become file names, so they must not contain path components
+fn is_safe_file_name(id: &str) -> bool {
+    !id.is_empty() && !id.contains(['/', '\\']) && id != "." && id != ".."
+}
+
+/// Write a JSON file only if its content changed, so unchanged data doesn't show up in git.
+/// With `dry_run`, only report whether it would be written.
+fn write_json_if_changed<T: Serialize>(
+    path: &std::path::Path,
+    value: &T,
+    dry_run: bool,
+) -> Result<bool, String> {
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize sync data: {}", e))?;
+    if std::fs::read_to_string(path).ok().as_deref() == Some(content.as_str()) {
+        return Ok(false);
+    }
+    if dry_run {
+        return Ok(true);
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create sync directory: {}", e))?;
+    }
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(true)
+}
+
+/// Every session memory in the database
+fn all_session_memories(conn: &rusqlite::Connection) -> Result<Vec<SessionMemory>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, project_id, claude_session_id, session_date, summary, key_decisions, open_threads, files_touched, duration_minutes, created_at
+             FROM session_memories
+             ORDER BY created_at DESC",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let memories = stmt
+        .query_map([], |row| {
+            let json_list = |value: Option<String>| -> Vec<String> {
+                serde_json::from_str(&value.unwrap_or_else(|| "[]".to_string())).unwrap_or_default()
+            };
+
+            Ok(SessionMemory {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                claude_session_id: row.get(2)?,
+                session_date: row.get(3)?,
+                summary: row.get(4)?,
+                key_decisions: json_list(row.get(5)?),
+                open_threads: json_list(row.get(6)?),
+                files_touched: json_list(row.get(7)?),
+                duration_minutes: row.get(8)?,
+                created_at: row.get(9)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query session memories: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to collect session memories: {}", e))?;
+
+    Ok(memories)
+}
+
+/// Local project names by id. Project ids are generated per machine, so synced
+/// memories are filed under the project's name instead.
+fn project_names(conn: &rusqlite::Connection) -> Result<HashMap<String, String>, String> {
+    conn.prepare("SELECT id, name FROM projects")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<_, _>>()
+        })
+        .map_err(|e| format!("Failed to query projects: {}", e))
+}
+
+/// Write each prompt to `prompts/<id>.json` and each project's session memories to
+/// `projects/<project name>/memories.json`. A copy already in the sync dir that is
+/// newer than the database's is kept, and entries only in the sync dir are left alone.
+/// Returns how many prompts and memories were (or, with `dry_run`, would be) written.
+fn export_sync_data(sync_dir: &std::path::Path, dry_run: bool) -> Result<(usize, usize), String> {
+    let mut prompt_count = 0;
+    let mut memory_count = 0;
+
+    for prompt in prompts::list_prompts()? {
+        if !is_safe_file_name(&prompt.id) {
+            continue;
+        }
+        let path = sync_dir.join("prompts").join(format!("{}.json", prompt.id));
+
+        let synced = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| serde_json::from_str::<Prompt>(&c).ok());
+        if synced.is_some_and(|s| is_later(prompt_updated_at(&s), prompt_updated_at(&prompt))) {
+            continue;
+        }
+
+        if write_json_if_changed(&path, &prompt, dry_run)? {
+            prompt_count += 1;
+        }
     }
+
+    let conn = db::get_connection()?;
+    let names = project_names(&conn)?;
+    let mut by_project: HashMap<String, Vec<SessionMemory>> = HashMap::new();
+    for memory in all_session_memories(&conn)? {
+        let Some(name) = names.get(&memory.project_id) else {
+            continue;
+        };
+        by_project.entry(name.clone()).or_default().push(memory);
+    }
+
+    for (project_name, memories) in by_project {
+        if !is_safe_file_name(&project_name) {
+            continue;
+        }
+        let path = sync_dir.join("projects").join(&project_name).join("memories.json");
+
+        let mut merged: HashMap<String, SessionMemory> = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| serde_json::from_str::<Vec<SessionMemory>>(&c).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|m| (m.id.clone(), m))
+            .collect();
+
+        let mut changed = 0;
+        for memory in memories {
+            let keep_synced = merged
+                .get(&memory.id)
+                .is_some_and(|synced| is_later(&synced.created_at, &memory.created_at));
+            if !keep_synced {
+                merged.insert(memory.id.clone(), memory);
+                changed += 1;
+            }
+        }
+
+        let mut merged: Vec<SessionMemory> = merged.into_values().collect();
+        merged.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| a.id.cmp(&b.id)));
+
+        if write_json_if_changed(&path, &merged, dry_run)? {
+            memory_count += changed;
+        }
+    }
+
+    Ok((prompt_count, memory_count))
+}
+
+/// Upsert the prompts (`prompts/<id>.json`) and session memories
+/// (`projects/<project name>/memories.json`) stored in the sync dir into the database.
+/// With `keep_newer`, a database copy newer than the synced one is left as is;
+/// otherwise the synced data overwrites it. Memories are attached to the local project
+/// of the same name; those of unknown (or ambiguously named) projects are skipped.
+/// Returns how many prompts and memories were applied.
+fn import_sync_data(sync_dir: &std::path::Path, keep_newer: bool) -> Result<(usize, usize), String> {
+    let conn = db::get_connection()?;
+    let mut prompt_count = 0;
+    let mut memory_count = 0;
+
+    let local_prompts: HashMap<String, Prompt> = if keep_newer {
+        prompts::list_prompts()?.into_iter().map(|p| (p.id.clone(), p)).collect()
+    } else {
+        HashMap::new()
+    };
+    let local_memories: HashMap<String, String> = if keep_newer {
+        all_session_memories(&conn)?
+            .into_iter()
+            .map(|m| (m.id, m.created_at))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    // Memories of projects this machine doesn't know can't be stored (project_id is a foreign key).
+    // A name shared by several local projects maps to none of them.
+    let names = project_names(&conn)?;
+    let mut ids_by_name: HashMap<&str, Option<&str>> = HashMap::new();
+    for (id, name) in &names {
+        ids_by_name
+            .entry(name.as_str())
+            .and_modify(|existing| *existing = None)
+            .or_insert(Some(id.as_str()));
+    }
+
+    if let Ok(entries) = std::fs::read_dir(sync_dir.join("prompts")) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map(|e| e != "json").unwrap_or(true) {
+                continue;
+            }
+            let Some(prompt) = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|c| serde_json::from_str::<Prompt>(&c).ok())
+            else {
+                continue;
+            };
+
+            if local_prompts
+                .get(&prompt.id)
+                .is_some_and(|local| !is_later(prompt_updated_at(&prompt), prompt_updated_at(local)))
+            {
+                continue;
+            }
+
+            conn.execute(
+                "INSERT INTO prompts (id, name, content, tags, variables, usage_count, last_used_at, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                 ON CONFLICT(id) DO UPDATE SET name = ?2, content = ?3, tags = ?4, variables = ?5,
+                    usage_count = ?6, last_used_at = ?7, created_at = ?8, updated_at = ?9",
+                rusqlite::params![
+                    prompt.id,
+                    prompt.name,
+                    prompt.content,
+                    serde_json::to_string(&prompt.tags).unwrap_or_else(|_| "[]".to_string()),
+                    serde_json::to_string(&prompt.variables).unwrap_or_else(|_| "[]".to_string()),
+                    prompt.usage_count,
+                    prompt.last_used_at,
+                    prompt.created_at,
+                    prompt.updated_at,
+                ],
+            )
+            .map_err(|e| format!("Failed to restore prompt: {}", e))?;
+            prompt_count += 1;
+        }
+    }
+
+    if let Ok(entries) = std::fs::read_dir(sync_dir.join("projects")) {
+        for entry in entries.flatten() {
+            let folder = entry.file_name().to_string_lossy().to_string();
+            // Older sync repos named the folder after the project id
+            let project_id = match ids_by_name.get(folder.as_str()) {
+                Some(Some(id)) => id.to_string(),
+                Some(None) => continue,
+                None if names.contains_key(&folder) => folder,
+                None => continue,
+            };
+            let Some(memories) = std::fs::read_to_string(entry.path().join("memories.json"))
+                .ok()
+                .and_then(|c| serde_json::from_str::<Vec<SessionMemory>>(&c).ok())
+            else {
+                continue;
+            };
+
+            for memory in memories {
+                if local_memories
+                    .get(&memory.id)
+                    .is_some_and(|local| !is_later(&memory.created_at, local))
+                {
+                    continue;
+                }
+
+                conn.execute(
+                    "INSERT INTO session_memories (id, project_id, claude_session_id, session_date, summary, key_decisions, open_threads, files_touched, duration_minutes, created_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                     ON CONFLICT(id) DO UPDATE SET project_id = ?2, claude_session_id = ?3, session_date = ?4,
+                        summary = ?5, key_decisions = ?6, open_threads = ?7, files_touched = ?8,
+                        duration_minutes = ?9, created_at = ?10",
+                    rusqlite::params![
+                        memory.id,
+                        project_id,
+                        memory.claude_session_id,
+                        memory.session_date,
+                        memory.summary,
+                        serde_json::to_string(&memory.key_decisions).unwrap_or_else(|_| "[]".to_string()),
+                        serde_json::to_string(&memory.open_threads).unwrap_or_else(|_| "[]".to_string()),
+                        serde_json::to_string(&memory.files_touched).unwrap_or_else(|_| "[]".to_string()),
+                        memory.duration_minutes,
+                        memory.created_at,
+                    ],
+                )
+                .map_err(|e| format!("Failed to restore session memory: {}", e))?;
+                memory_count += 1;
+            }
+        }
+    }
+
+    Ok((prompt_count, memory_count))
+}
+
+/// Reset the sync repo to an earlier commit and re-apply its prompts and memories
+/// to the database. Discards later local sync commits, so `confirm` must be true.
+#[tauri::command]
+pub fn restore_sync_to(commit: String, confirm: bool) -> Result<SyncRepoResult, String> {
+    let _guard = lock_sync();
+    if !confirm {
+        return Ok(SyncRepoResult {
+            success: false,
+            url: None,
+            message: "Restoring resets the sync folder and overwrites matching prompts and memories. Confirm to continue.".to_string(),
+            ..Default::default()
+        });
+    }
+
+    let sync_dir = get_sync_dir()?;
+    if !sync_dir.join(".git").exists() {
+        return Ok(SyncRepoResult {
+            success: false,
+            url: None,
+            message: "Sync not configured".to_string(),
+            ..Default::default()
+        });
+    }
+
+    let verify = Command::new("git")
+        .current_dir(&sync_dir)
+        .args(["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", commit)])
+        .output()
+        .map_err(|e| format!("Failed to verify commit: {}", e))?;
+
+    if !verify.status.success() {
+        return Ok(SyncRepoResult {
+            success: false,
+            url: None,
+            message: format!("Sync commit not found: {}", commit),
+            ..Default::default()
+        });
+    }
+    let sha = String::from_utf8_lossy(&verify.stdout).trim().to_string();
+
+    let output = Command::new("git")
+        .current_dir(&sync_dir)
+        .args(["reset", "--hard", &sha])
+        .output()
+        .map_err(|e| format!("Failed to reset sync repo: {}", e))?;
+
+    if !output.status.success() {
+        return Ok(SyncRepoResult {
+            success: false,
+            url: None,
+            message: String::from_utf8_lossy(&output.stderr).to_string(),
+            ..Default::default()
+        });
+    }
+
+    let (prompts, memories) = import_sync_data(&sync_dir, false)?;
+
+    Ok(SyncRepoResult {
+        success: true,
+        url: None,
+        message: format!(
+            "Restored sync to {} ({} prompts, {} memories re-applied)",
+            &sha[..7.min(sha.len())],
+            prompts,
+            memories
+        ),
+        ..Default::default()
+    })
+}
+
+/// Background sync settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoSyncConfig {
+    pub enabled: bool,
+    /// Minutes between syncs
+    pub interval_minutes: u64,
+}
+
+impl Default for AutoSyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: 15,
+        }
+    }
+}
+
+/// Emitted as `sync-state` when an automatic sync starts and finishes.
+/// `state` uses the tray state names ("syncing", then "normal").
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStateEvent {
+    pub state: String,
+    pub result: Option<SyncRepoResult>,
+    pub error: Option<String>,
+}
+
+/// Whether the database or the sync repo has anything not yet pushed
+fn has_local_changes(sync_dir: &std::path::Path) -> Result<bool, String> {
+    let (prompts, memories) = export_sync_data(sync_dir, true)?;
+    if prompts + memories > 0 {
+        return Ok(true);
+    }
+
+    let status = Command::new("git")
+        .current_dir(sync_dir)
+        .args(["status", "--porcelain"])
+        .output()
+        .map_err(|e| format!("Failed to get sync status: {}", e))?;
+    if !String::from_utf8_lossy(&status.stdout).trim().is_empty() {
+        return Ok(true);
+    }
+
+    Ok(get_divergence(sync_dir).is_some_and(|d| d.ahead > 0))
+}
+
+/// Pull then push, if sync is set up and there's something to push
+fn run_auto_sync(app_handle: &AppHandle) {
+    let _guard = lock_sync();
+    let Ok(sync_dir) = get_sync_dir() else {
+        return;
+    };
+    if !sync_dir.join(".git").exists() {
+        return;
+    }
+    match has_local_changes(&sync_dir) {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(e) => {
+            eprintln!("Auto-sync failed: {}", e);
+            return;
+        }
+    }
+
+    let _ = app_handle.emit(
+        "sync-state",
+        SyncStateEvent {
+            state: "syncing".to_string(),
+            result: None,
+            error: None,
+        },
+    );
+
+    let result = pull_changes().and_then(|pulled| {
+        if pulled.success {
+            push_changes()
+        } else {
+            Ok(pulled)
+        }
+    });
+    let (result, error) = match result {
+        Ok(result) => (Some(result), None),
+        Err(e) => (None, Some(e)),
+    };
+
+    let _ = app_handle.emit(
+        "sync-state",
+        SyncStateEvent {
+            state: "normal".to_string(),
+            result,
+            error,
+        },
+    );
+}
+
+/// Start the background task that syncs every `interval_minutes` while auto-sync is enabled
+pub fn start_auto_sync(app_handle: AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_sync = Instant::now();
+        loop {
+            std::thread::sleep(Duration::from_secs(AUTO_SYNC_POLL_SECS));
+
+            let Ok(cfg) = config::load_config() else {
+                continue;
+            };
+            let settings = cfg.auto_sync;
+            let interval = Duration::from_secs(settings.interval_minutes.max(1) * 60);
+            if !settings.enabled || last_sync.elapsed() < interval {
+                continue;
+            }
+
+            last_sync = Instant::now();
+            run_auto_sync(&app_handle);
+        }
+    });
+}
+
+/// Get the auto-sync settings
+#[tauri::command]
+pub fn get_auto_sync() -> Result<AutoSyncConfig, String> {
+    Ok(config::load_config()?.auto_sync)
+}
+
+/// Turn auto-sync on or off and optionally change its interval
+#[tauri::command]
+pub fn set_auto_sync(
+    enabled: bool,
+    interval_minutes: Option<u64>,
+) -> Result<AutoSyncConfig, String> {
+    if interval_minutes == Some(0) {
+        return Err("Auto-sync interval must be at least one minute".to_string());
+    }
+
+    let mut cfg = config::load_config()?;
+    cfg.auto_sync.enabled = enabled;
+    if let Some(minutes) = interval_minutes {
+        cfg.auto_sync.interval_minutes = minutes;
+    }
+    config::save_config(&cfg)?;
+
+    Ok(cfg.auto_sync)
 }
@@ -0,0 +1,121 @@
+use crate::commands::projects;
+use crate::commands::quick_commands::{self, TestRunResult};
+use crate::git;
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Quiet period after the last source change before tests re-run
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(1000);
+
+lazy_static::lazy_static! {
+    static ref TEST_WATCHERS: Mutex<HashMap<String, Debouncer<RecommendedWatcher>>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TestResultEvent {
+    pub project_path: String,
+    /// "success" or "error", for the status indicator
+    pub status: String,
+    pub result: Option<TestRunResult>,
+    /// Set when the tests couldn't be run at all
+    pub error: Option<String>,
+}
+
+/// Whether a changed path is a source file that should trigger a test run
+fn is_source_change(path: &Path, project_path: &Path) -> bool {
+    let relative = path.strip_prefix(project_path).unwrap_or(path);
+    !relative.starts_with(".git") && !git::should_exclude(path, project_path)
+}
+
+/// Run the tests and emit `test-result`
+fn run_and_emit(app_handle: &AppHandle, project_path: &str) {
+    let event = match quick_commands::run_tests_blocking(project_path, None) {
+        Ok(result) => TestResultEvent {
+            project_path: project_path.to_string(),
+            status: if result.success { "success" } else { "error" }.to_string(),
+            result: Some(result),
+            error: None,
+        },
+        Err(e) => TestResultEvent {
+            project_path: project_path.to_string(),
+            status: "error".to_string(),
+            result: None,
+            error: Some(e),
+        },
+    };
+    let _ = app_handle.emit("test-result", event);
+}
+
+/// Run the project's tests now and again whenever source files change, emitting
+/// `test-result` after each run. Runs never overlap; changes made during a run
+/// queue a single follow-up run.
+#[tauri::command]
+pub fn watch_and_run_tests(app_handle: AppHandle, project_path: String) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path)?;
+    if quick_commands::detect_test_command(project_path.clone())?.is_none() {
+        return Err("Could not detect a test command for this project".to_string());
+    }
+
+    let mut watchers = TEST_WATCHERS.lock().map_err(|e| e.to_string())?;
+    if watchers.contains_key(&project_path) {
+        return Ok(());
+    }
+
+    // A single worker thread runs the tests, so runs are serialized
+    let (tx, rx) = mpsc::channel::<()>();
+    let worker_path = project_path.clone();
+    thread::spawn(move || {
+        while rx.recv().is_ok() {
+            // Collapse changes that arrived while the previous run was going
+            while rx.try_recv().is_ok() {}
+            run_and_emit(&app_handle, &worker_path);
+        }
+    });
+
+    let root = PathBuf::from(&project_path);
+    let watched_root = root.clone();
+    let trigger = tx.clone();
+    let mut debouncer = new_debouncer(DEBOUNCE_INTERVAL, move |result: DebounceEventResult| {
+        let Ok(events) = result else {
+            return;
+        };
+
+        if events
+            .iter()
+            .any(|event| is_source_change(&event.path, &watched_root))
+        {
+            let _ = trigger.send(());
+        }
+    })
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    debouncer
+        .watcher()
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch project: {}", e))?;
+
+    // Initial run, so the status is known right away
+    let _ = tx.send(());
+
+    watchers.insert(project_path, debouncer);
+
+    Ok(())
+}
+
+/// Stop re-running a project's tests on change
+#[tauri::command]
+pub fn stop_test_watch(project_path: String) -> Result<(), String> {
+    let project_path = projects::normalize_path(&project_path).unwrap_or(project_path);
+    let mut watchers = TEST_WATCHERS.lock().map_err(|e| e.to_string())?;
+    // Dropping the debouncer stops the watcher and, with it, the worker thread
+    watchers.remove(&project_path);
+    Ok(())
+}
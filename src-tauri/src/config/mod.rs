@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commands::health::HealthWeights;
+use crate::commands::maintenance::MaintenanceConfig;
+use crate::commands::sync::AutoSyncConfig;
+use crate::pty::semantic_parser::SemanticBlockPrefs;
+
+/// Global Lumen settings, stored in ~/.lumen/config.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LumenConfig {
+    /// Which semantic blocks are collapsed by default
+    pub semantic_blocks: SemanticBlockPrefs,
+    /// Custom sync directory (a plain local git repo); defaults to ~/.lumen/sync
+    pub sync_dir: Option<String>,
+    /// Retention settings and last-run state for automatic maintenance
+    pub maintenance: MaintenanceConfig,
+    /// Take an "auto" snapshot when a session starts and ends
+    pub auto_snapshot_sessions: bool,
+    /// Relative weights of the project health score factors
+    pub health_weights: HealthWeights,
+    /// Claude Code data directory to read instead of ~/.claude
+    pub claude_home: Option<String>,
+    /// Background pull/push of the sync repo
+    pub auto_sync: AutoSyncConfig,
+}
+
+/// Get the path to the config file
+fn get_config_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not find home directory")?;
+    Ok(home.join(".lumen").join("config.json"))
+}
+
+/// Load config from disk, falling back to defaults if it doesn't exist yet
+pub fn load_config() -> Result<LumenConfig, String> {
+    let config_path = get_config_path()?;
+
+    if !config_path.exists() {
+        return Ok(LumenConfig::default());
+    }
+
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read config file: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse config file: {}", e))
+}
+
+/// Save config to disk
+pub fn save_config(config: &LumenConfig) -> Result<(), String> {
+    let config_path = get_config_path()?;
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create .lumen directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+
+    fs::write(&config_path, content).map_err(|e| format!("Failed to write config file: {}", e))
+}
+
+/// Per-project settings, stored in <project>/.lumen/config.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectConfig {
+    /// Commands typed into new terminals for the project, when requested at spawn
+    pub pty_startup_commands: Vec<String>,
+}
+
+/// Get the path to a project's config file
+fn get_project_config_path(project_path: &Path) -> PathBuf {
+    project_path.join(".lumen").join("config.json")
+}
+
+/// Load a project's config, falling back to defaults if it doesn't exist yet
+pub fn load_project_config(project_path: &Path) -> Result<ProjectConfig, String> {
+    let config_path = get_project_config_path(project_path);
+
+    if !config_path.exists() {
+        return Ok(ProjectConfig::default());
+    }
+
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read project config: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse project config: {}", e))
+}
+
+/// Save a project's config
+pub fn save_project_config(project_path: &Path, config: &ProjectConfig) -> Result<(), String> {
+    let config_path = get_project_config_path(project_path);
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create .lumen directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize project config: {}", e))?;
+
+    fs::write(&config_path, content)
+        .map_err(|e| format!("Failed to write project config: {}", e))
+}
@@ -1,5 +1,8 @@
 use rusqlite::{Connection, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// How long a connection waits for another connection's lock before failing
+const BUSY_TIMEOUT_MS: u32 = 5000;
 
 /// Get the path to the database file
 fn get_db_path() -> Result<PathBuf, String> {
@@ -25,8 +28,7 @@ fn get_db_path() -> Result<PathBuf, String> {
 pub fn init_db() -> Result<(), String> {
     let db_path = get_db_path()?;
 
-    let conn =
-        Connection::open(&db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+    let conn = open(&db_path)?;
 
     // Create tables
     conn.execute_batch(
@@ -94,21 +96,155 @@ pub fn init_db() -> Result<(), String> {
             FOREIGN KEY (project_id) REFERENCES projects(id)
         );
 
+        -- Session events table (timeline of discrete things that happened)
+        CREATE TABLE IF NOT EXISTS session_events (
+            id TEXT PRIMARY KEY,
+            session_id TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            data TEXT,
+            created_at DATETIME NOT NULL,
+            FOREIGN KEY (session_id) REFERENCES sessions(id)
+        );
+
+        -- Manual notes jotted against a session
+        CREATE TABLE IF NOT EXISTS session_notes (
+            id TEXT PRIMARY KEY,
+            session_id TEXT NOT NULL,
+            note TEXT NOT NULL,
+            created_at DATETIME NOT NULL,
+            FOREIGN KEY (session_id) REFERENCES sessions(id)
+        );
+
+        -- Per-project monthly token budgets
+        CREATE TABLE IF NOT EXISTS project_budgets (
+            project_id TEXT PRIMARY KEY,
+            monthly_tokens INTEGER NOT NULL,
+            updated_at DATETIME,
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
         -- Indexes
         CREATE INDEX IF NOT EXISTS idx_sessions_project ON sessions(project_id);
         CREATE INDEX IF NOT EXISTS idx_sessions_started ON sessions(started_at);
         CREATE INDEX IF NOT EXISTS idx_usage_date ON usage_stats(date);
         CREATE INDEX IF NOT EXISTS idx_session_memories_project ON session_memories(project_id);
         CREATE INDEX IF NOT EXISTS idx_session_memories_date ON session_memories(session_date);
+        CREATE INDEX IF NOT EXISTS idx_session_events_session ON session_events(session_id);
+        CREATE INDEX IF NOT EXISTS idx_session_notes_session ON session_notes(session_id);
         "#,
     )
     .map_err(|e| format!("Failed to create tables: {}", e))?;
 
+    // Columns added after the initial schema
+    add_column_if_missing(&conn, "sessions", "outcome", "TEXT")?;
+    add_column_if_missing(&conn, "prompts", "updated_at", "DATETIME")?;
+    add_column_if_missing(&conn, "sessions", "resumed_at", "DATETIME")?;
+
     Ok(())
 }
 
+/// Add a column to an existing table if it isn't there yet (schema upgrades)
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), String> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({})", table))
+        .map_err(|e| format!("Failed to read {} schema: {}", table, e))?;
+
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .map_err(|e| format!("Failed to read {} schema: {}", table, e))?
+        .filter_map(|r| r.ok())
+        .any(|name| name == column);
+
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )
+        .map_err(|e| format!("Failed to add {}.{}: {}", table, column, e))?;
+    }
+
+    Ok(())
+}
+
+/// Open a connection in WAL mode with a busy timeout, so commands running at the
+/// same time wait for each other instead of failing with "database is locked".
+/// WAL mode keeps `lumen.db-wal` and `lumen.db-shm` next to the database while
+/// it's in use; they're part of the database and shouldn't be deleted.
+fn open(db_path: &Path) -> Result<Connection, String> {
+    let conn =
+        Connection::open(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+
+    conn.execute_batch(&format!(
+        "PRAGMA busy_timeout = {}; PRAGMA journal_mode = WAL;",
+        BUSY_TIMEOUT_MS
+    ))
+    .map_err(|e| format!("Failed to configure database: {}", e))?;
+
+    Ok(conn)
+}
+
 /// Get a connection to the database
 pub fn get_connection() -> Result<Connection, String> {
     let db_path = get_db_path()?;
-    Connection::open(&db_path).map_err(|e| format!("Failed to open database: {}", e))
+    open(&db_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_concurrent_connections_do_not_lock() {
+        let dir = std::env::temp_dir().join(format!("lumen-db-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("lumen.db");
+
+        let conn = open(&db_path).unwrap();
+        let mode: String = conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode, "wal");
+        conn.execute_batch("CREATE TABLE events (id INTEGER PRIMARY KEY, value TEXT)")
+            .unwrap();
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let db_path = db_path.clone();
+                thread::spawn(move || -> Result<(), String> {
+                    let conn = open(&db_path)?;
+                    for j in 0..25 {
+                        if i % 2 == 0 {
+                            // Writers hold a transaction across several statements
+                            let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+                            tx.execute("INSERT INTO events (value) VALUES (?1)", [format!("{}-{}", i, j)])
+                                .map_err(|e| e.to_string())?;
+                            tx.commit().map_err(|e| e.to_string())?;
+                        } else {
+                            conn.query_row("SELECT COUNT(*) FROM events", [], |row| row.get::<_, i64>(0))
+                                .map_err(|e| e.to_string())?;
+                        }
+                    }
+                    Ok(())
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM events", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 100);
+
+        drop(conn);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -1,4 +1,6 @@
 use git2::{Repository, Signature, IndexAddOption};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use rayon::prelude::*;
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
@@ -40,67 +42,255 @@ pub const DEFAULT_EXCLUSIONS: &[&str] = &[
     "Thumbs.db",
 ];
 
-/// Check if a file path matches any of the exclusion patterns
-pub fn should_exclude(path: &Path, base_path: &Path) -> bool {
-    let relative = path.strip_prefix(base_path).unwrap_or(path);
-    let path_str = relative.to_string_lossy();
+/// The exclusion rule that matched a path
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExclusionMatch {
+    /// Where the rule came from: "default", "gitignore" or "lumenignore"
+    pub source: String,
+    pub pattern: String,
+}
+
+/// A single exclusion pattern, parsed with gitignore semantics
+#[derive(Debug, Clone)]
+struct ExclusionRule {
+    source: &'static str,
+    /// The pattern as written
+    pattern: String,
+    /// `!pattern`: re-include paths an earlier rule excluded
+    negated: bool,
+    /// `pattern/`: only match directories
+    dir_only: bool,
+}
 
-    for pattern in DEFAULT_EXCLUSIONS {
-        if glob_match(pattern, &path_str) {
-            return true;
+/// Compiled exclusion patterns for a project. Precedence, highest first:
+/// `.lumenignore` (can re-include anything, even defaults, with `!pattern`),
+/// then `DEFAULT_EXCLUSIONS` (which `.gitignore` can't override), then `.gitignore`.
+/// Within one file the last matching pattern wins, as in git.
+struct ExclusionRules {
+    rules: Vec<ExclusionRule>,
+    set: GlobSet,
+}
+
+impl ExclusionRules {
+    /// Compile the default patterns followed by the lines of .gitignore and .lumenignore
+    fn new(gitignore: &str, lumenignore: &str) -> Self {
+        let mut rules = Vec::new();
+        let mut builder = GlobSetBuilder::new();
+
+        let file_patterns = |source: &'static str, content: &str| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .map(|l| (source, l.to_string()))
+                .collect::<Vec<_>>()
+        };
+        let defaults = DEFAULT_EXCLUSIONS.iter().map(|p| ("default", p.to_string()));
+        let all = defaults
+            .chain(file_patterns("gitignore", gitignore))
+            .chain(file_patterns("lumenignore", lumenignore));
+
+        for (source, line) in all {
+            let line = line.as_str();
+            let (negated, pattern) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let dir_only = pattern.ends_with('/');
+            let pattern = pattern.trim_end_matches('/');
+
+            // A slash anywhere but the end anchors the pattern to the project root;
+            // otherwise it matches at any depth
+            let glob = match pattern.strip_prefix('/') {
+                Some(anchored) => anchored.to_string(),
+                None if pattern.contains('/') => pattern.to_string(),
+                None => format!("**/{}", pattern),
+            };
+
+            let Ok(glob) = GlobBuilder::new(&glob).literal_separator(true).build() else {
+                continue;
+            };
+            builder.add(glob);
+            rules.push(ExclusionRule {
+                source,
+                pattern: line.to_string(),
+                negated,
+                dir_only,
+            });
         }
+
+        let set = builder.build().unwrap_or_else(|_| GlobSet::empty());
+        Self { rules, set }
     }
 
-    // Also check .gitignore if present
-    let gitignore_path = base_path.join(".gitignore");
-    if gitignore_path.exists() {
-        if let Ok(content) = fs::read_to_string(&gitignore_path) {
-            for line in content.lines() {
-                let line = line.trim();
-                if !line.is_empty() && !line.starts_with('#') {
-                    if glob_match(line, &path_str) {
-                        return true;
-                    }
+    /// The rule deciding whether a single relative path is excluded
+    fn deciding_rule(&self, relative: &str, is_dir: bool) -> Option<&ExclusionRule> {
+        let matches: Vec<&ExclusionRule> = self
+            .set
+            .matches(relative)
+            .into_iter()
+            .map(|i| &self.rules[i])
+            .filter(|rule| is_dir || !rule.dir_only)
+            .collect();
+
+        let last_from = |source: &str| matches.iter().rev().find(|rule| rule.source == source);
+        last_from("lumenignore")
+            .or_else(|| last_from("default"))
+            .or_else(|| last_from("gitignore"))
+            .copied()
+    }
+
+    /// Check a relative path and each of its parent directories. Like git, a path
+    /// inside an excluded directory can't be re-included by a negated pattern.
+    fn check(&self, relative: &Path, is_dir: bool) -> Option<ExclusionMatch> {
+        let components: Vec<String> = relative
+            .components()
+            .filter_map(|c| match c {
+                std::path::Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect();
+
+        let mut prefix = String::new();
+        for (i, component) in components.iter().enumerate() {
+            if i > 0 {
+                prefix.push('/');
+            }
+            prefix.push_str(component);
+
+            let component_is_dir = i + 1 < components.len() || is_dir;
+            if let Some(rule) = self.deciding_rule(&prefix, component_is_dir) {
+                if !rule.negated {
+                    return Some(ExclusionMatch {
+                        source: rule.source.to_string(),
+                        pattern: rule.pattern.clone(),
+                    });
                 }
             }
         }
+
+        None
     }
+}
 
-    false
+lazy_static::lazy_static! {
+    /// Compiled exclusion rules per project, with the ignore file mtimes they were built from
+    static ref EXCLUSION_RULES: std::sync::Mutex<std::collections::HashMap<PathBuf, (IgnoreFileTimes, std::sync::Arc<ExclusionRules>)>> =
+        std::sync::Mutex::new(std::collections::HashMap::new());
+
+    /// One lock per snapshot repository, see `snapshot_repo_lock`
+    static ref SNAPSHOT_REPO_LOCKS: std::sync::Mutex<std::collections::HashMap<PathBuf, std::sync::Arc<std::sync::Mutex<()>>>> =
+        std::sync::Mutex::new(std::collections::HashMap::new());
 }
 
-/// Simple glob matching (supports * and **)
-fn glob_match(pattern: &str, path: &str) -> bool {
-    let pattern = pattern.trim_end_matches('/');
-    let path = path.trim_start_matches('/');
+/// The lock serializing changes to a project's snapshot repository. Creating,
+/// restoring, rewriting and pruning snapshots hold it, so a prune never repacks
+/// or moves HEAD under a snapshot being written.
+fn snapshot_repo_lock(project_path: &Path) -> std::sync::Arc<std::sync::Mutex<()>> {
+    let mut locks = match SNAPSHOT_REPO_LOCKS.lock() {
+        Ok(locks) => locks,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    locks
+        .entry(get_snapshot_repo_path(project_path))
+        .or_default()
+        .clone()
+}
 
-    // Handle exact matches
-    if pattern == path {
-        return true;
-    }
+/// Modification times of a project's .gitignore and .lumenignore
+type IgnoreFileTimes = [Option<std::time::SystemTime>; 2];
 
-    // Handle patterns starting with **/
-    if pattern.starts_with("**/") {
-        let suffix = &pattern[3..];
-        return path.ends_with(suffix) || path.contains(&format!("/{}", suffix));
-    }
+/// Get the compiled exclusion rules for a project, recompiling when its ignore files change
+fn exclusion_rules(base_path: &Path) -> std::sync::Arc<ExclusionRules> {
+    let gitignore_path = base_path.join(".gitignore");
+    let lumenignore_path = base_path.join(".lumenignore");
+    let modified_time = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    let modified = [modified_time(&gitignore_path), modified_time(&lumenignore_path)];
 
-    // Handle patterns with * wildcard
-    if pattern.contains('*') && !pattern.contains("**") {
-        let parts: Vec<&str> = pattern.split('*').collect();
-        if parts.len() == 2 {
-            let matches_start = parts[0].is_empty() || path.starts_with(parts[0]);
-            let matches_end = parts[1].is_empty() || path.ends_with(parts[1]);
-            return matches_start && matches_end;
+    let mut cache = match EXCLUSION_RULES.lock() {
+        Ok(cache) => cache,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if let Some((cached_modified, rules)) = cache.get(base_path) {
+        if *cached_modified == modified {
+            return rules.clone();
         }
     }
 
-    // Handle directory patterns
-    if path.starts_with(pattern) {
-        return true;
+    let gitignore = fs::read_to_string(&gitignore_path).unwrap_or_default();
+    let lumenignore = fs::read_to_string(&lumenignore_path).unwrap_or_default();
+    let rules = std::sync::Arc::new(ExclusionRules::new(&gitignore, &lumenignore));
+    cache.insert(base_path.to_path_buf(), (modified, rules.clone()));
+    rules
+}
+
+/// Find the exclusion rule that excludes a path, if any
+pub fn exclusion_reason(path: &Path, base_path: &Path) -> Option<ExclusionMatch> {
+    let relative = path.strip_prefix(base_path).unwrap_or(path);
+    exclusion_rules(base_path).check(relative, path.is_dir())
+}
+
+/// Check if a file path matches any of the exclusion patterns
+pub fn should_exclude(path: &Path, base_path: &Path) -> bool {
+    exclusion_reason(path, base_path).is_some()
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExcludedPath {
+    /// Relative path; directories end with "/" and stand for everything inside them
+    pub path: String,
+    pub reason: ExclusionMatch,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SnapshotPreview {
+    pub included: Vec<String>,
+    pub excluded: Vec<ExcludedPath>,
+}
+
+/// Walk the project the same way a snapshot copy does, without copying anything
+pub fn preview_snapshot_contents(project_path: &Path) -> Result<SnapshotPreview, String> {
+    let mut preview = SnapshotPreview::default();
+
+    let mut walker = WalkDir::new(project_path).into_iter();
+    while let Some(entry) = walker.next() {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+
+        if path == project_path {
+            continue;
+        }
+
+        let relative = path
+            .strip_prefix(project_path)
+            .map_err(|e| e.to_string())?
+            .to_string_lossy()
+            .replace('\\', "/");
+        let is_dir = entry.file_type().is_dir();
+
+        if let Some(reason) = exclusion_reason(path, project_path) {
+            if is_dir {
+                // Excluded directories are never descended into
+                walker.skip_current_dir();
+                preview.excluded.push(ExcludedPath {
+                    path: format!("{}/", relative),
+                    reason,
+                });
+            } else {
+                preview.excluded.push(ExcludedPath {
+                    path: relative,
+                    reason,
+                });
+            }
+        } else if !is_dir {
+            preview.included.push(relative);
+        }
     }
 
-    false
+    preview.included.sort();
+    preview.excluded.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(preview)
 }
 
 /// Get the snapshot repository path for a project
@@ -144,24 +334,26 @@ pub fn init_or_open_repo(project_path: &Path) -> Result<Repository, String> {
     }
 }
 
-/// Copy project files to snapshot directory
-pub fn copy_project_to_snapshot(project_path: &Path, snapshot_path: &Path) -> Result<i32, String> {
-    let mut file_count = 0;
+/// Whether a snapshot copy of a file is up to date: copies keep the source's
+/// mtime, so matching size and mtime means the file hasn't changed since
+fn is_copy_current(source: &fs::Metadata, dest: &Path) -> bool {
+    let Ok(dest) = fs::metadata(dest) else {
+        return false;
+    };
+    dest.is_file()
+        && dest.len() == source.len()
+        && matches!((dest.modified(), source.modified()), (Ok(a), Ok(b)) if a == b)
+}
 
-    // Clear existing files in snapshot (except .git)
-    for entry in fs::read_dir(snapshot_path).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
-        if path.file_name().map(|n| n != ".git").unwrap_or(false) {
-            if path.is_dir() {
-                fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
-            } else {
-                fs::remove_file(&path).map_err(|e| e.to_string())?;
-            }
-        }
-    }
+/// Sync project files into the snapshot directory, copying only files whose size
+/// or mtime changed and removing files that no longer exist (or are now excluded).
+/// Returns the number of files written.
+pub fn copy_project_to_snapshot(project_path: &Path, snapshot_path: &Path) -> Result<i32, String> {
+    let mut present = std::collections::HashSet::new();
+    let mut to_copy = Vec::new();
 
-    // Copy files from project to snapshot
+    // Find new and changed files, creating directories as we go so the
+    // parallel copy below never races on them
     for entry in WalkDir::new(project_path)
         .into_iter()
         .filter_entry(|e| !should_exclude(e.path(), project_path))
@@ -176,19 +368,101 @@ pub fn copy_project_to_snapshot(project_path: &Path, snapshot_path: &Path) -> Re
 
         let relative = path.strip_prefix(project_path).map_err(|e| e.to_string())?;
         let dest = snapshot_path.join(relative);
+        present.insert(relative.to_path_buf());
 
         if path.is_dir() {
+            if dest.is_file() {
+                fs::remove_file(&dest).map_err(|e| e.to_string())?;
+            }
             fs::create_dir_all(&dest).map_err(|e| e.to_string())?;
         } else {
-            if let Some(parent) = dest.parent() {
+            let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+            if is_copy_current(&metadata, &dest) {
+                continue;
+            }
+
+            if dest.is_dir() {
+                fs::remove_dir_all(&dest).map_err(|e| e.to_string())?;
+            } else if let Some(parent) = dest.parent() {
                 fs::create_dir_all(parent).map_err(|e| e.to_string())?;
             }
-            fs::copy(path, &dest).map_err(|e| e.to_string())?;
-            file_count += 1;
+            to_copy.push((path.to_path_buf(), dest, metadata));
+        }
+    }
+
+    let file_count = std::sync::atomic::AtomicI32::new(0);
+    to_copy.par_iter().try_for_each(|(source, dest, metadata)| {
+        fs::copy(source, dest).map_err(|e| e.to_string())?;
+        if let Ok(modified) = metadata.modified() {
+            let _ = fs::File::options()
+                .write(true)
+                .open(dest)
+                .and_then(|f| f.set_modified(modified));
+        }
+        file_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Ok::<(), String>(())
+    })?;
+
+    // Remove snapshot files that are gone from the project (except .git)
+    let mut stale = WalkDir::new(snapshot_path).min_depth(1).into_iter();
+    while let Some(entry) = stale.next() {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let relative = entry
+            .path()
+            .strip_prefix(snapshot_path)
+            .map_err(|e| e.to_string())?;
+
+        if relative == Path::new(".git") {
+            stale.skip_current_dir();
+            continue;
+        }
+        if present.contains(relative) {
+            continue;
+        }
+
+        if entry.file_type().is_dir() {
+            fs::remove_dir_all(entry.path()).map_err(|e| e.to_string())?;
+            stale.skip_current_dir();
+        } else {
+            fs::remove_file(entry.path()).map_err(|e| e.to_string())?;
         }
     }
 
-    Ok(file_count)
+    Ok(file_count.into_inner())
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CopyBenchmark {
+    pub file_count: i32,
+    pub total_bytes: u64,
+    pub duration_ms: u64,
+}
+
+/// Time a full project copy into a throwaway temp directory (nothing is committed)
+pub fn benchmark_snapshot_copy(project_path: &Path) -> Result<CopyBenchmark, String> {
+    let temp_dir = std::env::temp_dir().join(format!("lumen-bench-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&temp_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let started = std::time::Instant::now();
+    let result = copy_project_to_snapshot(project_path, &temp_dir);
+    let duration = started.elapsed();
+
+    let total_bytes = WalkDir::new(&temp_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum();
+
+    let _ = fs::remove_dir_all(&temp_dir);
+
+    Ok(CopyBenchmark {
+        file_count: result?,
+        total_bytes,
+        duration_ms: duration.as_millis() as u64,
+    })
 }
 
 /// Create a new snapshot
@@ -197,6 +471,18 @@ pub fn create_snapshot(
     name: &str,
     description: Option<&str>,
 ) -> Result<SnapshotInfo, String> {
+    create_snapshot_of_type(project_path, name, description, "manual")
+}
+
+/// Create a new snapshot of the given type ("manual" or "auto")
+pub fn create_snapshot_of_type(
+    project_path: &Path,
+    name: &str,
+    description: Option<&str>,
+    snapshot_type: &str,
+) -> Result<SnapshotInfo, String> {
+    let repo_lock = snapshot_repo_lock(project_path);
+    let _guard = repo_lock.lock().unwrap_or_else(|e| e.into_inner());
     let repo = init_or_open_repo(project_path)?;
     let snapshot_path = get_snapshot_repo_path(project_path);
 
@@ -218,10 +504,7 @@ pub fn create_snapshot(
     let tree = repo.find_tree(tree_id).map_err(|e| format!("Failed to find tree: {}", e))?;
 
     // Create commit message
-    let message = match description {
-        Some(desc) => format!("{}\n\n{}", name, desc),
-        None => name.to_string(),
-    };
+    let message = SnapshotMessage::new(name, description, snapshot_type).format();
 
     let sig = Signature::now("Lumen", "lumen@local")
         .map_err(|e| format!("Failed to create signature: {}", e))?;
@@ -240,10 +523,141 @@ pub fn create_snapshot(
         description: description.map(String::from),
         timestamp: chrono::Utc::now().to_rfc3339(),
         files_changed,
-        snapshot_type: "manual".to_string(),
+        snapshot_type: snapshot_type.to_string(),
+        tags: vec![],
     })
 }
 
+/// Prefix of the footer lines that carry a snapshot's metadata in its commit message
+const FOOTER_PREFIX: &str = "Lumen-";
+
+/// Trailer line older versions used to store tags
+const LEGACY_TAGS_TRAILER: &str = "Tags: ";
+
+/// A snapshot commit message: a name and optional description, followed by a
+/// machine-readable footer paragraph of `Lumen-<Key>: value` lines
+#[derive(Debug, Clone, PartialEq)]
+struct SnapshotMessage {
+    name: String,
+    description: Option<String>,
+    snapshot_type: String,
+    tags: Vec<String>,
+    /// Any other footer lines, keyed without the `Lumen-` prefix
+    metadata: std::collections::BTreeMap<String, String>,
+}
+
+impl SnapshotMessage {
+    fn new(name: &str, description: Option<&str>, snapshot_type: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.map(String::from),
+            snapshot_type: snapshot_type.to_string(),
+            tags: Vec::new(),
+            metadata: std::collections::BTreeMap::new(),
+        }
+    }
+
+    /// Render the commit message. The name is kept to a single line so it can't be
+    /// confused with the description or footer.
+    fn format(&self) -> String {
+        let name = self.name.split_whitespace().collect::<Vec<_>>().join(" ");
+        let description = self.description.as_deref().map(str::trim).filter(|d| !d.is_empty());
+        let mut message = match description {
+            Some(desc) => format!("{}\n\n{}", name, desc),
+            None => name,
+        };
+
+        message.push_str(&format!("\n\n{}Type: {}", FOOTER_PREFIX, self.snapshot_type));
+        if !self.tags.is_empty() {
+            message.push_str(&format!("\n{}Tags: {}", FOOTER_PREFIX, self.tags.join(", ")));
+        }
+        for (key, value) in &self.metadata {
+            message.push_str(&format!("\n{}{}: {}", FOOTER_PREFIX, key, value));
+        }
+        message
+    }
+
+    /// Parse a commit message, accepting the structured footer as well as older
+    /// `name`, `name\n\ndescription` and `Tags: ` trailer messages
+    fn parse(message: &str) -> Self {
+        let mut lines: Vec<&str> = message.trim_end().lines().collect();
+        let mut parsed = Self::new("Unnamed", None, "manual");
+
+        // The footer is the last paragraph, if every line in it is a footer line
+        let footer_start = lines
+            .iter()
+            .rposition(|l| l.trim().is_empty())
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let footer = &lines[footer_start..];
+        let is_footer = footer_start > 0
+            && !footer.is_empty()
+            && (footer.iter().all(|l| parse_footer_line(l).is_some())
+                || (footer.len() == 1 && footer[0].starts_with(LEGACY_TAGS_TRAILER)));
+
+        if is_footer {
+            for line in footer {
+                if let Some(tags) = line.strip_prefix(LEGACY_TAGS_TRAILER) {
+                    parsed.tags = split_tags(tags);
+                    continue;
+                }
+                let Some((key, value)) = parse_footer_line(line) else {
+                    continue;
+                };
+                match key {
+                    "Type" => parsed.snapshot_type = value.to_string(),
+                    "Tags" => parsed.tags = split_tags(value),
+                    _ => {
+                        parsed.metadata.insert(key.to_string(), value.to_string());
+                    }
+                }
+            }
+            lines.truncate(footer_start);
+            while lines.last().is_some_and(|l| l.trim().is_empty()) {
+                lines.pop();
+            }
+        }
+
+        // Like git, the subject runs up to the first blank line and the body follows it
+        let subject_end = lines
+            .iter()
+            .position(|l| l.trim().is_empty())
+            .unwrap_or(lines.len());
+        let subject = lines[..subject_end]
+            .iter()
+            .map(|l| l.trim())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !subject.is_empty() {
+            parsed.name = subject;
+        }
+
+        let body = lines[subject_end..].join("\n");
+        let body = body.trim_matches('\n');
+        if !body.trim().is_empty() {
+            parsed.description = Some(body.to_string());
+        }
+
+        parsed
+    }
+}
+
+/// Split a `Lumen-<Key>: value` footer line into key and value
+fn parse_footer_line(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.strip_prefix(FOOTER_PREFIX)?.split_once(": ")?;
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return None;
+    }
+    Some((key, value.trim()))
+}
+
+fn split_tags(tags: &str) -> Vec<String> {
+    tags.split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
 /// List all snapshots
 pub fn list_snapshots(project_path: &Path) -> Result<Vec<SnapshotInfo>, String> {
     let snapshot_path = get_snapshot_repo_path(project_path);
@@ -269,14 +683,8 @@ pub fn list_snapshots(project_path: &Path) -> Result<Vec<SnapshotInfo>, String>
             continue;
         }
 
-        // Parse name and description from commit message
-        let lines: Vec<&str> = message.lines().collect();
-        let name = lines.first().unwrap_or(&"Unnamed").to_string();
-        let description = if lines.len() > 2 {
-            Some(lines[2..].join("\n"))
-        } else {
-            None
-        };
+        // Parse name, description and footer metadata from commit message
+        let parsed = SnapshotMessage::parse(message);
 
         // Calculate files changed (diff with parent)
         let files_changed = if let Some(parent_id) = commit.parent_id(0).ok() {
@@ -303,11 +711,12 @@ pub fn list_snapshots(project_path: &Path) -> Result<Vec<SnapshotInfo>, String>
 
         snapshots.push(SnapshotInfo {
             id: oid.to_string(),
-            name,
-            description,
+            name: parsed.name,
+            description: parsed.description,
             timestamp,
             files_changed,
-            snapshot_type: "manual".to_string(),
+            snapshot_type: parsed.snapshot_type,
+            tags: parsed.tags,
         });
     }
 
@@ -316,6 +725,44 @@ pub fn list_snapshots(project_path: &Path) -> Result<Vec<SnapshotInfo>, String>
 
 /// Restore a snapshot to the project
 pub fn restore_snapshot(project_path: &Path, snapshot_id: &str) -> Result<(), String> {
+    restore_snapshot_with_progress(project_path, snapshot_id, |_| true).map(|_| ())
+}
+
+/// Progress of a snapshot restore, reported after each file is written
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreProgress {
+    pub files_written: usize,
+    pub total_files: usize,
+    /// Relative path of the file just written
+    pub path: String,
+}
+
+/// How a restore with progress ended
+#[derive(Debug, Clone)]
+pub enum RestoreOutcome {
+    /// All files were written
+    Completed { files_written: usize },
+    /// Stopped by the progress callback. `created` lists the files the restore
+    /// added that didn't exist in the project before, so they can be removed.
+    Cancelled {
+        files_written: usize,
+        created: Vec<PathBuf>,
+    },
+}
+
+/// Restore a snapshot, calling `on_progress` after each file is written.
+/// Returning false from `on_progress` stops the restore after that file.
+pub fn restore_snapshot_with_progress<F>(
+    project_path: &Path,
+    snapshot_id: &str,
+    on_progress: F,
+) -> Result<RestoreOutcome, String>
+where
+    F: FnMut(&RestoreProgress) -> bool,
+{
+    let repo_lock = snapshot_repo_lock(project_path);
+    let _guard = repo_lock.lock().unwrap_or_else(|e| e.into_inner());
     let snapshot_path = get_snapshot_repo_path(project_path);
     let repo = Repository::open(&snapshot_path)
         .map_err(|e| format!("Failed to open repository: {}", e))?;
@@ -336,8 +783,33 @@ pub fn restore_snapshot(project_path: &Path, snapshot_id: &str) -> Result<(), St
             .remove_untracked(true)
     )).map_err(|e| format!("Failed to checkout: {}", e))?;
 
-    // Copy files back to project
-    for entry in WalkDir::new(&snapshot_path)
+    // Copy files back to project, then reset to HEAD for future snapshots
+    // whether or not the copy finished
+    let outcome = copy_snapshot_to_project(&snapshot_path, project_path, on_progress);
+
+    let head = repo.head().map_err(|e| format!("Failed to get HEAD: {}", e))?;
+    let head_commit = head.peel_to_commit().map_err(|e| format!("Failed to get HEAD commit: {}", e))?;
+    let head_tree = head_commit.tree().map_err(|e| format!("Failed to get HEAD tree: {}", e))?;
+
+    repo.checkout_tree(head_tree.as_object(), Some(
+        git2::build::CheckoutBuilder::new().force()
+    )).map_err(|e| format!("Failed to reset: {}", e))?;
+
+    outcome
+}
+
+/// Copy the checked-out snapshot files into the project
+fn copy_snapshot_to_project<F>(
+    snapshot_path: &Path,
+    project_path: &Path,
+    mut on_progress: F,
+) -> Result<RestoreOutcome, String>
+where
+    F: FnMut(&RestoreProgress) -> bool,
+{
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+    for entry in WalkDir::new(snapshot_path)
         .into_iter()
         .filter_entry(|e| e.file_name() != ".git")
     {
@@ -348,29 +820,125 @@ pub fn restore_snapshot(project_path: &Path, snapshot_id: &str) -> Result<(), St
             continue;
         }
 
-        let relative = path.strip_prefix(&snapshot_path).map_err(|e| e.to_string())?;
-        let dest = project_path.join(relative);
-
+        let relative = path.strip_prefix(snapshot_path).map_err(|e| e.to_string())?;
         if path.is_dir() {
-            fs::create_dir_all(&dest).map_err(|e| e.to_string())?;
+            dirs.push(relative.to_path_buf());
         } else {
-            if let Some(parent) = dest.parent() {
-                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            files.push(relative.to_path_buf());
+        }
+    }
+
+    for dir in &dirs {
+        fs::create_dir_all(project_path.join(dir)).map_err(|e| e.to_string())?;
+    }
+
+    let total_files = files.len();
+    let mut created = Vec::new();
+    for (i, relative) in files.iter().enumerate() {
+        let dest = project_path.join(relative);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        if !dest.exists() {
+            created.push(dest.clone());
+        }
+        fs::copy(snapshot_path.join(relative), &dest).map_err(|e| e.to_string())?;
+
+        let progress = RestoreProgress {
+            files_written: i + 1,
+            total_files,
+            path: relative.to_string_lossy().replace('\\', "/"),
+        };
+        if !on_progress(&progress) {
+            return Ok(RestoreOutcome::Cancelled {
+                files_written: i + 1,
+                created,
+            });
+        }
+    }
+
+    Ok(RestoreOutcome::Completed {
+        files_written: total_files,
+    })
+}
+
+/// Project files that differ from the latest snapshot
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct WorkingTreeStatus {
+    pub added: Vec<String>,
+    pub modified: Vec<String>,
+    pub deleted: Vec<String>,
+}
+
+impl WorkingTreeStatus {
+    pub fn total(&self) -> usize {
+        self.added.len() + self.modified.len() + self.deleted.len()
+    }
+}
+
+/// Compare the project's current files against the latest snapshot
+pub fn get_working_tree_status(project_path: &Path) -> Result<WorkingTreeStatus, String> {
+    let snapshot_path = get_snapshot_repo_path(project_path);
+
+    // Snapshot tree blobs by relative path (empty if no snapshot repo yet)
+    let mut snapshot_files: std::collections::HashMap<String, git2::Oid> =
+        std::collections::HashMap::new();
+    if snapshot_path.join(".git").exists() {
+        let repo = Repository::open(&snapshot_path)
+            .map_err(|e| format!("Failed to open repository: {}", e))?;
+        let tree = repo
+            .head()
+            .and_then(|h| h.peel_to_tree())
+            .map_err(|e| format!("Failed to get HEAD tree: {}", e))?;
+
+        tree.walk(git2::TreeWalkMode::PreOrder, |root, entry| {
+            if entry.kind() == Some(git2::ObjectType::Blob) {
+                let name = entry.name().unwrap_or_default();
+                snapshot_files.insert(format!("{}{}", root, name), entry.id());
+            }
+            git2::TreeWalkResult::Ok
+        })
+        .map_err(|e| format!("Failed to walk snapshot tree: {}", e))?;
+    }
+
+    let mut status = WorkingTreeStatus::default();
+
+    for entry in WalkDir::new(project_path)
+        .into_iter()
+        .filter_entry(|e| !should_exclude(e.path(), project_path))
+    {
+        let entry = entry.map_err(|e| e.to_string())?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative = entry
+            .path()
+            .strip_prefix(project_path)
+            .map_err(|e| e.to_string())?
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        match snapshot_files.remove(&relative) {
+            Some(snapshot_oid) => {
+                let current_oid = git2::Oid::hash_file(git2::ObjectType::Blob, entry.path())
+                    .map_err(|e| format!("Failed to hash {}: {}", relative, e))?;
+                if current_oid != snapshot_oid {
+                    status.modified.push(relative);
+                }
             }
-            fs::copy(path, &dest).map_err(|e| e.to_string())?;
+            None => status.added.push(relative),
         }
     }
 
-    // Reset to HEAD for future snapshots
-    let head = repo.head().map_err(|e| format!("Failed to get HEAD: {}", e))?;
-    let head_commit = head.peel_to_commit().map_err(|e| format!("Failed to get HEAD commit: {}", e))?;
-    let head_tree = head_commit.tree().map_err(|e| format!("Failed to get HEAD tree: {}", e))?;
+    // Whatever is left in the snapshot no longer exists in the project
+    status.deleted = snapshot_files.into_keys().collect();
 
-    repo.checkout_tree(head_tree.as_object(), Some(
-        git2::build::CheckoutBuilder::new().force()
-    )).map_err(|e| format!("Failed to reset: {}", e))?;
+    status.added.sort();
+    status.modified.sort();
+    status.deleted.sort();
 
-    Ok(())
+    Ok(status)
 }
 
 #[derive(Debug, Clone)]
@@ -381,67 +949,579 @@ pub struct SnapshotInfo {
     pub timestamp: String,
     pub files_changed: i32,
     pub snapshot_type: String,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
-pub struct FileChange {
-    pub path: String,
-    pub status: String, // "added", "modified", "deleted", "renamed"
-    pub additions: i32,
-    pub deletions: i32,
+pub struct SnapshotIdMapping {
+    pub old_id: String,
+    pub new_id: String,
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
-pub struct SnapshotDiff {
-    pub files: Vec<FileChange>,
-    pub total_additions: i32,
-    pub total_deletions: i32,
-}
+/// Rewrite snapshot commit messages in a single pass over the history, oldest first.
+/// `rewrite` returns a new message for commits that should change; those commits and
+/// all of their descendants get new ids, and HEAD is moved to the rewritten tip.
+/// Returns the id change of every rewritten commit.
+fn rewrite_snapshot_messages(
+    repo: &Repository,
+    reflog_message: &str,
+    mut rewrite: impl FnMut(git2::Oid, &git2::Commit) -> Option<String>,
+) -> Result<Vec<SnapshotIdMapping>, String> {
+    let head = repo.head().map_err(|e| format!("Failed to get HEAD: {}", e))?;
+    let head_ref = head
+        .name()
+        .ok_or("HEAD is not a named reference")?
+        .to_string();
+    let start_oid = head.target().ok_or("HEAD does not point at a commit")?;
 
-/// Get the diff for a specific snapshot (compared to its parent)
-pub fn get_snapshot_diff(project_path: &Path, snapshot_id: &str) -> Result<SnapshotDiff, String> {
-    let snapshot_path = get_snapshot_repo_path(project_path);
-    let repo = Repository::open(&snapshot_path)
-        .map_err(|e| format!("Failed to open repository: {}", e))?;
+    let mut revwalk = repo.revwalk().map_err(|e| format!("Failed to create revwalk: {}", e))?;
+    revwalk
+        .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)
+        .map_err(|e| format!("Failed to sort revwalk: {}", e))?;
+    revwalk.push(start_oid).map_err(|e| format!("Failed to push HEAD: {}", e))?;
 
-    let oid = git2::Oid::from_str(snapshot_id)
-        .map_err(|e| format!("Invalid snapshot ID: {}", e))?;
+    // Oldest first, so every parent is rewritten before its children
+    let mut rewritten: std::collections::HashMap<git2::Oid, git2::Oid> =
+        std::collections::HashMap::new();
+    let mut mappings = Vec::new();
+    let mut new_head = None;
 
-    let commit = repo.find_commit(oid)
-        .map_err(|e| format!("Snapshot not found: {}", e))?;
+    for oid in revwalk {
+        let oid = oid.map_err(|e| format!("Failed to get oid: {}", e))?;
+        let commit = repo.find_commit(oid).map_err(|e| format!("Failed to find commit: {}", e))?;
+        let message = commit.message().unwrap_or("");
 
-    let tree = commit.tree().map_err(|e| format!("Failed to get tree: {}", e))?;
+        let new_message = rewrite(oid, &commit);
 
-    // Get parent commit tree (or empty tree if this is the first commit)
-    let parent_tree = commit.parent(0)
-        .ok()
-        .and_then(|p| p.tree().ok());
+        let parent_ids: Vec<git2::Oid> = commit
+            .parent_ids()
+            .map(|p| rewritten.get(&p).copied().unwrap_or(p))
+            .collect();
+        let parents_changed = commit.parent_ids().any(|p| rewritten.contains_key(&p));
 
-    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
-        .map_err(|e| format!("Failed to create diff: {}", e))?;
+        if new_message.is_none() && !parents_changed {
+            new_head = Some(oid);
+            continue;
+        }
 
-    let mut files = Vec::new();
-    let mut total_additions = 0;
-    let mut total_deletions = 0;
+        let parents = parent_ids
+            .iter()
+            .map(|p| repo.find_commit(*p))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to find parent commit: {}", e))?;
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        let tree = commit.tree().map_err(|e| format!("Failed to get tree: {}", e))?;
+
+        // Keep the original author/committer so snapshot timestamps are unchanged
+        let new_oid = repo
+            .commit(
+                None,
+                &commit.author(),
+                &commit.committer(),
+                new_message.as_deref().unwrap_or(message),
+                &tree,
+                &parent_refs,
+            )
+            .map_err(|e| format!("Failed to rewrite snapshot: {}", e))?;
+
+        rewritten.insert(oid, new_oid);
+        mappings.push(SnapshotIdMapping {
+            old_id: oid.to_string(),
+            new_id: new_oid.to_string(),
+        });
+        new_head = Some(new_oid);
+    }
 
-    diff.foreach(
-        &mut |delta, _| {
-            let status = match delta.status() {
-                git2::Delta::Added => "added",
-                git2::Delta::Deleted => "deleted",
-                git2::Delta::Modified => "modified",
-                git2::Delta::Renamed => "renamed",
-                git2::Delta::Copied => "copied",
-                _ => "unknown",
-            };
+    if let Some(new_head) = new_head.filter(|_| !mappings.is_empty()) {
+        // Fails rather than dropping a snapshot committed since the walk started
+        repo.reference_matching(&head_ref, new_head, true, start_oid, reflog_message)
+            .map_err(|e| format!("Failed to update HEAD: {}", e))?;
+    }
 
-            let path = delta.new_file().path()
-                .or_else(|| delta.old_file().path())
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_default();
+    Ok(mappings)
+}
+
+/// Add `tag` to every snapshot taken within [from, to] (unix seconds).
+/// Tags live in commit messages, so tagged snapshots and all of their descendants
+/// get new ids; the history is rewritten in a single pass and the id changes returned.
+pub fn tag_snapshots_in_range(
+    project_path: &Path,
+    from: i64,
+    to: i64,
+    tag: &str,
+) -> Result<Vec<SnapshotIdMapping>, String> {
+    let repo_lock = snapshot_repo_lock(project_path);
+    let _guard = repo_lock.lock().unwrap_or_else(|e| e.into_inner());
+    let snapshot_path = get_snapshot_repo_path(project_path);
+    let repo = Repository::open(&snapshot_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    rewrite_snapshot_messages(&repo, "Tag snapshots", |_, commit| {
+        let message = commit.message().unwrap_or("");
+        let seconds = commit.time().seconds();
+        if message == "Initial snapshot" || seconds < from || seconds > to {
+            return None;
+        }
+
+        let mut parsed = SnapshotMessage::parse(message);
+        if parsed.tags.iter().any(|t| t == tag) {
+            None
+        } else {
+            parsed.tags.push(tag.to_string());
+            Some(parsed.format())
+        }
+    })
+}
+
+/// Change a snapshot's name and description. The message is part of the commit, so
+/// the snapshot and every later snapshot are rewritten; returns the snapshot's new id.
+pub fn rename_snapshot(
+    project_path: &Path,
+    snapshot_id: &str,
+    new_name: &str,
+    new_description: Option<&str>,
+) -> Result<String, String> {
+    let repo_lock = snapshot_repo_lock(project_path);
+    let _guard = repo_lock.lock().unwrap_or_else(|e| e.into_inner());
+    let snapshot_path = get_snapshot_repo_path(project_path);
+    let repo = Repository::open(&snapshot_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    let target = git2::Oid::from_str(snapshot_id)
+        .map_err(|e| format!("Invalid snapshot ID: {}", e))?;
+    let commit = repo.find_commit(target)
+        .map_err(|e| format!("Snapshot not found: {}", e))?;
+    if commit.message() == Some("Initial snapshot") {
+        return Err("The initial snapshot cannot be renamed".to_string());
+    }
+
+    let mut parsed = SnapshotMessage::parse(commit.message().unwrap_or(""));
+    parsed.name = new_name.to_string();
+    parsed.description = new_description.map(|d| d.to_string());
+    let new_message = parsed.format();
+    if commit.message() == Some(new_message.as_str()) {
+        return Ok(snapshot_id.to_string());
+    }
+
+    let mut found = false;
+    let mappings = rewrite_snapshot_messages(&repo, "Rename snapshot", |oid, _| {
+        if oid == target {
+            found = true;
+            Some(new_message.clone())
+        } else {
+            None
+        }
+    })?;
+    if !found {
+        return Err("Snapshot is not part of the snapshot history".to_string());
+    }
+
+    mappings
+        .into_iter()
+        .find(|m| m.old_id == target.to_string())
+        .map(|m| m.new_id)
+        .ok_or_else(|| "Failed to rewrite snapshot".to_string())
+}
+
+/// Auto snapshots get this fraction of the manual retention window when pruning
+const AUTO_SNAPSHOT_RETENTION_DIVISOR: i64 = 2;
+
+/// Unreachable loose objects are only deleted once this old, like `git gc`'s prune expiry
+const LOOSE_OBJECT_EXPIRY: std::time::Duration = std::time::Duration::from_secs(14 * 24 * 60 * 60);
+
+/// Drop old snapshots, keeping the `keep_last` most recent plus any newer than
+/// `keep_days` days. Auto snapshots are counted separately and kept for only half
+/// as many snapshots (rounded up) and days. The newest snapshot is never dropped.
+/// Kept snapshots are re-chained onto the initial commit (their ids change), then
+/// the objects are repacked; unreachable ones are deleted once they expire.
+/// Returns how many snapshots were removed.
+pub fn prune_snapshots(
+    project_path: &Path,
+    keep_last: usize,
+    keep_days: Option<i64>,
+) -> Result<usize, String> {
+    let snapshot_path = get_snapshot_repo_path(project_path);
+    if !snapshot_path.join(".git").exists() {
+        return Ok(0);
+    }
+    let repo_lock = snapshot_repo_lock(project_path);
+    let _guard = repo_lock.lock().unwrap_or_else(|e| e.into_inner());
+
+    let pruned = rechain_kept_snapshots(&snapshot_path, keep_last, keep_days)?;
+    if pruned > 0 {
+        // The snapshots are already gone; failing to reclaim their space isn't fatal
+        if let Err(e) = compact_objects(&snapshot_path, LOOSE_OBJECT_EXPIRY) {
+            eprintln!("Failed to reclaim space from pruned snapshots: {}", e);
+        }
+    }
+
+    Ok(pruned)
+}
+
+/// The history rewrite behind `prune_snapshots`. Returns how many snapshots were dropped.
+fn rechain_kept_snapshots(
+    snapshot_path: &Path,
+    keep_last: usize,
+    keep_days: Option<i64>,
+) -> Result<usize, String> {
+    let repo = Repository::open(snapshot_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    let head = repo.head().map_err(|e| format!("Failed to get HEAD: {}", e))?;
+    let head_ref = head
+        .name()
+        .ok_or("HEAD is not a named reference")?
+        .to_string();
+    let start_oid = head.target().ok_or("HEAD does not point at a commit")?;
+
+    let mut revwalk = repo.revwalk().map_err(|e| format!("Failed to create revwalk: {}", e))?;
+    revwalk.push(start_oid).map_err(|e| format!("Failed to push HEAD: {}", e))?;
+    revwalk
+        .simplify_first_parent()
+        .map_err(|e| format!("Failed to configure revwalk: {}", e))?;
+
+    // Newest first
+    let mut base = None;
+    let mut snapshots = Vec::new();
+    for oid in revwalk {
+        let oid = oid.map_err(|e| format!("Failed to get oid: {}", e))?;
+        let commit = repo.find_commit(oid).map_err(|e| format!("Failed to find commit: {}", e))?;
+        if commit.message() == Some("Initial snapshot") || commit.parent_count() == 0 {
+            base = Some(commit);
+        } else {
+            snapshots.push(commit);
+        }
+    }
+    let base = base.ok_or("Snapshot history has no initial commit")?;
+
+    let now = chrono::Utc::now().timestamp();
+    let keep_seconds = keep_days.map(|days| days * 24 * 60 * 60);
+    let mut seen_manual = 0;
+    let mut seen_auto = 0;
+    let keep: Vec<bool> = snapshots
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let is_auto = SnapshotMessage::parse(c.message().unwrap_or("")).snapshot_type == "auto";
+            let (seen, limit, window) = if is_auto {
+                (
+                    &mut seen_auto,
+                    keep_last.div_ceil(AUTO_SNAPSHOT_RETENTION_DIVISOR as usize),
+                    keep_seconds.map(|s| s / AUTO_SNAPSHOT_RETENTION_DIVISOR),
+                )
+            } else {
+                (&mut seen_manual, keep_last, keep_seconds)
+            };
+            *seen += 1;
+            // The newest snapshot is always kept, whatever its type
+            i == 0 || *seen <= limit || window.is_some_and(|w| c.time().seconds() >= now - w)
+        })
+        .collect();
+
+    let pruned = keep.iter().filter(|k| !**k).count();
+    if pruned == 0 {
+        return Ok(0);
+    }
+
+    // Re-chain the kept snapshots, oldest first
+    let mut parent = base;
+    for (commit, _) in snapshots.iter().zip(&keep).rev().filter(|(_, k)| **k) {
+        let tree = commit.tree().map_err(|e| format!("Failed to get tree: {}", e))?;
+        let new_oid = repo
+            .commit(
+                None,
+                &commit.author(),
+                &commit.committer(),
+                commit.message().unwrap_or(""),
+                &tree,
+                &[&parent],
+            )
+            .map_err(|e| format!("Failed to rewrite snapshot: {}", e))?;
+        parent = repo
+            .find_commit(new_oid)
+            .map_err(|e| format!("Failed to find commit: {}", e))?;
+    }
+
+    // Fails rather than dropping a snapshot committed since the walk started
+    repo.reference_matching(&head_ref, parent.id(), true, start_oid, "Prune snapshots")
+        .map_err(|e| format!("Failed to update HEAD: {}", e))?;
+
+    Ok(pruned)
+}
+
+/// Repack a repository's objects into a single pack holding only what its refs
+/// reach, then delete the reflogs, the loose objects now in that pack, unreachable
+/// loose objects older than `expiry` and older packs. Stands in for `git gc`,
+/// which libgit2 doesn't provide.
+fn compact_objects(repo_path: &Path, expiry: std::time::Duration) -> Result<(), String> {
+    let objects_dir = repo_path.join(".git").join("objects");
+    let pack_dir = objects_dir.join("pack");
+
+    // Scoped so the repository (and its open packs) are closed before deleting files
+    let new_pack = {
+        let repo = Repository::open(repo_path)
+            .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+        let mut walk = repo.revwalk().map_err(|e| format!("Failed to create revwalk: {}", e))?;
+        let references = repo
+            .references()
+            .map_err(|e| format!("Failed to list references: {}", e))?;
+        for reference in references.flatten() {
+            // Reflogs still point at the dropped commits
+            if let Some(name) = reference.name() {
+                let _ = repo.reflog_delete(name);
+            }
+            if let Ok(commit) = reference.peel_to_commit() {
+                walk.push(commit.id())
+                    .map_err(|e| format!("Failed to walk {}: {}", commit.id(), e))?;
+            }
+        }
+        let _ = repo.reflog_delete("HEAD");
+
+        let mut builder = repo
+            .packbuilder()
+            .map_err(|e| format!("Failed to create pack builder: {}", e))?;
+        builder
+            .insert_walk(&mut walk)
+            .map_err(|e| format!("Failed to collect objects: {}", e))?;
+        let mut pack = git2::Buf::new();
+        builder
+            .write_buf(&mut pack)
+            .map_err(|e| format!("Failed to build pack: {}", e))?;
+
+        // A pack file is named after the checksum at its end
+        let checksum = git2::Oid::from_bytes(&pack[pack.len().saturating_sub(20)..])
+            .map_err(|e| format!("Failed to read pack checksum: {}", e))?;
+
+        let odb = repo.odb().map_err(|e| format!("Failed to open object database: {}", e))?;
+        let mut writer = odb
+            .packwriter()
+            .map_err(|e| format!("Failed to create pack writer: {}", e))?;
+        std::io::Write::write_all(&mut writer, &pack)
+            .map_err(|e| format!("Failed to write pack: {}", e))?;
+        writer.commit().map_err(|e| format!("Failed to index pack: {}", e))?;
+
+        format!("pack-{}", checksum)
+    };
+    if !pack_dir.join(format!("{}.pack", new_pack)).exists() {
+        return Err("Repacked objects were not written".to_string());
+    }
+
+    let packed = pack_index_oids(&pack_dir.join(format!("{}.idx", new_pack)))?;
+
+    // Loose objects live in two-hex-digit directories. Ones written since the walk
+    // aren't in the new pack, so only packed or expired objects are removed.
+    for entry in fs::read_dir(&objects_dir).map_err(|e| format!("Failed to read objects: {}", e))? {
+        let dir = entry.map_err(|e| format!("Failed to read objects: {}", e))?.path();
+        let Some(prefix) = dir.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if prefix.len() != 2 || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+            continue;
+        }
+
+        for object in fs::read_dir(&dir).map_err(|e| format!("Failed to read objects: {}", e))? {
+            let path = object.map_err(|e| format!("Failed to read objects: {}", e))?.path();
+            let is_packed = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|rest| git2::Oid::from_str(&format!("{}{}", prefix, rest)).ok())
+                .is_some_and(|oid| packed.contains(&oid));
+            let is_expired = || {
+                fs::metadata(&path)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|t| t.elapsed().ok())
+                    .is_some_and(|age| age >= expiry)
+            };
+            if is_packed || is_expired() {
+                fs::remove_file(&path)
+                    .map_err(|e| format!("Failed to remove loose object: {}", e))?;
+            }
+        }
+        // Only succeeds once the directory is empty
+        let _ = fs::remove_dir(&dir);
+    }
+
+    // Older packs go too once the new pack holds all they do, or they've expired.
+    // Writing an object that's already packed refreshes the pack's mtime.
+    for entry in fs::read_dir(&pack_dir).map_err(|e| format!("Failed to read packs: {}", e))? {
+        let path = entry.map_err(|e| format!("Failed to read packs: {}", e))?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("idx") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|n| n.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if !stem.starts_with("pack-") || stem == new_pack {
+            continue;
+        }
+        let is_expired = fs::metadata(pack_dir.join(format!("{}.pack", stem)))
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|age| age >= expiry);
+        if !is_expired && !pack_index_oids(&path)?.is_subset(&packed) {
+            continue;
+        }
+
+        for extension in ["pack", "rev", "keep"] {
+            let _ = fs::remove_file(pack_dir.join(format!("{}.{}", stem, extension)));
+        }
+        fs::remove_file(&path).map_err(|e| format!("Failed to remove old pack: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Object ids listed in a (version 2) pack index
+fn pack_index_oids(idx_path: &Path) -> Result<std::collections::HashSet<git2::Oid>, String> {
+    let idx = fs::read(idx_path).map_err(|e| format!("Failed to read pack index: {}", e))?;
+
+    // Magic and version, then 256 cumulative fan-out counts, then the sorted ids
+    const HEADER: usize = 8;
+    const FANOUT: usize = 256 * 4;
+    if idx.len() < HEADER + FANOUT || idx[..8] != [0xff, b't', b'O', b'c', 0, 0, 0, 2] {
+        return Err(format!("Unsupported pack index: {}", idx_path.display()));
+    }
+    let count_at = HEADER + FANOUT - 4;
+    let count = u32::from_be_bytes([
+        idx[count_at],
+        idx[count_at + 1],
+        idx[count_at + 2],
+        idx[count_at + 3],
+    ]) as usize;
+
+    let ids = idx
+        .get(HEADER + FANOUT..HEADER + FANOUT + count * 20)
+        .ok_or_else(|| format!("Truncated pack index: {}", idx_path.display()))?;
+    ids.chunks(20)
+        .map(|id| git2::Oid::from_bytes(id).map_err(|e| format!("Invalid pack index: {}", e)))
+        .collect()
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SnapshotSize {
+    pub id: String,
+    pub name: String,
+    /// Uncompressed size of the file contents this snapshot added or changed
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SnapshotRepoSize {
+    /// Everything under .lumen/snapshots, including .git
+    pub total_bytes: u64,
+    /// Newest first
+    pub snapshots: Vec<SnapshotSize>,
+}
+
+/// Measure the snapshot directory on disk and estimate what each snapshot adds to it
+pub fn get_snapshot_repo_size(project_path: &Path) -> Result<SnapshotRepoSize, String> {
+    let snapshot_path = get_snapshot_repo_path(project_path);
+    if !snapshot_path.join(".git").exists() {
+        return Ok(SnapshotRepoSize {
+            total_bytes: 0,
+            snapshots: vec![],
+        });
+    }
+
+    let total_bytes = WalkDir::new(&snapshot_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum();
+
+    let repo = Repository::open(&snapshot_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+    let odb = repo.odb().map_err(|e| format!("Failed to open object database: {}", e))?;
+
+    let mut revwalk = repo.revwalk().map_err(|e| format!("Failed to create revwalk: {}", e))?;
+    revwalk.push_head().map_err(|e| format!("Failed to push HEAD: {}", e))?;
+
+    let mut snapshots = Vec::new();
+    for oid in revwalk {
+        let oid = oid.map_err(|e| format!("Failed to get oid: {}", e))?;
+        let commit = repo.find_commit(oid).map_err(|e| format!("Failed to find commit: {}", e))?;
+        let message = commit.message().unwrap_or("");
+        if message == "Initial snapshot" {
+            continue;
+        }
+
+        let tree = commit.tree().map_err(|e| format!("Failed to get tree: {}", e))?;
+        let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .map_err(|e| format!("Failed to create diff: {}", e))?;
+
+        // Only new blobs take up space; unchanged files are shared with the parent
+        let bytes = diff
+            .deltas()
+            .filter(|d| matches!(d.status(), git2::Delta::Added | git2::Delta::Modified))
+            .filter_map(|d| odb.read_header(d.new_file().id()).ok())
+            .map(|(size, _)| size as u64)
+            .sum();
+
+        snapshots.push(SnapshotSize {
+            id: oid.to_string(),
+            name: SnapshotMessage::parse(message).name,
+            bytes,
+        });
+    }
+
+    Ok(SnapshotRepoSize {
+        total_bytes,
+        snapshots,
+    })
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileChange {
+    pub path: String,
+    pub status: String, // "added", "modified", "deleted", "renamed"
+    pub additions: i32,
+    pub deletions: i32,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SnapshotDiff {
+    pub files: Vec<FileChange>,
+    pub total_additions: i32,
+    pub total_deletions: i32,
+}
+
+/// Collect per-file and total line counts from a tree diff. Renames are detected
+/// so their changes are attributed to the new path.
+fn summarize_diff(mut diff: git2::Diff) -> Result<SnapshotDiff, String> {
+    diff.find_similar(None)
+        .map_err(|e| format!("Failed to detect renames: {}", e))?;
+
+    fn delta_path(delta: &git2::DiffDelta) -> String {
+        delta.new_file().path()
+            .or_else(|| delta.old_file().path())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    let mut files = Vec::new();
+    let mut line_counts: std::collections::HashMap<String, (i32, i32)> =
+        std::collections::HashMap::new();
+
+    diff.foreach(
+        &mut |delta, _| {
+            let status = match delta.status() {
+                git2::Delta::Added => "added",
+                git2::Delta::Deleted => "deleted",
+                git2::Delta::Modified => "modified",
+                git2::Delta::Renamed => "renamed",
+                git2::Delta::Copied => "copied",
+                _ => "unknown",
+            };
 
             files.push(FileChange {
-                path,
+                path: delta_path(&delta),
                 status: status.to_string(),
                 additions: 0,
                 deletions: 0,
@@ -450,16 +1530,32 @@ pub fn get_snapshot_diff(project_path: &Path, snapshot_id: &str) -> Result<Snaps
         },
         None,
         None,
-        Some(&mut |_delta, _hunk, line| {
+        Some(&mut |delta, _hunk, line| {
+            // Binary files have no meaningful line counts
+            if delta.flags().is_binary() {
+                return true;
+            }
+            let counts = line_counts.entry(delta_path(&delta)).or_default();
             match line.origin() {
-                '+' => total_additions += 1,
-                '-' => total_deletions += 1,
+                '+' => counts.0 += 1,
+                '-' => counts.1 += 1,
                 _ => {}
             }
             true
         }),
     ).map_err(|e| format!("Failed to iterate diff: {}", e))?;
 
+    let mut total_additions = 0;
+    let mut total_deletions = 0;
+    for file in &mut files {
+        if let Some((additions, deletions)) = line_counts.get(&file.path) {
+            file.additions = *additions;
+            file.deletions = *deletions;
+        }
+        total_additions += file.additions;
+        total_deletions += file.deletions;
+    }
+
     Ok(SnapshotDiff {
         files,
         total_additions,
@@ -467,37 +1563,481 @@ pub fn get_snapshot_diff(project_path: &Path, snapshot_id: &str) -> Result<Snaps
     })
 }
 
+/// Get the diff for a specific snapshot (compared to its parent)
+pub fn get_snapshot_diff(project_path: &Path, snapshot_id: &str) -> Result<SnapshotDiff, String> {
+    let snapshot_path = get_snapshot_repo_path(project_path);
+    let repo = Repository::open(&snapshot_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    let oid = git2::Oid::from_str(snapshot_id)
+        .map_err(|e| format!("Invalid snapshot ID: {}", e))?;
+
+    let commit = repo.find_commit(oid)
+        .map_err(|e| format!("Snapshot not found: {}", e))?;
+
+    let tree = commit.tree().map_err(|e| format!("Failed to get tree: {}", e))?;
+
+    // Get parent commit tree (or empty tree if this is the first commit)
+    let parent_tree = commit.parent(0)
+        .ok()
+        .and_then(|p| p.tree().ok());
+
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+        .map_err(|e| format!("Failed to create diff: {}", e))?;
+
+    summarize_diff(diff)
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DirectoryChange {
+    /// Directory path truncated to the requested depth ("." for the project root)
+    pub directory: String,
+    pub files_changed: i32,
+    pub additions: i32,
+    pub deletions: i32,
+}
+
+/// Aggregate a diff's per-file changes into per-directory totals, grouping paths by
+/// their first `depth` directory components. Busiest directories come first.
+pub fn group_diff_by_directory(diff: &SnapshotDiff, depth: usize) -> Vec<DirectoryChange> {
+    let mut totals: std::collections::HashMap<String, DirectoryChange> =
+        std::collections::HashMap::new();
+
+    for file in &diff.files {
+        let components: Vec<&str> = file.path.split('/').collect();
+        let dirs = &components[..components.len().saturating_sub(1)];
+        let directory = if dirs.is_empty() || depth == 0 {
+            ".".to_string()
+        } else {
+            dirs[..depth.min(dirs.len())].join("/")
+        };
+
+        let entry = totals
+            .entry(directory.clone())
+            .or_insert_with(|| DirectoryChange {
+                directory,
+                files_changed: 0,
+                additions: 0,
+                deletions: 0,
+            });
+        entry.files_changed += 1;
+        entry.additions += file.additions;
+        entry.deletions += file.deletions;
+    }
+
+    let mut changes: Vec<DirectoryChange> = totals.into_values().collect();
+    changes.sort_by(|a, b| {
+        (b.additions + b.deletions)
+            .cmp(&(a.additions + a.deletions))
+            .then(b.files_changed.cmp(&a.files_changed))
+            .then(a.directory.cmp(&b.directory))
+    });
+    changes
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileChurn {
+    pub path: String,
+    /// Number of snapshots that changed the file
+    pub change_count: i32,
+    /// Timestamp of the most recent snapshot that changed it
+    pub last_changed: String,
+}
+
+/// Count how many of the last `last_n` snapshots changed each file, by diffing each
+/// snapshot against its parent. Ranked most-changed first, then most recently changed.
+pub fn get_churn_stats(project_path: &Path, last_n: usize) -> Result<Vec<FileChurn>, String> {
+    let snapshot_path = get_snapshot_repo_path(project_path);
+    if !snapshot_path.join(".git").exists() {
+        return Ok(vec![]);
+    }
+
+    let repo = Repository::open(&snapshot_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    let mut revwalk = repo.revwalk().map_err(|e| format!("Failed to create revwalk: {}", e))?;
+    revwalk.push_head().map_err(|e| format!("Failed to push HEAD: {}", e))?;
+
+    // Newest first, so the first time a path is seen is its most recent change
+    let mut churn: std::collections::HashMap<String, FileChurn> = std::collections::HashMap::new();
+
+    for oid in revwalk.take(last_n) {
+        let oid = oid.map_err(|e| format!("Failed to get oid: {}", e))?;
+        let commit = repo.find_commit(oid).map_err(|e| format!("Failed to find commit: {}", e))?;
+        if commit.message() == Some("Initial snapshot") {
+            break;
+        }
+
+        let tree = commit.tree().map_err(|e| format!("Failed to get tree: {}", e))?;
+        let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+        let mut diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .map_err(|e| format!("Failed to create diff: {}", e))?;
+        diff.find_similar(None)
+            .map_err(|e| format!("Failed to detect renames: {}", e))?;
+
+        let timestamp = chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_default();
+
+        for delta in diff.deltas() {
+            let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+                continue;
+            };
+            let path = path.to_string_lossy().to_string();
+
+            churn
+                .entry(path.clone())
+                .or_insert_with(|| FileChurn {
+                    path,
+                    change_count: 0,
+                    last_changed: timestamp.clone(),
+                })
+                .change_count += 1;
+        }
+    }
+
+    let mut files: Vec<FileChurn> = churn.into_values().collect();
+    files.sort_by(|a, b| {
+        b.change_count
+            .cmp(&a.change_count)
+            .then_with(|| b.last_changed.cmp(&a.last_changed))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    Ok(files)
+}
+
+/// Find a file's blob and git file mode at a specific snapshot (None if it
+/// didn't exist then)
+fn find_blob_at_snapshot<'r>(
+    repo: &'r Repository,
+    snapshot_id: &str,
+    file_path: &str,
+) -> Result<Option<(git2::Blob<'r>, i32)>, String> {
+    let oid = git2::Oid::from_str(snapshot_id)
+        .map_err(|e| format!("Invalid snapshot ID: {}", e))?;
+
+    let commit = repo.find_commit(oid)
+        .map_err(|e| format!("Snapshot not found: {}", e))?;
+
+    let tree = commit.tree().map_err(|e| format!("Failed to get tree: {}", e))?;
+
+    match tree.get_path(Path::new(file_path)) {
+        Ok(entry) => repo.find_blob(entry.id())
+            .map(|blob| Some((blob, entry.filemode())))
+            .map_err(|e| format!("Failed to find blob: {}", e)),
+        Err(_) => Ok(None), // File doesn't exist at this snapshot
+    }
+}
+
+/// Git file mode for a working tree file
+fn file_mode(path: &Path) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if fs::metadata(path).map(|m| m.permissions().mode() & 0o111 != 0).unwrap_or(false) {
+            return 0o100755;
+        }
+    }
+    0o100644
+}
+
+/// Write a directory of the project (with the same exclusions as a snapshot copy)
+/// as a tree in `repo`. Returns None for directories with nothing to include.
+fn write_working_tree(
+    repo: &Repository,
+    dir: &Path,
+    project_path: &Path,
+) -> Result<Option<git2::Oid>, String> {
+    let mut builder = repo.treebuilder(None)
+        .map_err(|e| format!("Failed to create tree: {}", e))?;
+
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        if should_exclude(&path, project_path) {
+            continue;
+        }
+
+        let name = entry.file_name();
+        // Doesn't follow symlinks, so a link loop or a link out of the project
+        // is recorded as a link rather than walked or read
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+        let (oid, mode) = if file_type.is_symlink() {
+            let target = fs::read_link(&path).map_err(|e| e.to_string())?;
+            let oid = repo.blob(target.to_string_lossy().as_bytes())
+                .map_err(|e| format!("Failed to write blob: {}", e))?;
+            (oid, i32::from(git2::FileMode::Link))
+        } else if file_type.is_dir() {
+            match write_working_tree(repo, &path, project_path)? {
+                Some(oid) => (oid, 0o040000),
+                None => continue,
+            }
+        } else {
+            let content = fs::read(&path).map_err(|e| e.to_string())?;
+            let oid = repo.blob(&content)
+                .map_err(|e| format!("Failed to write blob: {}", e))?;
+            (oid, file_mode(&path))
+        };
+
+        builder.insert(Path::new(&name), oid, mode)
+            .map_err(|e| format!("Failed to add tree entry: {}", e))?;
+    }
+
+    if builder.is_empty() {
+        return Ok(None);
+    }
+    builder.write()
+        .map(Some)
+        .map_err(|e| format!("Failed to write tree: {}", e))
+}
+
+/// Diff a snapshot (HEAD if `snapshot_id` is None) against the project's current
+/// files. The working tree is staged in memory only; nothing in the snapshot
+/// directory is touched.
+pub fn diff_snapshot_to_working(
+    project_path: &Path,
+    snapshot_id: Option<&str>,
+) -> Result<SnapshotDiff, String> {
+    let snapshot_path = get_snapshot_repo_path(project_path);
+    let repo = Repository::open(&snapshot_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    // New objects go to the highest-priority backend, so this keeps them off disk
+    let odb = repo.odb().map_err(|e| format!("Failed to open object database: {}", e))?;
+    let _mempack = odb.add_new_mempack_backend(1000)
+        .map_err(|e| format!("Failed to create in-memory object store: {}", e))?;
+
+    let commit = match snapshot_id {
+        Some(id) => {
+            let oid = git2::Oid::from_str(id)
+                .map_err(|e| format!("Invalid snapshot ID: {}", e))?;
+            repo.find_commit(oid)
+                .map_err(|e| format!("Snapshot not found: {}", e))?
+        }
+        None => repo.head()
+            .and_then(|h| h.peel_to_commit())
+            .map_err(|e| format!("Failed to get HEAD: {}", e))?,
+    };
+    let snapshot_tree = commit.tree().map_err(|e| format!("Failed to get tree: {}", e))?;
+
+    let working_oid = match write_working_tree(&repo, project_path, project_path)? {
+        Some(oid) => oid,
+        None => repo.treebuilder(None)
+            .and_then(|b| b.write())
+            .map_err(|e| format!("Failed to write tree: {}", e))?,
+    };
+    let working_tree = repo.find_tree(working_oid)
+        .map_err(|e| format!("Failed to find tree: {}", e))?;
+
+    let diff = repo.diff_tree_to_tree(Some(&snapshot_tree), Some(&working_tree), None)
+        .map_err(|e| format!("Failed to create diff: {}", e))?;
+
+    summarize_diff(diff)
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TextDiff {
+    /// Unified diff, with `path` in the headers
+    pub patch: String,
+    pub additions: i32,
+    pub deletions: i32,
+}
+
+/// Line diff between two versions of a text file
+pub fn diff_text(old: &str, new: &str, path: &str) -> Result<TextDiff, String> {
+    let mut patch = git2::Patch::from_buffers(
+        old.as_bytes(),
+        Some(Path::new(path)),
+        new.as_bytes(),
+        Some(Path::new(path)),
+        None,
+    )
+    .map_err(|e| format!("Failed to diff {}: {}", path, e))?;
+
+    let (_, additions, deletions) = patch
+        .line_stats()
+        .map_err(|e| format!("Failed to count changes: {}", e))?;
+    let patch_text = patch
+        .to_buf()
+        .map_err(|e| format!("Failed to format diff: {}", e))?;
+
+    Ok(TextDiff {
+        patch: String::from_utf8_lossy(&patch_text).to_string(),
+        additions: additions as i32,
+        deletions: deletions as i32,
+    })
+}
+
 /// Get file content at a specific snapshot
 pub fn get_file_at_snapshot(
     project_path: &Path,
     snapshot_id: &str,
     file_path: &str,
 ) -> Result<Option<String>, String> {
+    let snapshot_path = get_snapshot_repo_path(project_path);
+    let repo = Repository::open(&snapshot_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    let content = find_blob_at_snapshot(&repo, snapshot_id, file_path)?.map(|(blob, _)| {
+        if blob.is_binary() {
+            "[Binary file]".to_string()
+        } else {
+            String::from_utf8_lossy(blob.content()).to_string()
+        }
+    });
+    Ok(content)
+}
+
+/// Restore a single file from a snapshot, leaving the rest of the project untouched
+pub fn restore_file_from_snapshot(
+    project_path: &Path,
+    snapshot_id: &str,
+    file_path: &str,
+) -> Result<(), String> {
+    let relative = Path::new(file_path);
+    if relative.is_absolute()
+        || relative.components().any(|c| !matches!(c, std::path::Component::Normal(_)))
+    {
+        return Err(format!("Invalid file path: {}", file_path));
+    }
+
+    let repo_lock = snapshot_repo_lock(project_path);
+    let _guard = repo_lock.lock().unwrap_or_else(|e| e.into_inner());
+    let snapshot_path = get_snapshot_repo_path(project_path);
+    let repo = Repository::open(&snapshot_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    let (blob, mode) = find_blob_at_snapshot(&repo, snapshot_id, file_path)?
+        .ok_or_else(|| format!("{} does not exist in this snapshot", file_path))?;
+
+    let target = project_path.join(relative);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    // Replace whatever is there now rather than writing through a link
+    let is_link = mode == i32::from(git2::FileMode::Link);
+    if let Ok(existing) = fs::symlink_metadata(&target) {
+        if existing.file_type().is_symlink() || (is_link && !existing.is_dir()) {
+            fs::remove_file(&target)
+                .map_err(|e| format!("Failed to replace file: {}", e))?;
+        }
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        use std::os::unix::fs::PermissionsExt;
+
+        if is_link {
+            let link_target = std::ffi::OsStr::from_bytes(blob.content());
+            return std::os::unix::fs::symlink(link_target, &target)
+                .map_err(|e| format!("Failed to create link: {}", e));
+        }
+
+        fs::write(&target, blob.content())
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+        let permissions = if mode == i32::from(git2::FileMode::BlobExecutable) {
+            0o755
+        } else {
+            0o644
+        };
+        fs::set_permissions(&target, fs::Permissions::from_mode(permissions))
+            .map_err(|e| format!("Failed to set permissions: {}", e))
+    }
+
+    // Without symlink support a link is restored as a file holding its target,
+    // as git does with core.symlinks off
+    #[cfg(not(unix))]
+    fs::write(&target, blob.content())
+        .map_err(|e| format!("Failed to write file: {}", e))
+}
+
+/// Write every file in a snapshot to a zip archive at `dest_zip`, keeping relative
+/// paths and file contents byte for byte. Returns the number of files written.
+pub fn export_snapshot(
+    project_path: &Path,
+    snapshot_id: &str,
+    dest_zip: &Path,
+) -> Result<usize, String> {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
     let snapshot_path = get_snapshot_repo_path(project_path);
     let repo = Repository::open(&snapshot_path)
         .map_err(|e| format!("Failed to open repository: {}", e))?;
 
     let oid = git2::Oid::from_str(snapshot_id)
         .map_err(|e| format!("Invalid snapshot ID: {}", e))?;
-
     let commit = repo.find_commit(oid)
         .map_err(|e| format!("Snapshot not found: {}", e))?;
-
     let tree = commit.tree().map_err(|e| format!("Failed to get tree: {}", e))?;
 
-    match tree.get_path(Path::new(file_path)) {
-        Ok(entry) => {
-            let blob = repo.find_blob(entry.id())
-                .map_err(|e| format!("Failed to find blob: {}", e))?;
-
-            if blob.is_binary() {
-                Ok(Some("[Binary file]".to_string()))
-            } else {
-                Ok(Some(String::from_utf8_lossy(blob.content()).to_string()))
+    // Stamp every entry with the snapshot's time rather than the zip default (1980)
+    let modified = chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+        .map(|t| t.naive_utc())
+        .and_then(|t| {
+            use chrono::{Datelike, Timelike};
+            zip::DateTime::from_date_and_time(
+                t.year() as u16,
+                t.month() as u8,
+                t.day() as u8,
+                t.hour() as u8,
+                t.minute() as u8,
+                t.second() as u8,
+            )
+            .ok()
+        })
+        .unwrap_or_default();
+
+    let mut entries = Vec::new();
+    tree.walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+        if entry.kind() == Some(git2::ObjectType::Blob) {
+            if let Some(name) = entry.name() {
+                entries.push((format!("{}{}", dir, name), entry.id(), entry.filemode()));
             }
         }
-        Err(_) => Ok(None), // File doesn't exist at this snapshot
+        git2::TreeWalkResult::Ok
+    })
+    .map_err(|e| format!("Failed to walk snapshot tree: {}", e))?;
+
+    if let Some(parent) = dest_zip.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let file = fs::File::create(dest_zip)
+        .map_err(|e| format!("Failed to create zip file: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+
+    for (path, oid, mode) in &entries {
+        let blob = repo.find_blob(*oid)
+            .map_err(|e| format!("Failed to find blob for {}: {}", path, e))?;
+
+        if *mode == i32::from(git2::FileMode::Link) {
+            let target = String::from_utf8_lossy(blob.content());
+            let options = SimpleFileOptions::default().last_modified_time(modified);
+            zip.add_symlink(path.as_str(), target.as_ref(), options)
+                .map_err(|e| format!("Failed to add {} to zip: {}", path, e))?;
+            continue;
+        }
+
+        let permissions = if *mode == i32::from(git2::FileMode::BlobExecutable) { 0o755 } else { 0o644 };
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .last_modified_time(modified)
+            .unix_permissions(permissions);
+        zip.start_file(path.as_str(), options)
+            .map_err(|e| format!("Failed to add {} to zip: {}", path, e))?;
+        zip.write_all(blob.content())
+            .map_err(|e| format!("Failed to write {} to zip: {}", path, e))?;
     }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finish zip file: {}", e))?;
+
+    Ok(entries.len())
 }
 
 /// Compare two snapshots
@@ -526,49 +2066,333 @@ pub fn compare_snapshots(
     let diff = repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), None)
         .map_err(|e| format!("Failed to create diff: {}", e))?;
 
-    let mut files = Vec::new();
-    let mut total_additions = 0;
-    let mut total_deletions = 0;
+    summarize_diff(diff)
+}
 
-    diff.foreach(
-        &mut |delta, _| {
-            let status = match delta.status() {
-                git2::Delta::Added => "added",
-                git2::Delta::Deleted => "deleted",
-                git2::Delta::Modified => "modified",
-                git2::Delta::Renamed => "renamed",
-                git2::Delta::Copied => "copied",
-                _ => "unknown",
-            };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            let path = delta.new_file().path()
-                .or_else(|| delta.old_file().path())
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_default();
+    fn excluded(rules: &ExclusionRules, path: &str, is_dir: bool) -> bool {
+        rules.check(Path::new(path), is_dir).is_some()
+    }
 
-            files.push(FileChange {
-                path,
-                status: status.to_string(),
-                additions: 0,
-                deletions: 0,
-            });
-            true
-        },
-        None,
-        None,
-        Some(&mut |_delta, _hunk, line| {
-            match line.origin() {
-                '+' => total_additions += 1,
-                '-' => total_deletions += 1,
-                _ => {}
+    #[test]
+    fn test_exclusion_nested_globs() {
+        let rules = ExclusionRules::new("src/**/*.test.ts\nbuild/*/cache\n", "");
+
+        assert!(excluded(&rules, "src/a/b/c.test.ts", false));
+        assert!(excluded(&rules, "src/c.test.ts", false));
+        assert!(!excluded(&rules, "lib/c.test.ts", false));
+        assert!(excluded(&rules, "build/debug/cache", true));
+        assert!(excluded(&rules, "build/debug/cache/entry.bin", false));
+        assert!(!excluded(&rules, "build/debug/nested/cache", true));
+        assert!(excluded(&rules, "packages/app/node_modules/x/index.js", false));
+    }
+
+    #[test]
+    fn test_exclusion_negation_and_anchoring() {
+        let rules = ExclusionRules::new("*.log\n!keep.log\n/dist\nlogs/\n!logs/important.txt\n", "");
+
+        assert!(excluded(&rules, "debug.log", false));
+        assert!(excluded(&rules, "nested/debug.log", false));
+        assert!(!excluded(&rules, "keep.log", false));
+        assert!(!excluded(&rules, "nested/keep.log", false));
+
+        assert!(excluded(&rules, "dist/app.js", false));
+        assert!(!excluded(&rules, "web/dist/app.js", false));
+
+        // Files inside an excluded directory can't be re-included
+        assert!(excluded(&rules, "logs/important.txt", false));
+
+        // Defaults can't be re-included by .gitignore
+        let rules = ExclusionRules::new("!.env\n", "");
+        assert!(excluded(&rules, ".env", false));
+    }
+
+    #[test]
+    fn test_exclusion_directory_only_patterns() {
+        let rules = ExclusionRules::new("logs/\n", "");
+
+        assert!(excluded(&rules, "logs", true));
+        assert!(excluded(&rules, "logs/today.txt", false));
+        assert!(excluded(&rules, "app/logs/today.txt", false));
+        assert!(!excluded(&rules, "logs", false));
+        assert!(!excluded(&rules, "changelogs/today.txt", false));
+    }
+
+    #[test]
+    fn test_lumenignore_exclusions() {
+        let rules = ExclusionRules::new("", "assets/\n*.psd\n!.env.example\n");
+
+        assert!(excluded(&rules, "assets/big.png", false));
+        assert!(excluded(&rules, "design/logo.psd", false));
+        assert!(!excluded(&rules, "src/main.rs", false));
+
+        // .lumenignore can re-include what the defaults exclude
+        assert!(!excluded(&rules, ".env.example", false));
+        assert!(excluded(&rules, ".env.local", false));
+
+        let reason = rules.check(Path::new("assets/big.png"), false).unwrap();
+        assert_eq!(reason.source, "lumenignore");
+        assert_eq!(reason.pattern, "assets/");
+    }
+
+    #[test]
+    fn test_lumenignore_applies_to_snapshot_copy() {
+        let root = std::env::temp_dir().join(format!("lumen-ignore-test-{}", uuid::Uuid::new_v4()));
+        let project = root.join("project");
+        let snapshot = root.join("snapshot");
+        fs::create_dir_all(project.join("assets")).unwrap();
+        fs::create_dir_all(&snapshot).unwrap();
+        fs::write(project.join("assets/big.bin"), "data").unwrap();
+        fs::write(project.join("main.rs"), "fn main() {}").unwrap();
+
+        assert_eq!(copy_project_to_snapshot(&project, &snapshot).unwrap(), 2);
+        assert!(snapshot.join("assets/big.bin").exists());
+
+        fs::write(project.join(".lumenignore"), "assets/\n").unwrap();
+        copy_project_to_snapshot(&project, &snapshot).unwrap();
+        assert!(!snapshot.join("assets").exists());
+        assert!(snapshot.join("main.rs").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_snapshot_copy_is_incremental() {
+        let root = std::env::temp_dir().join(format!("lumen-copy-test-{}", uuid::Uuid::new_v4()));
+        let project = root.join("project");
+        let snapshot = root.join("snapshot");
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::create_dir_all(snapshot.join(".git")).unwrap();
+        for i in 0..50 {
+            fs::write(project.join("src").join(format!("file{}.txt", i)), i.to_string()).unwrap();
+        }
+
+        assert_eq!(copy_project_to_snapshot(&project, &snapshot).unwrap(), 50);
+        // Nothing changed, so nothing is copied
+        assert_eq!(copy_project_to_snapshot(&project, &snapshot).unwrap(), 0);
+
+        fs::write(project.join("src/file0.txt"), "changed").unwrap();
+        fs::remove_file(project.join("src/file1.txt")).unwrap();
+        assert_eq!(copy_project_to_snapshot(&project, &snapshot).unwrap(), 1);
+        assert_eq!(fs::read_to_string(snapshot.join("src/file0.txt")).unwrap(), "changed");
+        assert!(!snapshot.join("src/file1.txt").exists());
+        assert!(snapshot.join(".git").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_restore_with_progress_can_be_cancelled() {
+        let project = std::env::temp_dir().join(format!("lumen-restore-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&project).unwrap();
+        for i in 0..4 {
+            fs::write(project.join(format!("file{}.txt", i)), "old").unwrap();
+        }
+        let snapshot = create_snapshot(&project, "four files", None).unwrap();
+
+        for i in 0..4 {
+            fs::remove_file(project.join(format!("file{}.txt", i))).unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let outcome = restore_snapshot_with_progress(&project, &snapshot.id, |progress| {
+            seen.push((progress.files_written, progress.total_files));
+            progress.files_written < 2
+        })
+        .unwrap();
+
+        assert_eq!(seen, vec![(1, 4), (2, 4)]);
+        match outcome {
+            RestoreOutcome::Cancelled { files_written, created } => {
+                assert_eq!(files_written, 2);
+                assert_eq!(created.len(), 2);
+                assert!(created.iter().all(|f| f.exists()));
             }
-            true
-        }),
-    ).map_err(|e| format!("Failed to iterate diff: {}", e))?;
+            other => panic!("expected a cancelled restore, got {:?}", other),
+        }
 
-    Ok(SnapshotDiff {
-        files,
-        total_additions,
-        total_deletions,
-    })
+        let outcome = restore_snapshot_with_progress(&project, &snapshot.id, |_| true).unwrap();
+        assert!(matches!(outcome, RestoreOutcome::Completed { files_written: 4 }));
+        assert_eq!(fs::read_to_string(project.join("file3.txt")).unwrap(), "old");
+
+        let _ = fs::remove_dir_all(&project);
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_snapshot() {
+        let project = std::env::temp_dir().join(format!("lumen-prune-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&project).unwrap();
+        for i in 0..3 {
+            fs::write(project.join("file.txt"), format!("v{}", i)).unwrap();
+            create_snapshot_of_type(&project, &format!("auto {}", i), None, "auto").unwrap();
+        }
+
+        assert_eq!(prune_snapshots(&project, 1, None).unwrap(), 2);
+        let remaining = list_snapshots(&project).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].name, "auto 2");
+
+        // A manual snapshot older than the newest auto one still counts toward its own limit
+        fs::write(project.join("file.txt"), "manual").unwrap();
+        create_snapshot(&project, "manual", None).unwrap();
+        fs::write(project.join("file.txt"), "v3").unwrap();
+        create_snapshot_of_type(&project, "auto 3", None, "auto").unwrap();
+        assert_eq!(prune_snapshots(&project, 1, None).unwrap(), 1);
+        let names: Vec<String> = list_snapshots(&project)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["auto 3", "manual"]);
+
+        // The unreachable objects of the dropped snapshots wait out their expiry,
+        // after which only the pack of what's reachable is left
+        let objects = get_snapshot_repo_path(&project).join(".git").join("objects");
+        let count_objects = || {
+            let loose = fs::read_dir(&objects)
+                .unwrap()
+                .flatten()
+                .filter(|e| e.file_name().len() == 2)
+                .flat_map(|e| fs::read_dir(e.path()).unwrap().flatten())
+                .count();
+            let packs = fs::read_dir(objects.join("pack"))
+                .unwrap()
+                .flatten()
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "pack"))
+                .count();
+            (loose, packs)
+        };
+        assert!(count_objects().0 > 0);
+        compact_objects(&get_snapshot_repo_path(&project), std::time::Duration::ZERO).unwrap();
+        assert_eq!(count_objects(), (0, 1));
+        let repo = Repository::open(get_snapshot_repo_path(&project)).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.tree().unwrap().len(), 1);
+        let base = head.parent(0).unwrap().parent(0).unwrap();
+        assert_eq!(base.message(), Some("Initial snapshot"));
+
+        let _ = fs::remove_dir_all(&project);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_working_diff_records_symlinks_as_links() {
+        let project = std::env::temp_dir().join(format!("lumen-symlink-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("a.txt"), "a").unwrap();
+        create_snapshot(&project, "one file", None).unwrap();
+
+        let outside = project.with_extension("outside");
+        fs::write(&outside, "secret\n").unwrap();
+        std::os::unix::fs::symlink(".", project.join("loop")).unwrap();
+        std::os::unix::fs::symlink(&outside, project.join("outside")).unwrap();
+
+        let diff = diff_snapshot_to_working(&project, None).unwrap();
+        let mut added: Vec<&str> = diff.files.iter().map(|f| f.path.as_str()).collect();
+        added.sort();
+        assert_eq!(added, vec!["loop", "outside"]);
+        // Only the link target is stored, not the file it points at
+        assert_eq!(diff.total_additions, 2);
+
+        let _ = fs::remove_dir_all(&project);
+        let _ = fs::remove_file(&outside);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_restore_file_keeps_mode_and_links() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let project = std::env::temp_dir().join(format!("lumen-restore-mode-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("run.sh"), "#!/bin/sh\n").unwrap();
+        fs::set_permissions(project.join("run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+        let snapshot = create_snapshot(&project, "script", None).unwrap();
+
+        // Add a link entry to the snapshot's tree
+        let repo = Repository::open(get_snapshot_repo_path(&project)).unwrap();
+        let commit = repo.find_commit(git2::Oid::from_str(&snapshot.id).unwrap()).unwrap();
+        let mut builder = repo.treebuilder(Some(&commit.tree().unwrap())).unwrap();
+        let link = repo.blob(b"run.sh").unwrap();
+        builder.insert("link", link, i32::from(git2::FileMode::Link)).unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        let sig = Signature::now("Lumen", "lumen@local").unwrap();
+        let with_link = repo.commit(None, &sig, &sig, "script and link", &tree, &[&commit]).unwrap();
+
+        fs::set_permissions(project.join("run.sh"), fs::Permissions::from_mode(0o644)).unwrap();
+        fs::write(project.join("link"), "not a link").unwrap();
+
+        restore_file_from_snapshot(&project, &snapshot.id, "run.sh").unwrap();
+        restore_file_from_snapshot(&project, &with_link.to_string(), "link").unwrap();
+
+        let mode = fs::metadata(project.join("run.sh")).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+        assert!(fs::symlink_metadata(project.join("link")).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_link(project.join("link")).unwrap(), Path::new("run.sh"));
+
+        let _ = fs::remove_dir_all(&project);
+    }
+
+    #[test]
+    fn test_snapshot_message_round_trip() {
+        let mut message = SnapshotMessage::new(
+            "Refactor auth",
+            Some("First paragraph.\n\nSecond paragraph\nwith two lines."),
+            "auto",
+        );
+        message.tags = vec!["wip".to_string(), "auth".to_string()];
+        message.metadata.insert("Trigger".to_string(), "idle".to_string());
+
+        let text = message.format();
+        assert!(text.ends_with("Lumen-Type: auto\nLumen-Tags: wip, auth\nLumen-Trigger: idle"));
+        assert_eq!(SnapshotMessage::parse(&text), message);
+    }
+
+    #[test]
+    fn test_snapshot_message_name_with_blank_lines() {
+        let message = SnapshotMessage::new("Before\n\nrefactor", Some("Details"), "manual");
+        let parsed = SnapshotMessage::parse(&message.format());
+
+        assert_eq!(parsed.name, "Before refactor");
+        assert_eq!(parsed.description.as_deref(), Some("Details"));
+    }
+
+    #[test]
+    fn test_snapshot_message_subject_only() {
+        let parsed = SnapshotMessage::parse("Quick save\n");
+        assert_eq!(parsed.name, "Quick save");
+        assert_eq!(parsed.description, None);
+
+        let parsed = SnapshotMessage::parse("Quick save\n\nLumen-Type: manual");
+        assert_eq!(parsed.name, "Quick save");
+        assert_eq!(parsed.description, None);
+    }
+
+    #[test]
+    fn test_snapshot_message_multi_paragraph_body() {
+        let parsed = SnapshotMessage::parse(
+            "Wrapped snapshot\nname\n\n\nFirst paragraph\nstill first.\n\nSecond paragraph.\n",
+        );
+        assert_eq!(parsed.name, "Wrapped snapshot name");
+        assert_eq!(
+            parsed.description.as_deref(),
+            Some("First paragraph\nstill first.\n\nSecond paragraph.")
+        );
+    }
+
+    #[test]
+    fn test_snapshot_message_legacy_formats() {
+        let parsed = SnapshotMessage::parse("Quick save");
+        assert_eq!(parsed.name, "Quick save");
+        assert_eq!(parsed.description, None);
+        assert_eq!(parsed.snapshot_type, "manual");
+
+        let parsed = SnapshotMessage::parse("Checkpoint\n\nPara one\n\nPara two\n\nTags: a, b");
+        assert_eq!(parsed.name, "Checkpoint");
+        assert_eq!(parsed.description.as_deref(), Some("Para one\n\nPara two"));
+        assert_eq!(parsed.tags, vec!["a", "b"]);
+    }
 }
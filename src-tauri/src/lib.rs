@@ -1,10 +1,11 @@
 pub mod cli;
 mod commands;
+mod config;
 mod db;
 mod git;
 mod pty;
 
-use commands::{analytics, claude_code, github, hooks, memory, projects, prompts, pty as pty_commands, quick_commands, session_memory, sessions, snapshots, sync};
+use commands::{analytics, budgets, claude_code, commit_message, git_watch, github, health, hooks, maintenance, memory, operations, projects, prompts, pty as pty_commands, quick_commands, session_events, session_memory, session_notes, sessions, snapshots, sync, test_watch};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -13,67 +14,165 @@ pub fn run() {
         eprintln!("Failed to initialize database: {}", e);
     }
 
+    // Older rows may have been stored with trailing slashes, `~`, etc.
+    if let Err(e) = projects::normalize_stored_project_paths() {
+        eprintln!("Failed to normalize project paths: {}", e);
+    }
+
+    // Prune and clean up in the background per the retention settings
+    maintenance::run_on_startup();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .setup(|app| {
+            sync::start_auto_sync(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Snapshot commands
             snapshots::create_snapshot,
             snapshots::list_snapshots,
             snapshots::restore_snapshot,
+            snapshots::restore_snapshot_with_progress,
+            operations::cancel_operation,
             snapshots::get_snapshot_diff,
+            snapshots::diff_working_tree,
             snapshots::get_file_at_snapshot,
+            snapshots::claude_md_history,
+            snapshots::restore_file_from_snapshot,
             snapshots::compare_snapshots,
+            snapshots::get_unsaved_work_warning,
+            snapshots::tag_snapshots_in_range,
+            snapshots::rename_snapshot,
+            snapshots::preview_snapshot_contents,
+            snapshots::get_snapshot_diff_by_directory,
+            snapshots::get_churn_stats,
+            snapshots::benchmark_snapshot,
+            snapshots::prune_snapshots,
+            snapshots::export_snapshots_csv,
+            snapshots::export_snapshot,
+            snapshots::get_snapshot_stats,
+            snapshots::get_auto_snapshot_sessions,
+            snapshots::set_auto_snapshot_sessions,
             // Project commands
             projects::list_projects,
             projects::get_current_project,
+            projects::get_project_overview,
             projects::init_project,
+            projects::check_project_path_collision,
+            projects::repair_project_layout,
+            projects::normalize_project_path,
             projects::delete_project,
             projects::scaffold_project,
             projects::list_directory,
+            projects::is_binary_file,
+            projects::get_project_readme,
             projects::read_claude_md,
             projects::write_claude_md,
+            projects::lint_claude_md,
             projects::get_failed_approaches,
             projects::add_failed_approach,
             projects::remove_failed_approach,
             projects::clear_failed_approaches,
+            projects::validate_project_files,
+            health::get_project_health,
+            health::get_health_weights,
+            health::set_health_weights,
             // Session commands
             sessions::create_session,
             sessions::end_session,
+            sessions::update_session_files,
+            sessions::increment_session_retry,
             sessions::list_sessions,
             sessions::get_today_stats,
+            sessions::classify_session,
+            sessions::delete_sessions_before,
+            sessions::archive_sessions_before,
+            sessions::generate_session_summary_md,
+            sessions::get_incomplete_sessions,
+            sessions::resume_session_by_id,
+            sessions::abandon_session,
+            // Session event commands
+            session_events::record_session_event,
+            session_events::get_session_timeline,
+            // Session note commands
+            session_notes::add_session_note,
+            session_notes::get_session_notes,
+            session_notes::delete_session_note,
             // Memory commands
             memory::get_memory,
             memory::set_memory,
             memory::delete_memory,
             // Prompt commands
             prompts::list_prompts,
+            prompts::search_prompts,
             prompts::create_prompt,
             prompts::update_prompt,
             prompts::get_prompt,
             prompts::delete_prompt,
             prompts::use_prompt,
+            prompts::render_prompt,
+            prompts::import_prompts_from_dir,
+            prompts::export_prompts_to_dir,
             // Analytics commands
             analytics::get_daily_stats,
             analytics::get_weekly_stats,
             analytics::get_overall_stats,
             analytics::get_project_stats,
+            analytics::get_outcome_stats,
+            analytics::get_idle_gaps,
+            analytics::get_hourly_stats,
+            analytics::get_streak,
+            // Budget commands
+            budgets::set_project_token_budget,
+            budgets::get_project_token_budget,
             // PTY commands
             pty_commands::pty_spawn,
             pty_commands::pty_write,
             pty_commands::pty_resize,
             pty_commands::pty_kill,
+            pty_commands::pty_list,
+            pty_commands::detect_default_shell,
+            pty_commands::get_pty_startup_commands,
+            pty_commands::set_pty_startup_commands,
+            pty_commands::pty_run_and_capture,
+            pty_commands::pty_cwd_history,
+            pty_commands::get_semantic_block_prefs,
+            pty_commands::set_semantic_block_prefs,
+            pty_commands::replay_session,
+            pty_commands::pause_replay,
+            pty_commands::resume_replay,
+            pty_commands::stop_replay,
+            pty_commands::export_semantic_blocks,
             // Claude Code integration
             claude_code::get_claude_code_stats,
             claude_code::get_claude_code_sessions,
             claude_code::get_claude_code_projects,
+            claude_code::get_real_token_usage,
+            claude_code::claude_session_file_changes,
+            claude_code::get_claude_home_override,
+            claude_code::set_claude_home,
             // Quick commands
             quick_commands::get_package_scripts,
             quick_commands::get_quick_commands,
+            quick_commands::detect_test_command,
+            quick_commands::detect_entry_point,
+            quick_commands::run_tests,
+            test_watch::watch_and_run_tests,
+            test_watch::stop_test_watch,
             // GitHub commands
             github::get_git_status,
+            github::get_branches_status,
+            github::get_git_log,
             github::git_stage_all,
+            github::git_stage_files,
+            github::git_unstage_files,
+            github::get_sensitive_patterns,
+            github::set_sensitive_patterns,
             github::git_commit,
+            github::git_commit_amend,
+            commit_message::build_commit_message,
             github::git_push,
             github::git_pull,
             github::check_gh_cli,
@@ -85,11 +184,30 @@ pub fn run() {
             github::git_init,
             github::create_github_repo,
             github::git_add_remote,
+            github::get_repo_operation_state,
+            github::git_abort_operation,
+            github::git_stash_save,
+            github::git_stash_list,
+            github::git_stash_pop,
+            github::git_tag,
+            github::git_list_tags,
+            github::find_stale_branches,
+            github::git_delete_branch,
+            github::scan_git_history_for_secrets,
+            git_watch::watch_git_status,
+            git_watch::unwatch_git_status,
             // Sync commands
             sync::create_sync_repo,
             sync::connect_sync_repo,
+            sync::connect_sync_remote,
             sync::sync_pull,
             sync::sync_push,
+            sync::sync_resolve,
+            sync::get_auto_sync,
+            sync::set_auto_sync,
+            sync::init_local_sync,
+            sync::list_sync_history,
+            sync::restore_sync_to,
             // Session memory commands
             session_memory::save_session_memory,
             session_memory::get_session_memories,
@@ -98,12 +216,21 @@ pub fn run() {
             // Hooks commands
             hooks::get_hooks_status,
             hooks::install_hooks,
+            hooks::verify_hooks,
             hooks::uninstall_hooks,
             hooks::get_pending_sessions,
             hooks::import_session_summary,
             hooks::clear_pending_session,
             hooks::set_prompt_prefix,
             hooks::get_prompt_prefix,
+            hooks::list_context_cache,
+            hooks::clear_context_cache,
+            hooks::preview_prompt_prefix_effect,
+            // Maintenance commands
+            maintenance::get_maintenance_config,
+            maintenance::set_maintenance_config,
+            maintenance::run_maintenance_now,
+            maintenance::get_last_maintenance,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,125 @@
+use portable_pty::{native_pty_system, CommandBuilder, PtySize};
+use serde::Serialize;
+use std::io::Read;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{detect_default_shell, ShellInfo};
+
+/// How often to check whether the command has exited
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// How long to wait for remaining output after the command exits
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Output of a command run to completion in a throwaway PTY
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedOutput {
+    /// Combined stdout/stderr (a PTY doesn't separate them), with \r\n normalized to \n
+    pub output: String,
+    /// None if the command was killed after timing out
+    pub exit_code: Option<u32>,
+    pub timed_out: bool,
+}
+
+/// Arguments that make the shell run a single command and exit
+fn run_args(shell: &ShellInfo, command: &str) -> Vec<String> {
+    let name = std::path::Path::new(&shell.shell)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match name.as_str() {
+        "pwsh" | "powershell" => vec![
+            "-NoLogo".to_string(),
+            "-NoProfile".to_string(),
+            "-Command".to_string(),
+            command.to_string(),
+        ],
+        "cmd" => vec!["/C".to_string(), command.to_string()],
+        _ => vec!["-c".to_string(), command.to_string()],
+    }
+}
+
+/// Run a command in a fresh PTY and collect its output until it exits or the timeout hits.
+/// Nothing is streamed to the frontend or fed through the parsers.
+pub fn run_and_capture(
+    cwd: Option<&str>,
+    command: &str,
+    timeout: Duration,
+) -> Result<CapturedOutput, String> {
+    let pair = native_pty_system()
+        .openpty(PtySize {
+            rows: 24,
+            cols: 200,
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .map_err(|e| format!("Failed to open PTY: {}", e))?;
+
+    let shell = detect_default_shell();
+    let mut cmd = CommandBuilder::new(&shell.shell);
+    for arg in run_args(&shell, command) {
+        cmd.arg(arg);
+    }
+    if let Some(dir) = cwd {
+        cmd.cwd(dir);
+    }
+
+    let mut child = pair
+        .slave
+        .spawn_command(cmd)
+        .map_err(|e| format!("Failed to spawn command: {}", e))?;
+
+    // Close our end of the slave so the reader sees EOF once the command exits
+    drop(pair.slave);
+
+    let mut reader = pair
+        .master
+        .try_clone_reader()
+        .map_err(|e| format!("Failed to clone reader: {}", e))?;
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => output.extend_from_slice(&buf[..n]),
+            }
+        }
+        let _ = tx.send(output);
+    });
+
+    let started = Instant::now();
+    let (exit_code, timed_out) = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break (Some(status.exit_code()), false),
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break (None, true);
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => return Err(format!("Failed to wait for command: {}", e)),
+        }
+    };
+
+    // Dropping the master unblocks the reader on platforms that don't signal EOF
+    let output = match rx.recv_timeout(DRAIN_TIMEOUT) {
+        Ok(output) => output,
+        Err(_) => {
+            drop(pair.master);
+            rx.recv_timeout(DRAIN_TIMEOUT).unwrap_or_default()
+        }
+    };
+
+    Ok(CapturedOutput {
+        output: String::from_utf8_lossy(&output).replace("\r\n", "\n"),
+        exit_code,
+        timed_out,
+    })
+}
@@ -14,6 +14,10 @@ pub enum ClaudeState {
     ToolUse(String),
     /// Claude is asking a question and waiting for response
     Asking,
+    /// Claude is in plan mode, presenting a plan (possibly awaiting approval)
+    Planning,
+    /// Claude is waiting for permission to proceed (y/n, a numbered menu, or Enter)
+    AwaitingApproval,
     /// An error occurred
     Error,
     /// Task completed successfully
@@ -26,6 +30,14 @@ impl Default for ClaudeState {
     }
 }
 
+/// A choice offered by an approval prompt
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApprovalOption {
+    /// What to send to the terminal to pick this option ("y", "1", "\r", ...)
+    pub key: String,
+    pub label: String,
+}
+
 /// Metadata about the current Claude state
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ClaudeStateInfo {
@@ -38,6 +50,15 @@ pub struct ClaudeStateInfo {
     pub progress: Option<u8>,
     /// Whether Claude is waiting for user input
     pub awaiting_input: bool,
+    /// Whether the pending input is approval of a plan (rather than a general question)
+    pub plan_approval: bool,
+    /// Choices offered by the pending approval prompt (if AwaitingApproval state)
+    #[serde(default)]
+    pub approval_options: Vec<ApprovalOption>,
+    /// Percentage of the context window left before auto-compaction, once Claude
+    /// Code has reported it
+    #[serde(default)]
+    pub context_remaining: Option<u8>,
 }
 
 /// Parser that tracks Claude Code output and determines state
@@ -65,6 +86,9 @@ impl ClaudeStateParser {
     /// Parse new output chunk and return state if changed
     pub fn parse(&mut self, output: &str) -> Option<ClaudeStateInfo> {
         let previous_state = self.current_state.state.clone();
+        let previous_plan_approval = self.current_state.plan_approval;
+        let previous_options = self.current_state.approval_options.clone();
+        let previous_context = self.current_state.context_remaining;
 
         // Add to buffer (keep last 2KB for pattern matching)
         self.buffer.push_str(output);
@@ -75,9 +99,19 @@ impl ClaudeStateParser {
 
         // Detect state from output patterns
         self.detect_state(output);
+        if self.current_state.state != ClaudeState::Planning {
+            self.current_state.plan_approval = false;
+        }
+        if self.current_state.state != ClaudeState::AwaitingApproval {
+            self.current_state.approval_options.clear();
+        }
 
         // Return state info if state changed
-        if self.current_state.state != previous_state {
+        if self.current_state.state != previous_state
+            || self.current_state.plan_approval != previous_plan_approval
+            || self.current_state.approval_options != previous_options
+            || self.current_state.context_remaining != previous_context
+        {
             Some(self.current_state.clone())
         } else {
             None
@@ -109,7 +143,12 @@ impl ClaudeStateParser {
         // Note: Claude Code uses ANSI escape codes, so we check for patterns in the visible text
 
         let clean_output = strip_ansi_codes(output);
-        let clean_buffer = strip_ansi_codes(&self.buffer);
+        let mut clean_buffer = strip_ansi_codes(&self.buffer);
+
+        // Context usage is reported alongside any state; keep the last figure seen
+        if let Some(remaining) = parse_context_remaining(&clean_output) {
+            self.current_state.context_remaining = Some(remaining);
+        }
 
         // Check for tool usage patterns
         if let Some(tool) = self.detect_tool_use(&clean_output) {
@@ -131,6 +170,34 @@ impl ClaudeStateParser {
             return;
         }
 
+        // Check for plan mode (before questions, since plan approval is phrased as one)
+        match self.detect_plan(&clean_output, &clean_buffer) {
+            Some(approval) => {
+                self.current_state.state = ClaudeState::Planning;
+                self.current_state.plan_approval = approval;
+                self.current_state.awaiting_input = approval;
+                return;
+            }
+            // The plan approval menu has been answered; forget it so its
+            // question isn't picked up again below
+            None if self.current_state.plan_approval => {
+                self.current_state.plan_approval = false;
+                self.current_state.awaiting_input = false;
+                self.buffer = output.to_string();
+                clean_buffer = clean_output.clone();
+            }
+            None => {}
+        }
+
+        // Check for approval prompts (before questions, since they usually include one)
+        if let Some((question, options)) = self.detect_approval(&clean_buffer) {
+            self.current_state.state = ClaudeState::AwaitingApproval;
+            self.current_state.question = question;
+            self.current_state.approval_options = options;
+            self.current_state.awaiting_input = true;
+            return;
+        }
+
         // Check for question patterns
         if let Some(question) = self.detect_question(&clean_buffer) {
             self.current_state.state = ClaudeState::Asking;
@@ -207,6 +274,111 @@ impl ClaudeStateParser {
         None
     }
 
+    /// Detect plan mode. Returns `Some(true)` when a plan is awaiting approval,
+    /// `Some(false)` when Claude is in plan mode but not yet asking.
+    fn detect_plan(&self, output: &str, buffer: &str) -> Option<bool> {
+        // Plan approval gate: the plan is shown followed by a proceed/keep-planning menu
+        let recent: Vec<&str> = buffer.lines().rev().take(15).collect();
+        let approval_markers = [
+            "Ready to code?",
+            "Here is Claude's plan",
+            "keep planning",
+        ];
+        // The menu must still be the last thing printed; once answered, more output follows it
+        if recent
+            .iter()
+            .any(|line| approval_markers.iter().any(|m| line.contains(m)))
+            && self.detect_approval(buffer).is_some()
+        {
+            return Some(true);
+        }
+
+        // Plan mode indicator in the status line
+        if output.contains("plan mode on") || output.contains("⏸ plan mode") {
+            return Some(false);
+        }
+
+        None
+    }
+
+    /// Detect a prompt waiting for approval at the end of the output: a y/n prompt,
+    /// a numbered option menu or "Press Enter to continue". Returns the question
+    /// (if one precedes the prompt) and the options offered.
+    fn detect_approval(&self, buffer: &str) -> Option<(Option<String>, Vec<ApprovalOption>)> {
+        // Claude Code draws prompts in boxes; drop the borders and blank lines
+        let lines: Vec<&str> = buffer
+            .lines()
+            .map(|l| l.trim_matches(|c: char| c.is_whitespace() || BOX_CHARS.contains(&c)))
+            .filter(|l| !l.is_empty())
+            .collect();
+        // The prompt must be the last thing printed, or it has already been answered
+        let last = lines.len().checked_sub(1)?;
+
+        let question_before = |end: usize| {
+            lines[..end]
+                .iter()
+                .rev()
+                .take(5)
+                .find(|l| l.ends_with('?'))
+                .map(|l| l.to_string())
+        };
+
+        // Numbered menu, one option per line, possibly followed by a short hint
+        let is_hint = lines[last].to_lowercase().contains("esc")
+            && parse_menu_option(lines[last]).is_none();
+        let menu_end = if is_hint { last.checked_sub(1)? } else { last };
+        let mut menu_start = menu_end + 1;
+        while menu_start > 0 && parse_menu_option(lines[menu_start - 1]).is_some() {
+            menu_start -= 1;
+        }
+        if menu_end + 1 - menu_start >= 2 {
+            let menu = &lines[menu_start..=menu_end];
+            let options: Vec<ApprovalOption> =
+                menu.iter().filter_map(|l| parse_menu_option(l)).collect();
+            let numbered_in_order = options
+                .iter()
+                .enumerate()
+                .all(|(i, o)| o.key == (i + 1).to_string());
+            let has_cursor = menu.iter().any(|l| l.starts_with(MENU_CURSORS));
+            let question = question_before(menu_start);
+            if numbered_in_order && (has_cursor || question.is_some() || is_yes_no_menu(&options)) {
+                return Some((question, options));
+            }
+        }
+
+        let line = lines[last];
+
+        // Menu on a single line: "1. Yes  2. No"
+        let inline = parse_inline_menu(line);
+        if is_yes_no_menu(&inline) {
+            return Some((question_before(last), inline));
+        }
+
+        let lower = line.to_lowercase();
+        if YES_NO_MARKERS.iter().any(|m| lower.contains(m)) {
+            let question = if line.contains('?') {
+                Some(line.to_string())
+            } else {
+                question_before(last)
+            };
+            let options = vec![
+                ApprovalOption { key: "y".to_string(), label: "Yes".to_string() },
+                ApprovalOption { key: "n".to_string(), label: "No".to_string() },
+            ];
+            return Some((question, options));
+        }
+
+        if lower.contains("press enter to continue") {
+            let options = vec![ApprovalOption {
+                key: "\r".to_string(),
+                label: "Continue".to_string(),
+            }];
+            return Some((question_before(last), options));
+        }
+
+        None
+    }
+
     fn detect_question(&self, buffer: &str) -> Option<String> {
         // Look for question patterns in recent output
         let lines: Vec<&str> = buffer.lines().rev().take(10).collect();
@@ -241,6 +413,117 @@ impl ClaudeStateParser {
     }
 }
 
+/// Box-drawing characters Claude Code uses to frame prompts
+const BOX_CHARS: &[char] = &['│', '╭', '╮', '╰', '╯', '─', '┃', '┌', '┐', '└', '┘'];
+
+/// Markers of the selected entry in a menu
+const MENU_CURSORS: &[char] = &['❯', '›', '>'];
+
+/// Inline yes/no prompt markers (lowercased)
+const YES_NO_MARKERS: &[&str] = &["(y/n)", "[y/n]", "(yes/no)", "[yes/no]"];
+
+/// Parse a menu line like "❯ 1. Yes" or "2) No, and tell Claude what to do differently"
+fn parse_menu_option(line: &str) -> Option<ApprovalOption> {
+    let line = line.trim_start_matches(MENU_CURSORS).trim_start();
+    let digits: String = line.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() || digits.len() > 2 {
+        return None;
+    }
+    let rest = line[digits.len()..].strip_prefix(['.', ')'])?;
+    if !rest.starts_with(' ') {
+        return None;
+    }
+    let label = rest.trim();
+    if label.is_empty() {
+        return None;
+    }
+    Some(ApprovalOption {
+        key: digits,
+        label: label.to_string(),
+    })
+}
+
+/// Parse several options on one line, separated by two or more spaces: "1. Yes  2. No"
+fn parse_inline_menu(line: &str) -> Vec<ApprovalOption> {
+    let options: Vec<ApprovalOption> = line
+        .split("  ")
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(parse_menu_option)
+        .collect::<Option<Vec<_>>>()
+        .unwrap_or_default();
+    if options.len() >= 2 && options.iter().enumerate().all(|(i, o)| o.key == (i + 1).to_string()) {
+        options
+    } else {
+        Vec::new()
+    }
+}
+
+/// Whether a menu's options are a yes/no choice
+fn is_yes_no_menu(options: &[ApprovalOption]) -> bool {
+    options.len() >= 2
+        && options.first().is_some_and(|o| o.label.starts_with("Yes"))
+        && options.iter().any(|o| o.label.starts_with("No"))
+}
+
+/// Extract the percentage of the context window left from a status line, e.g.
+/// "Context left until auto-compact: 12%", "Context used: 58%" or
+/// "150k/200k tokens". Returns None if no figure is present.
+fn parse_context_remaining(output: &str) -> Option<u8> {
+    for line in output.lines().rev() {
+        let lower = line.to_lowercase();
+        let mentions_context = lower.contains("context");
+
+        if mentions_context {
+            if let Some(percent) = number_before(&lower, '%') {
+                let percent = percent.clamp(0.0, 100.0);
+                let remaining = if lower.contains("used") { 100.0 - percent } else { percent };
+                return Some(remaining.round() as u8);
+            }
+        }
+
+        if mentions_context || lower.contains("tokens") {
+            if let Some((used, total)) = token_ratio(&lower) {
+                let remaining = (1.0 - used / total).clamp(0.0, 1.0) * 100.0;
+                return Some(remaining.round() as u8);
+            }
+        }
+    }
+    None
+}
+
+/// The number (which may have a decimal point) directly before `marker`
+fn number_before(text: &str, marker: char) -> Option<f64> {
+    let end = text.find(marker)?;
+    let before = text[..end].trim_end();
+    let start = before
+        .rfind(|c: char| !c.is_ascii_digit() && c != '.')
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    before[start..].parse().ok()
+}
+
+/// Parse a token count like "42k", "1.2m" or "15000"
+fn parse_token_count(text: &str) -> Option<f64> {
+    let text = text.trim().trim_end_matches(',');
+    let (number, multiplier) = match text.chars().last()? {
+        'k' => (&text[..text.len() - 1], 1_000.0),
+        'm' => (&text[..text.len() - 1], 1_000_000.0),
+        _ => (text, 1.0),
+    };
+    number.replace(',', "").parse::<f64>().ok().map(|n| n * multiplier)
+}
+
+/// Used and total tokens from "<used>/<total>" (e.g. "150k/200k tokens")
+fn token_ratio(text: &str) -> Option<(f64, f64)> {
+    let slash = text.find('/')?;
+    let used = text[..slash].split_whitespace().last()?;
+    let total = text[slash + 1..].split_whitespace().next()?;
+    let used = parse_token_count(used)?;
+    let total = parse_token_count(total)?;
+    (total > 0.0).then_some((used, total))
+}
+
 /// Strip ANSI escape codes from string
 fn strip_ansi_codes(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
@@ -292,4 +575,148 @@ mod tests {
         assert!(state.is_some());
         assert_eq!(state.unwrap().state, ClaudeState::ToolUse("Read".to_string()));
     }
+
+    #[test]
+    fn test_detect_plan_mode() {
+        let mut parser = ClaudeStateParser::new();
+        let state = parser
+            .parse("I'll look into the codebase first.\n  ⏸ plan mode on (shift+tab to cycle)")
+            .unwrap();
+        assert_eq!(state.state, ClaudeState::Planning);
+        assert!(!state.plan_approval);
+        assert!(!state.awaiting_input);
+    }
+
+    #[test]
+    fn test_detect_plan_approval() {
+        let mut parser = ClaudeStateParser::new();
+        parser.parse("⏸ plan mode on (shift+tab to cycle)");
+        let state = parser
+            .parse(
+                "Ready to code?\n\nHere is Claude's plan:\n  1. Add the parser\n\n\
+                 Would you like to proceed?\n❯ 1. Yes, and auto-accept edits\n  \
+                 2. Yes, and manually approve edits\n  3. No, keep planning\n",
+            )
+            .unwrap();
+        assert_eq!(state.state, ClaudeState::Planning);
+        assert!(state.plan_approval);
+        assert!(state.awaiting_input);
+    }
+
+    #[test]
+    fn test_answered_plan_approval_is_cleared() {
+        let mut parser = ClaudeStateParser::new();
+        parser.parse("⏸ plan mode on (shift+tab to cycle)");
+        parser.parse(
+            "Here is Claude's plan:\n  1. Add the parser\n\n\
+             Would you like to proceed?\n❯ 1. Yes, and auto-accept edits\n  \
+             2. Yes, and manually approve edits\n  3. No, keep planning\n",
+        );
+        assert!(parser.get_state().plan_approval);
+
+        parser.parse("1\nI'll start by adding the parser module.\n");
+        let state = parser.get_state();
+        assert!(!state.plan_approval);
+        assert!(!state.awaiting_input);
+    }
+
+    #[test]
+    fn test_detect_permission_menu() {
+        let mut parser = ClaudeStateParser::new();
+        let state = parser
+            .parse(
+                "╭──────────────────────────────────────╮\n\
+                 │ Bash command                         │\n\
+                 │   npm test                           │\n\
+                 │ Do you want to proceed?              │\n\
+                 │ ❯ 1. Yes                             │\n\
+                 │   2. Yes, and don't ask again        │\n\
+                 │   3. No, and tell Claude what to do differently (esc) │\n\
+                 ╰──────────────────────────────────────╯\n",
+            )
+            .unwrap();
+        assert_eq!(state.state, ClaudeState::AwaitingApproval);
+        assert!(state.awaiting_input);
+        assert_eq!(state.question.as_deref(), Some("Do you want to proceed?"));
+        let keys: Vec<&str> = state.approval_options.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, ["1", "2", "3"]);
+        assert_eq!(state.approval_options[1].label, "Yes, and don't ask again");
+    }
+
+    #[test]
+    fn test_detect_inline_menu() {
+        let mut parser = ClaudeStateParser::new();
+        let state = parser.parse("Do you want to make this edit to main.rs?\n1. Yes  2. No\n").unwrap();
+        assert_eq!(state.state, ClaudeState::AwaitingApproval);
+        assert_eq!(state.approval_options.len(), 2);
+        assert_eq!(state.approval_options[1].label, "No");
+    }
+
+    #[test]
+    fn test_detect_yes_no_prompt() {
+        let mut parser = ClaudeStateParser::new();
+        let state = parser.parse("Do you want to proceed? (y/n) ").unwrap();
+        assert_eq!(state.state, ClaudeState::AwaitingApproval);
+        assert_eq!(state.question.as_deref(), Some("Do you want to proceed? (y/n)"));
+        let keys: Vec<&str> = state.approval_options.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, ["y", "n"]);
+    }
+
+    #[test]
+    fn test_detect_press_enter() {
+        let mut parser = ClaudeStateParser::new();
+        let state = parser.parse("Login successful.\nPress Enter to continue…\n").unwrap();
+        assert_eq!(state.state, ClaudeState::AwaitingApproval);
+        assert_eq!(state.approval_options.len(), 1);
+        assert_eq!(state.approval_options[0].key, "\r");
+    }
+
+    #[test]
+    fn test_numbered_list_is_not_approval() {
+        let mut parser = ClaudeStateParser::new();
+        parser.parse("Steps:\n1. Add the parser\n2. Wire it up\n");
+        assert_ne!(parser.get_state().state, ClaudeState::AwaitingApproval);
+    }
+
+    #[test]
+    fn test_answered_prompt_is_not_approval() {
+        let mut parser = ClaudeStateParser::new();
+        parser.parse("Do you want to proceed? (y/n) ");
+        let state = parser.parse("y\nRunning the migration now and checking the output for problems\n").unwrap();
+        assert_ne!(state.state, ClaudeState::AwaitingApproval);
+        assert!(state.approval_options.is_empty());
+    }
+
+    #[test]
+    fn test_context_remaining() {
+        assert_eq!(parse_context_remaining("Context left until auto-compact: 12%"), Some(12));
+        assert_eq!(parse_context_remaining("  Context left: 42%\n"), Some(42));
+        assert_eq!(parse_context_remaining("Context used: 58%"), Some(42));
+        assert_eq!(parse_context_remaining("Context: 150k/200k tokens"), Some(25));
+        assert_eq!(parse_context_remaining("Wrote 3 files (100%)"), None);
+        assert_eq!(parse_context_remaining("nothing to see"), None);
+    }
+
+    #[test]
+    fn test_context_remaining_is_emitted_and_kept() {
+        let mut parser = ClaudeStateParser::new();
+        let state = parser.parse("⠋ Thinking... Context left until auto-compact: 30%").unwrap();
+        assert_eq!(state.context_remaining, Some(30));
+
+        // A later state change without a figure keeps the last one
+        let state = parser.parse("Read(/src/main.rs)").unwrap();
+        assert_eq!(state.context_remaining, Some(30));
+
+        // A new figure alone is reported even if the state is unchanged
+        let state = parser.parse("Read(/src/lib.rs) Context left until auto-compact: 25%").unwrap();
+        assert_eq!(state.context_remaining, Some(25));
+    }
+
+    #[test]
+    fn test_general_question_is_not_plan_approval() {
+        let mut parser = ClaudeStateParser::new();
+        let state = parser.parse("Would you like me to add tests as well?").unwrap();
+        assert_eq!(state.state, ClaudeState::Asking);
+        assert!(!state.plan_approval);
+    }
 }
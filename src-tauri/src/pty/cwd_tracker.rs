@@ -0,0 +1,153 @@
+/// Maximum number of directories kept per PTY
+const MAX_HISTORY: usize = 50;
+
+/// Longest partial escape sequence kept between output chunks
+const MAX_PENDING: usize = 4096;
+
+/// Tracks a PTY's working directory from OSC 7 reports
+/// (`ESC ] 7 ; file://host/path BEL`), which shells emit with shell integration enabled.
+/// Shells that don't emit OSC 7 just leave the history at the starting directory.
+pub struct CwdTracker {
+    /// Directories, oldest first, without consecutive duplicates
+    history: Vec<String>,
+    /// Unterminated OSC 7 sequence carried over from the previous chunk
+    pending: String,
+}
+
+impl CwdTracker {
+    /// Continue a saved history (oldest first), starting in `initial`
+    pub fn with_history(saved: Vec<String>, initial: Option<String>) -> Self {
+        let mut tracker = Self {
+            history: Vec::new(),
+            pending: String::new(),
+        };
+        for dir in saved.into_iter().chain(initial) {
+            tracker.push(dir);
+        }
+        tracker
+    }
+
+    /// Scan a chunk of output; returns true if the working directory changed
+    pub fn parse(&mut self, output: &str) -> bool {
+        let mut text = std::mem::take(&mut self.pending);
+        text.push_str(output);
+
+        let mut changed = false;
+        let mut rest = text.as_str();
+
+        while let Some(start) = rest.find("\x1b]7;") {
+            let after = &rest[start + 4..];
+            let terminator = after
+                .find('\x07')
+                .map(|i| (i, 1))
+                .into_iter()
+                .chain(after.find("\x1b\\").map(|i| (i, 2)))
+                .min_by_key(|(i, _)| *i);
+
+            let Some((end, len)) = terminator else {
+                // Sequence continues in the next chunk
+                if rest.len() - start <= MAX_PENDING {
+                    self.pending = rest[start..].to_string();
+                }
+                break;
+            };
+
+            if let Some(dir) = parse_file_url(&after[..end]) {
+                changed |= self.push(dir);
+            }
+            rest = &after[end + len..];
+        }
+
+        changed
+    }
+
+    fn push(&mut self, dir: String) -> bool {
+        if self.history.last() == Some(&dir) {
+            return false;
+        }
+        self.history.push(dir);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        true
+    }
+
+    /// All tracked directories, oldest first
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Recent directories, most recent first, each listed once
+    pub fn recent(&self) -> Vec<String> {
+        let mut recent: Vec<String> = Vec::new();
+        for dir in self.history.iter().rev() {
+            if !recent.contains(dir) {
+                recent.push(dir.clone());
+            }
+        }
+        recent
+    }
+}
+
+/// Extract the path from a `file://host/path` URL, decoding %XX escapes
+fn parse_file_url(url: &str) -> Option<String> {
+    let without_scheme = url.strip_prefix("file://")?;
+    let path = &without_scheme[without_scheme.find('/')?..];
+
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Some(byte) = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    let decoded = String::from_utf8_lossy(&decoded).to_string();
+
+    // Windows paths arrive as /C:/Users/...
+    if decoded.len() > 2 && decoded.as_bytes()[2] == b':' {
+        return Some(decoded[1..].to_string());
+    }
+
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc7_across_chunks() {
+        let mut tracker = CwdTracker::with_history(Vec::new(), Some("/home/me".to_string()));
+        assert!(!tracker.parse("prompt$ cd src\r\n\x1b]7;file://host/home/me/my%20"));
+        assert!(tracker.parse("src\x07prompt$ "));
+        assert_eq!(tracker.recent(), vec!["/home/me/my src", "/home/me"]);
+    }
+
+    #[test]
+    fn test_recent_dedupes() {
+        let mut tracker = CwdTracker::with_history(Vec::new(), None);
+        tracker.parse("\x1b]7;file://h/a\x1b\\\x1b]7;file://h/b\x07\x1b]7;file://h/a\x07");
+        assert_eq!(tracker.recent(), vec!["/a", "/b"]);
+    }
+
+    #[test]
+    fn test_with_history_continues_saved_dirs() {
+        let saved = vec!["/a".to_string(), "/b".to_string()];
+        let mut tracker = CwdTracker::with_history(saved, Some("/b".to_string()));
+        assert_eq!(tracker.history(), ["/a", "/b"]);
+
+        tracker.parse("\x1b]7;file://h/c\x07");
+        assert_eq!(tracker.recent(), vec!["/c", "/b", "/a"]);
+    }
+}
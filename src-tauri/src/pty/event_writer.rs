@@ -0,0 +1,99 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::commands::{session_events, sessions};
+use crate::db;
+
+/// How long a looked up active session is trusted before checking again
+const SESSION_LOOKUP_TTL: Duration = Duration::from_secs(5);
+
+/// A write against the active session of a PTY's project
+enum SessionWrite {
+    Event {
+        kind: String,
+        data: serde_json::Value,
+    },
+    Retry,
+}
+
+/// Records session timeline events for a PTY on a background thread, so the
+/// PTY reader never waits on the database
+pub struct SessionEventWriter {
+    tx: Option<Sender<SessionWrite>>,
+}
+
+impl SessionEventWriter {
+    /// Start a writer for the PTY's project; a PTY without one records nothing
+    pub fn new(project_path: Option<String>) -> Self {
+        let tx = project_path.map(|project_path| {
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || write_events(&project_path, rx));
+            tx
+        });
+        Self { tx }
+    }
+
+    /// Queue an event for the active session, if any
+    pub fn event(&self, kind: &str, data: serde_json::Value) {
+        self.send(SessionWrite::Event {
+            kind: kind.to_string(),
+            data,
+        });
+    }
+
+    /// Queue a retry of the active session, if any
+    pub fn retry(&self) {
+        self.send(SessionWrite::Retry);
+    }
+
+    fn send(&self, write: SessionWrite) {
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(write);
+        }
+    }
+}
+
+/// Apply queued writes until the PTY's writer is dropped, reusing one
+/// connection and re-checking the active session at most every few seconds
+fn write_events(project_path: &str, rx: Receiver<SessionWrite>) {
+    let mut conn: Option<rusqlite::Connection> = None;
+    let mut session_id: Option<String> = None;
+    let mut last_lookup: Option<Instant> = None;
+
+    for write in rx {
+        if conn.is_none() {
+            match db::get_connection() {
+                Ok(c) => conn = Some(c),
+                Err(e) => {
+                    eprintln!("Failed to open database for session events: {}", e);
+                    continue;
+                }
+            }
+        }
+        let Some(conn) = conn.as_ref() else {
+            continue;
+        };
+
+        if last_lookup
+            .map(|t| t.elapsed() >= SESSION_LOOKUP_TTL)
+            .unwrap_or(true)
+        {
+            session_id = session_events::find_active_session(conn, project_path);
+            last_lookup = Some(Instant::now());
+        }
+        let Some(session_id) = session_id.as_deref() else {
+            continue;
+        };
+
+        let result = match write {
+            SessionWrite::Event { kind, data } => {
+                session_events::insert_event(conn, session_id, &kind, &data).map(|_| ())
+            }
+            SessionWrite::Retry => sessions::increment_retry(conn, session_id).map(|_| ()),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to record session event: {}", e);
+        }
+    }
+}
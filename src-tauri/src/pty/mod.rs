@@ -1,15 +1,28 @@
+pub mod capture;
 pub mod claude_parser;
+pub mod cwd_tracker;
+pub mod event_writer;
+pub mod recorder;
+pub mod replay;
 pub mod semantic_parser;
+pub mod utf8_decoder;
 
-use portable_pty::{native_pty_system, CommandBuilder, PtySize, MasterPty, Child};
+use portable_pty::{native_pty_system, CommandBuilder, ExitStatus, PtySize, MasterPty, Child};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 
-use claude_parser::ClaudeStateParser;
-use semantic_parser::SemanticBlockParser;
+use crate::config;
+use claude_parser::{ClaudeState, ClaudeStateInfo, ClaudeStateParser};
+use cwd_tracker::CwdTracker;
+use event_writer::SessionEventWriter;
+use recorder::SessionRecorder;
+use semantic_parser::{BlockType, SemanticBlock, SemanticBlockParser};
+use utf8_decoder::Utf8Decoder;
 
 /// Check if an executable exists in PATH (Windows only)
 #[cfg(windows)]
@@ -21,6 +34,229 @@ fn which_exists(cmd: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// The shell a new PTY will launch
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellInfo {
+    pub shell: String,
+    pub args: Vec<String>,
+}
+
+/// Determine the shell to launch for the current platform
+pub fn detect_default_shell() -> ShellInfo {
+    #[cfg(windows)]
+    let (shell, args): (String, Vec<&str>) = {
+        // On Windows, prefer PowerShell 7 (pwsh) > Windows PowerShell > cmd
+        if which_exists("pwsh") {
+            ("pwsh".to_string(), vec!["-NoLogo"])
+        } else if which_exists("powershell") {
+            ("powershell".to_string(), vec!["-NoLogo"])
+        } else {
+            (std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string()), vec![])
+        }
+    };
+
+    #[cfg(not(windows))]
+    let (shell, args): (String, Vec<&str>) = {
+        // On Unix, use $SHELL or fall back to bash
+        let sh = std::env::var("SHELL")
+            .unwrap_or_else(|_| "/bin/bash".to_string());
+        (sh, vec!["-l"]) // -l for login shell
+    };
+
+    ShellInfo {
+        shell,
+        args: args.into_iter().map(String::from).collect(),
+    }
+}
+
+/// Find an executable by path, or by name on PATH (trying PATHEXT extensions on Windows)
+fn find_executable(cmd: &str) -> Option<std::path::PathBuf> {
+    let path = std::path::Path::new(cmd);
+    if path.components().count() > 1 || path.is_absolute() {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+
+    #[cfg(windows)]
+    let extensions: Vec<String> = std::iter::once(String::new())
+        .chain(
+            std::env::var("PATHEXT")
+                .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+                .split(';')
+                .map(|e| e.to_string()),
+        )
+        .collect();
+    #[cfg(not(windows))]
+    let extensions = [String::new()];
+
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths).find_map(|dir| {
+        extensions
+            .iter()
+            .map(|ext| dir.join(format!("{}{}", cmd, ext)))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+/// The shell to launch: `shell` and `args` if given (checking the executable exists),
+/// otherwise the platform default. Args alone apply to the default shell.
+pub fn resolve_shell(shell: Option<String>, args: Option<Vec<String>>) -> Result<ShellInfo, String> {
+    let shell = shell.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+
+    let Some(shell) = shell else {
+        let default = detect_default_shell();
+        return Ok(ShellInfo {
+            args: args.unwrap_or(default.args),
+            shell: default.shell,
+        });
+    };
+
+    if find_executable(&shell).is_none() {
+        return Err(format!("Shell not found: {}", shell));
+    }
+
+    Ok(ShellInfo {
+        shell,
+        args: args.unwrap_or_default(),
+    })
+}
+
+/// A live PTY as seen by `pty_list`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyStatus {
+    pub id: String,
+    /// Whether the shell process is still running
+    pub running: bool,
+    /// Exit code, once the shell has exited
+    pub exit_code: Option<u32>,
+}
+
+/// Payload of the `pty-exit-<id>` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyExit {
+    /// Exit code, if the shell exited normally
+    pub code: Option<i32>,
+    /// Signal number, if the shell was killed by a signal (Unix only)
+    pub signal: Option<i32>,
+}
+
+impl PtyExit {
+    fn from_status(status: &ExitStatus) -> Self {
+        // portable-pty only exposes the signal through its description
+        let description = status.to_string();
+        match description.strip_prefix("Terminated by ") {
+            Some(name) => Self {
+                code: None,
+                signal: signal_number(name),
+            },
+            None => Self {
+                code: Some(status.exit_code() as i32),
+                signal: None,
+            },
+        }
+    }
+}
+
+/// Map a `strsignal` description back to its number. glibc gives just the
+/// name ("Killed"), macOS appends the number ("Killed: 9").
+fn signal_number(name: &str) -> Option<i32> {
+    if let Some((_, number)) = name.rsplit_once(": ") {
+        if let Ok(n) = number.trim().parse() {
+            return Some(n);
+        }
+    }
+    if let Some(number) = name.strip_prefix("Signal ") {
+        return number.trim().parse().ok();
+    }
+
+    const SIGNALS: &[(&str, i32)] = &[
+        ("Hangup", 1),
+        ("Interrupt", 2),
+        ("Quit", 3),
+        ("Illegal instruction", 4),
+        ("Trace/breakpoint trap", 5),
+        ("Aborted", 6),
+        ("Floating point exception", 8),
+        ("Killed", 9),
+        ("Segmentation fault", 11),
+        ("Broken pipe", 13),
+        ("Alarm clock", 14),
+        ("Terminated", 15),
+    ];
+    SIGNALS
+        .iter()
+        .find(|(description, _)| *description == name)
+        .map(|(_, n)| *n)
+}
+
+/// How long to wait for the shell to be reaped after its output closes
+const EXIT_WAIT_ATTEMPTS: u32 = 100;
+const EXIT_WAIT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Wait for a PTY's shell to exit, polling so only this PTY's child is locked
+/// and `pty_list`/`pty_kill` are never blocked on it
+fn wait_for_exit(child: &Mutex<Box<dyn Child + Send + Sync>>) -> PtyExit {
+    for _ in 0..EXIT_WAIT_ATTEMPTS {
+        let status = match child.lock() {
+            Ok(mut child) => child.try_wait(),
+            Err(_) => break,
+        };
+        match status {
+            Ok(Some(status)) => return PtyExit::from_status(&status),
+            Ok(None) => thread::sleep(EXIT_WAIT_INTERVAL),
+            Err(_) => break,
+        }
+    }
+
+    PtyExit {
+        code: None,
+        signal: None,
+    }
+}
+
+/// How to start a new PTY
+#[derive(Debug, Clone, Default)]
+pub struct SpawnOptions {
+    pub cwd: Option<String>,
+    /// Shell and arguments to launch; the platform default if None
+    pub shell: Option<ShellInfo>,
+    /// Commands typed into the shell right after it starts
+    pub startup_commands: Vec<String>,
+    /// Extra environment variables, on top of the inherited environment.
+    /// Only passed to the child process; never stored.
+    pub env: HashMap<String, String>,
+}
+
+/// Turn a Claude state change into a session timeline event
+fn record_state_event(events: &SessionEventWriter, info: &ClaudeStateInfo) {
+    match &info.state {
+        ClaudeState::ToolUse(tool) => {
+            events.event("tool_used", serde_json::json!({ "tool": tool }));
+        }
+        state => {
+            if *state == ClaudeState::Error {
+                events.retry();
+            }
+            events.event("claude_state", serde_json::json!({ "state": state }));
+        }
+    }
+}
+
+/// Turn noteworthy semantic blocks (errors, commands) into session timeline events
+fn record_block_event(events: &SessionEventWriter, block: &SemanticBlock) {
+    match &block.block_type {
+        BlockType::Error => {
+            events.event("error", serde_json::json!({ "message": block.content }));
+        }
+        BlockType::Command { cmd } => {
+            events.event("command_run", serde_json::json!({ "command": cmd }));
+        }
+        _ => {}
+    }
+}
+
 /// Manages PTY instances
 pub struct PtyManager {
     ptys: HashMap<String, PtyInstance>,
@@ -28,8 +264,9 @@ pub struct PtyManager {
 
 struct PtyInstance {
     master: Box<dyn MasterPty + Send>,
-    child: Box<dyn Child + Send + Sync>,
+    child: Arc<Mutex<Box<dyn Child + Send + Sync>>>,
     writer: Box<dyn Write + Send>,
+    cwd_tracker: Arc<Mutex<CwdTracker>>,
 }
 
 impl PtyManager {
@@ -46,8 +283,15 @@ impl PtyManager {
         app_handle: AppHandle,
         cols: u16,
         rows: u16,
-        cwd: Option<String>,
+        options: SpawnOptions,
     ) -> Result<(), String> {
+        let SpawnOptions {
+            cwd,
+            shell,
+            startup_commands,
+            env,
+        } = options;
+
         let pty_system = native_pty_system();
 
         let pair = pty_system
@@ -59,48 +303,47 @@ impl PtyManager {
             })
             .map_err(|e| format!("Failed to open PTY: {}", e))?;
 
-        // Determine shell based on platform
-        #[cfg(windows)]
-        let (shell, shell_args): (String, Vec<&str>) = {
-            // On Windows, prefer PowerShell 7 (pwsh) > Windows PowerShell > cmd
-            if which_exists("pwsh") {
-                ("pwsh".to_string(), vec!["-NoLogo"])
-            } else if which_exists("powershell") {
-                ("powershell".to_string(), vec!["-NoLogo"])
-            } else {
-                (std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string()), vec![])
-            }
-        };
-
-        #[cfg(not(windows))]
-        let (shell, shell_args): (String, Vec<&str>) = {
-            // On Unix, use $SHELL or fall back to bash
-            let sh = std::env::var("SHELL")
-                .unwrap_or_else(|_| "/bin/bash".to_string());
-            (sh, vec!["-l"]) // -l for login shell
-        };
+        let shell = shell.unwrap_or_else(detect_default_shell);
 
-        let mut cmd = CommandBuilder::new(&shell);
-        for arg in shell_args {
+        let mut cmd = CommandBuilder::new(&shell.shell);
+        for arg in &shell.args {
             cmd.arg(arg);
         }
 
         // Set working directory if provided
-        if let Some(dir) = cwd {
-            cmd.cwd(&dir);
+        if let Some(dir) = &cwd {
+            cmd.cwd(dir);
+        }
+
+        // Added to (and overriding) the environment inherited from Lumen
+        for (key, value) in &env {
+            cmd.env(key, value);
         }
 
         let child = pair
             .slave
             .spawn_command(cmd)
             .map_err(|e| format!("Failed to spawn shell: {}", e))?;
+        let child = Arc::new(Mutex::new(child));
 
         // Get writer for stdin
-        let writer = pair
+        let mut writer = pair
             .master
             .take_writer()
             .map_err(|e| format!("Failed to get writer: {}", e))?;
 
+        // Type the startup commands; the shell reads them once it's ready
+        if !startup_commands.is_empty() {
+            for command in &startup_commands {
+                writer
+                    .write_all(format!("{}\r", command).as_bytes())
+                    .map_err(|e| format!("Failed to write startup command: {}", e))?;
+            }
+            writer
+                .flush()
+                .map_err(|e| format!("Failed to flush: {}", e))?;
+        }
+
         // Clone for reader thread
         let mut reader = pair
             .master
@@ -109,27 +352,64 @@ impl PtyManager {
 
         let pty_id = id.clone();
         let app = app_handle.clone();
+        // Pick up where the project's active session left off, if it has a saved history
+        let saved_cwds = cwd.as_deref().map(recorder::load_cwd_history).unwrap_or_default();
+        let cwd_tracker = Arc::new(Mutex::new(CwdTracker::with_history(
+            saved_cwds,
+            cwd.clone(),
+        )));
+        let reader_cwd_tracker = cwd_tracker.clone();
+        let reader_child = child.clone();
+        let project_path = cwd;
+        let block_prefs = config::load_config()
+            .map(|c| c.semantic_blocks)
+            .unwrap_or_default();
 
         // Spawn thread to read PTY output and emit to frontend
         thread::spawn(move || {
             let mut buf = [0u8; 4096];
+            let mut decoder = Utf8Decoder::new();
             let mut claude_parser = ClaudeStateParser::new();
-            let mut semantic_parser = SemanticBlockParser::new();
+            let mut last_recorded_state: Option<ClaudeState> = None;
+            let mut semantic_parser = SemanticBlockParser::new(block_prefs);
+            let mut recorder = SessionRecorder::new(project_path.clone());
+            let events = SessionEventWriter::new(project_path);
 
             loop {
                 match reader.read(&mut buf) {
                     Ok(0) => break, // EOF
                     Ok(n) => {
-                        let data = String::from_utf8_lossy(&buf[..n]).to_string();
+                        // Characters split across reads are held until complete
+                        let data = decoder.decode(&buf[..n]);
+                        if data.is_empty() {
+                            continue;
+                        }
+
+                        // Record output with timestamps for later replay
+                        recorder.record(&data);
+
+                        // Track directory changes reported by the shell
+                        if let Ok(mut tracker) = reader_cwd_tracker.lock() {
+                            if tracker.parse(&data) {
+                                recorder.record_cwd_history(tracker.history());
+                            }
+                        }
 
                         // Parse for Claude state changes
                         if let Some(state_info) = claude_parser.parse(&data) {
+                            // Only state transitions go on the timeline, not context updates
+                            if last_recorded_state.as_ref() != Some(&state_info.state) {
+                                record_state_event(&events, &state_info);
+                                last_recorded_state = Some(state_info.state.clone());
+                            }
                             let _ = app.emit(&format!("claude-state-{}", pty_id), &state_info);
                         }
 
                         // Parse for semantic blocks
                         let blocks = semantic_parser.parse(&data);
                         for block in blocks {
+                            record_block_event(&events, &block);
+                            recorder.record_block(&block);
                             let _ = app.emit(&format!("semantic-block-{}", pty_id), &block);
                         }
 
@@ -140,13 +420,24 @@ impl PtyManager {
                 }
             }
 
+            // Emit any bytes of an incomplete character left at EOF
+            let rest = decoder.finish();
+            if !rest.is_empty() {
+                recorder.record(&rest);
+                let _ = app.emit(&format!("pty-output-{}", pty_id), rest);
+            }
+
             // Flush any remaining semantic blocks
             if let Some(block) = semantic_parser.flush() {
+                record_block_event(&events, &block);
+                recorder.record_block(&block);
                 let _ = app.emit(&format!("semantic-block-{}", pty_id), &block);
             }
+            recorder.flush_blocks();
 
-            // Notify that PTY has closed
-            let _ = app.emit(&format!("pty-exit-{}", pty_id), ());
+            // Notify that PTY has closed, with how the shell exited
+            let exit = wait_for_exit(&reader_child);
+            let _ = app.emit(&format!("pty-exit-{}", pty_id), &exit);
         });
 
         self.ptys.insert(
@@ -155,6 +446,7 @@ impl PtyManager {
                 master: pair.master,
                 child,
                 writer,
+                cwd_tracker,
             },
         );
 
@@ -198,10 +490,46 @@ impl PtyManager {
         Ok(())
     }
 
+    /// Ids of the PTYs that haven't been killed, sorted
+    pub fn list(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.ptys.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Each PTY and whether its shell process is still running
+    pub fn statuses(&self) -> Vec<PtyStatus> {
+        self.list()
+            .into_iter()
+            .filter_map(|id| {
+                let pty = self.ptys.get(&id)?;
+                let exit = pty.child.lock().ok()?.try_wait().ok().flatten();
+                Some(PtyStatus {
+                    id,
+                    running: exit.is_none(),
+                    exit_code: exit.map(|status| status.exit_code()),
+                })
+            })
+            .collect()
+    }
+
+    /// Recent working directories of a PTY, most recent first
+    pub fn cwd_history(&self, id: &str) -> Result<Vec<String>, String> {
+        let pty = self
+            .ptys
+            .get(id)
+            .ok_or_else(|| "PTY not found".to_string())?;
+
+        let tracker = pty.cwd_tracker.lock().map_err(|e| e.to_string())?;
+        Ok(tracker.recent())
+    }
+
     /// Kill PTY process
     pub fn kill(&mut self, id: &str) -> Result<(), String> {
-        if let Some(mut pty) = self.ptys.remove(id) {
-            let _ = pty.child.kill();
+        if let Some(pty) = self.ptys.remove(id) {
+            if let Ok(mut child) = pty.child.lock() {
+                let _ = child.kill();
+            }
         }
         Ok(())
     }
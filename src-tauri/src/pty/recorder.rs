@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::semantic_parser::SemanticBlock;
+use crate::commands::session_events;
+
+/// How often to re-check which session (if any) is active for the PTY's project
+const SESSION_LOOKUP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Minimum time between rewrites of a session's semantic block file
+const BLOCKS_WRITE_INTERVAL: Duration = Duration::from_secs(2);
+
+lazy_static::lazy_static! {
+    /// Serializes block log merges, so PTYs sharing a session don't drop each other's blocks
+    static ref BLOCKS_WRITE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// A single chunk of PTY output with the time it was read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedChunk {
+    /// Unix timestamp in milliseconds
+    pub t: u64,
+    pub data: String,
+}
+
+/// Get the path of the output recording for a session
+pub fn recording_path(project_path: &Path, session_id: &str) -> PathBuf {
+    project_path
+        .join(".lumen")
+        .join("sessions")
+        .join(format!("{}.pty.jsonl", session_id))
+}
+
+/// Get the path of the semantic block log for a session
+pub fn blocks_path(project_path: &Path, session_id: &str) -> PathBuf {
+    project_path
+        .join(".lumen")
+        .join("sessions")
+        .join(format!("{}.blocks.json", session_id))
+}
+
+/// Get the path of the working directory history for a session
+pub fn cwd_history_path(project_path: &Path, session_id: &str) -> PathBuf {
+    project_path
+        .join(".lumen")
+        .join("sessions")
+        .join(format!("{}.cwd.json", session_id))
+}
+
+/// Load the saved working directory history of the project's active session, oldest first
+pub fn load_cwd_history(project_path: &str) -> Vec<String> {
+    session_events::active_session_for_path(project_path)
+        .and_then(|session_id| {
+            fs::read_to_string(cwd_history_path(Path::new(project_path), &session_id)).ok()
+        })
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Records PTY output, with timestamps, and the semantic blocks parsed from it
+/// into the active session's recordings
+pub struct SessionRecorder {
+    project_path: Option<String>,
+    session_id: Option<String>,
+    file: Option<File>,
+    last_lookup: Option<Instant>,
+    /// Semantic blocks not yet merged into the active session's block log
+    pending_blocks: Vec<SemanticBlock>,
+    last_blocks_write: Option<Instant>,
+}
+
+impl SessionRecorder {
+    pub fn new(project_path: Option<String>) -> Self {
+        Self {
+            project_path,
+            session_id: None,
+            file: None,
+            last_lookup: None,
+            pending_blocks: Vec::new(),
+            last_blocks_write: None,
+        }
+    }
+
+    /// Add a semantic block to the active session's block log
+    pub fn record_block(&mut self, block: &SemanticBlock) {
+        self.refresh_session();
+
+        if self.session_id.is_none() {
+            return;
+        }
+
+        self.pending_blocks.push(block.clone());
+
+        if self
+            .last_blocks_write
+            .map(|t| t.elapsed() >= BLOCKS_WRITE_INTERVAL)
+            .unwrap_or(true)
+        {
+            self.flush_blocks();
+        }
+    }
+
+    /// Merge pending semantic blocks into the session's block log on disk
+    pub fn flush_blocks(&mut self) {
+        if self.pending_blocks.is_empty() {
+            return;
+        }
+        let (Some(project_path), Some(session_id)) =
+            (self.project_path.as_deref(), self.session_id.as_deref())
+        else {
+            return;
+        };
+
+        let _guard = BLOCKS_WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let path = blocks_path(Path::new(project_path), session_id);
+        let mut blocks: Vec<SemanticBlock> = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        blocks.append(&mut self.pending_blocks);
+        blocks.sort_by_key(|block| block.timestamp);
+
+        if let Ok(content) = serde_json::to_string(&blocks) {
+            if let Err(e) = fs::write(&path, content) {
+                eprintln!("Failed to write semantic blocks: {}", e);
+            }
+        }
+        self.last_blocks_write = Some(Instant::now());
+    }
+
+    /// Save the PTY's working directory history (oldest first) to the active session
+    pub fn record_cwd_history(&mut self, history: &[String]) {
+        self.refresh_session();
+
+        let (Some(project_path), Some(session_id)) =
+            (self.project_path.as_deref(), self.session_id.as_deref())
+        else {
+            return;
+        };
+
+        let path = cwd_history_path(Path::new(project_path), session_id);
+        if let Ok(content) = serde_json::to_string(history) {
+            if let Err(e) = fs::write(&path, content) {
+                eprintln!("Failed to write working directory history: {}", e);
+            }
+        }
+    }
+
+    /// Append an output chunk if a session is active
+    pub fn record(&mut self, data: &str) {
+        self.refresh_session();
+
+        if let Some(file) = self.file.as_mut() {
+            let chunk = RecordedChunk {
+                t: chrono::Utc::now().timestamp_millis() as u64,
+                data: data.to_string(),
+            };
+            if let Ok(line) = serde_json::to_string(&chunk) {
+                let _ = writeln!(file, "{}", line);
+            }
+        }
+    }
+
+    /// Look up the active session (rate limited) and open its recording if it changed
+    fn refresh_session(&mut self) {
+        let Some(project_path) = self.project_path.as_deref() else {
+            return;
+        };
+
+        if self
+            .last_lookup
+            .map(|t| t.elapsed() < SESSION_LOOKUP_INTERVAL)
+            .unwrap_or(false)
+        {
+            return;
+        }
+        self.last_lookup = Some(Instant::now());
+
+        let active = session_events::active_session_for_path(project_path);
+        if active == self.session_id {
+            return;
+        }
+
+        // Finish the previous session's block log before switching
+        self.flush_blocks();
+        let project_path = self.project_path.as_deref().unwrap_or_default();
+
+        self.file = active.as_deref().and_then(|session_id| {
+            let path = recording_path(Path::new(project_path), session_id);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).ok()?;
+            }
+            OpenOptions::new().create(true).append(true).open(path).ok()
+        });
+        self.session_id = active;
+    }
+}
+
+impl Drop for SessionRecorder {
+    fn drop(&mut self) {
+        self.flush_blocks();
+    }
+}
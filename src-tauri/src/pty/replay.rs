@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use super::recorder::RecordedChunk;
+
+/// Granularity for checking pause/stop while waiting between chunks
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Playback controls shared with a replay thread
+#[derive(Default)]
+struct ReplayControl {
+    paused: AtomicBool,
+    stopped: AtomicBool,
+}
+
+lazy_static::lazy_static! {
+    static ref REPLAYS: Mutex<HashMap<String, Arc<ReplayControl>>> = Mutex::new(HashMap::new());
+}
+
+/// Start replaying a recording. Output is emitted on `replay-output-{id}`,
+/// and `replay-exit-{id}` fires when playback finishes or is stopped.
+pub fn start_replay(app_handle: AppHandle, recording: &Path, speed: f64) -> Result<String, String> {
+    if speed <= 0.0 || !speed.is_finite() {
+        return Err("Replay speed must be greater than zero".to_string());
+    }
+
+    let content = fs::read_to_string(recording)
+        .map_err(|e| format!("Failed to read session recording: {}", e))?;
+
+    let chunks: Vec<RecordedChunk> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+
+    let replay_id = uuid::Uuid::new_v4().to_string();
+    let control = Arc::new(ReplayControl::default());
+
+    REPLAYS
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(replay_id.clone(), control.clone());
+
+    let id = replay_id.clone();
+    thread::spawn(move || {
+        let mut previous_t: Option<u64> = None;
+
+        'chunks: for chunk in chunks {
+            // Wait for the (scaled) gap since the previous chunk
+            let gap_ms = previous_t.map(|p| chunk.t.saturating_sub(p)).unwrap_or(0);
+            let mut remaining = Duration::from_millis((gap_ms as f64 / speed) as u64);
+            previous_t = Some(chunk.t);
+
+            loop {
+                if control.stopped.load(Ordering::Relaxed) {
+                    break 'chunks;
+                }
+                if control.paused.load(Ordering::Relaxed) {
+                    thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+                if remaining.is_zero() {
+                    break;
+                }
+                let step = remaining.min(POLL_INTERVAL);
+                thread::sleep(step);
+                remaining -= step;
+            }
+
+            let _ = app_handle.emit(&format!("replay-output-{}", id), chunk.data);
+        }
+
+        if let Ok(mut replays) = REPLAYS.lock() {
+            replays.remove(&id);
+        }
+        let _ = app_handle.emit(&format!("replay-exit-{}", id), ());
+    });
+
+    Ok(replay_id)
+}
+
+fn get_control(replay_id: &str) -> Result<Arc<ReplayControl>, String> {
+    REPLAYS
+        .lock()
+        .map_err(|e| e.to_string())?
+        .get(replay_id)
+        .cloned()
+        .ok_or_else(|| "Replay not found".to_string())
+}
+
+/// Pause or resume a running replay
+pub fn set_paused(replay_id: &str, paused: bool) -> Result<(), String> {
+    get_control(replay_id)?.paused.store(paused, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Stop a running replay
+pub fn stop(replay_id: &str) -> Result<(), String> {
+    get_control(replay_id)?.stopped.store(true, Ordering::Relaxed);
+    Ok(())
+}
@@ -28,6 +28,64 @@ pub enum BlockType {
     CommandOutput { exit_code: Option<i32> },
 }
 
+impl BlockType {
+    /// Every name `kind` can return
+    pub const KINDS: [&'static str; 11] = [
+        "thinking",
+        "code",
+        "tool",
+        "tool_output",
+        "question",
+        "error",
+        "text",
+        "file_content",
+        "diff",
+        "command",
+        "command_output",
+    ];
+
+    /// Stable name for the block type (matches its serialized tag)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BlockType::Thinking => "thinking",
+            BlockType::Code { .. } => "code",
+            BlockType::Tool { .. } => "tool",
+            BlockType::ToolOutput { .. } => "tool_output",
+            BlockType::Question => "question",
+            BlockType::Error => "error",
+            BlockType::Text => "text",
+            BlockType::FileContent { .. } => "file_content",
+            BlockType::Diff { .. } => "diff",
+            BlockType::Command { .. } => "command",
+            BlockType::CommandOutput { .. } => "command_output",
+        }
+    }
+}
+
+/// User preferences for which blocks start out collapsed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticBlockPrefs {
+    /// Block kinds (see `BlockType::kind`) that may be collapsed
+    pub collapse_types: Vec<String>,
+    /// Collapse only when the block has more than this many lines
+    pub line_threshold: usize,
+}
+
+impl Default for SemanticBlockPrefs {
+    fn default() -> Self {
+        Self {
+            collapse_types: vec![
+                "code".to_string(),
+                "tool_output".to_string(),
+                "command_output".to_string(),
+                "file_content".to_string(),
+            ],
+            line_threshold: 10,
+        }
+    }
+}
+
 /// A semantic block parsed from Claude's output
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticBlock {
@@ -65,11 +123,13 @@ struct PartialBlock {
 /// Parses terminal output into semantic blocks
 pub struct SemanticBlockParser {
     state: ParserState,
+    prefs: SemanticBlockPrefs,
 }
 
 impl SemanticBlockParser {
-    pub fn new() -> Self {
+    pub fn new(prefs: SemanticBlockPrefs) -> Self {
         Self {
+            prefs,
             state: ParserState {
                 current_block: None,
                 block_counter: 0,
@@ -273,14 +333,11 @@ impl SemanticBlockParser {
     }
 
     fn finish_current_block(&mut self) -> Option<SemanticBlock> {
+        let prefs = &self.prefs;
         self.state.current_block.take().map(|partial| {
-            let collapsed_default = matches!(
-                partial.block_type,
-                BlockType::Code { .. }
-                    | BlockType::ToolOutput { .. }
-                    | BlockType::CommandOutput { .. }
-                    | BlockType::FileContent { .. }
-            ) && partial.content.lines().count() > 10;
+            let kind = partial.block_type.kind();
+            let collapsed_default = prefs.collapse_types.iter().any(|t| t == kind)
+                && partial.content.lines().count() > prefs.line_threshold;
 
             SemanticBlock {
                 id: partial.id,
@@ -308,7 +365,7 @@ impl SemanticBlockParser {
 }
 
 /// Strip ANSI escape codes from string
-fn strip_ansi_codes(s: &str) -> String {
+pub(crate) fn strip_ansi_codes(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
     let mut chars = s.chars().peekable();
 
@@ -337,9 +394,28 @@ fn strip_ansi_codes(s: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_block_kinds_listed() {
+        let blocks = [
+            BlockType::Thinking,
+            BlockType::Code { language: None },
+            BlockType::Tool { name: "Read".to_string() },
+            BlockType::ToolOutput { name: "Read".to_string(), success: true },
+            BlockType::Question,
+            BlockType::Error,
+            BlockType::Text,
+            BlockType::FileContent { path: "a.rs".to_string() },
+            BlockType::Diff { path: None },
+            BlockType::Command { cmd: "ls".to_string() },
+            BlockType::CommandOutput { exit_code: Some(0) },
+        ];
+        let kinds: Vec<&str> = blocks.iter().map(BlockType::kind).collect();
+        assert_eq!(kinds, BlockType::KINDS);
+    }
+
     #[test]
     fn test_parse_code_block() {
-        let mut parser = SemanticBlockParser::new();
+        let mut parser = SemanticBlockParser::new(SemanticBlockPrefs::default());
         let output = "```typescript\nconst x = 1;\n```\n";
         let blocks = parser.parse(output);
         // Code block should be detected
@@ -348,15 +424,32 @@ mod tests {
 
     #[test]
     fn test_detect_question() {
-        let parser = SemanticBlockParser::new();
+        let parser = SemanticBlockParser::new(SemanticBlockPrefs::default());
         let block_type = parser.detect_block_type("Would you like me to continue?");
         assert!(matches!(block_type, Some(BlockType::Question)));
     }
 
     #[test]
     fn test_detect_tool() {
-        let parser = SemanticBlockParser::new();
+        let parser = SemanticBlockParser::new(SemanticBlockPrefs::default());
         let block_type = parser.detect_block_type("Read(/path/to/file)");
         assert!(matches!(block_type, Some(BlockType::Tool { name }) if name == "Read"));
     }
+
+    #[test]
+    fn test_collapse_prefs() {
+        let prefs = SemanticBlockPrefs {
+            collapse_types: vec!["text".to_string()],
+            line_threshold: 1,
+        };
+        let mut parser = SemanticBlockParser::new(prefs);
+        parser.parse("first line of text\nsecond line of text\n");
+        let block = parser.flush().unwrap();
+        assert!(block.collapsed_default);
+
+        let mut parser = SemanticBlockParser::new(SemanticBlockPrefs::default());
+        parser.parse("first line of text\nsecond line of text\n");
+        let block = parser.flush().unwrap();
+        assert!(!block.collapsed_default);
+    }
 }
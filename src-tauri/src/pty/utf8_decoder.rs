@@ -0,0 +1,100 @@
+/// Decodes PTY output read in fixed-size chunks. A multi-byte character split
+/// across two reads is held back until the rest of it arrives, instead of being
+/// decoded as replacement characters on both sides of the boundary.
+pub struct Utf8Decoder {
+    /// Bytes of an incomplete character carried over from the previous chunk
+    pending: Vec<u8>,
+}
+
+impl Utf8Decoder {
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::with_capacity(4),
+        }
+    }
+
+    /// Decode a chunk, returning the complete characters read so far.
+    /// Invalid sequences become U+FFFD, as with `String::from_utf8_lossy`.
+    pub fn decode(&mut self, bytes: &[u8]) -> String {
+        let mut input = std::mem::take(&mut self.pending);
+        input.extend_from_slice(bytes);
+
+        let mut output = String::with_capacity(input.len());
+        let mut rest = input.as_slice();
+
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    output.push_str(valid);
+                    break;
+                }
+                Err(e) => {
+                    let (valid, after) = rest.split_at(e.valid_up_to());
+                    output.push_str(std::str::from_utf8(valid).unwrap_or_default());
+
+                    match e.error_len() {
+                        Some(len) => {
+                            output.push(char::REPLACEMENT_CHARACTER);
+                            rest = &after[len..];
+                        }
+                        None => {
+                            // Incomplete character at the end; wait for the rest of it
+                            self.pending.extend_from_slice(after);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Decode whatever is left once the stream has ended
+    pub fn finish(&mut self) -> String {
+        let rest = std::mem::take(&mut self.pending);
+        String::from_utf8_lossy(&rest).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_multibyte_sequence() {
+        let text = "héllo ✓ 日本";
+        let bytes = text.as_bytes();
+        // Split inside the three-byte ✓
+        let split = text.find('✓').unwrap() + 1;
+
+        let mut decoder = Utf8Decoder::new();
+        let first = decoder.decode(&bytes[..split]);
+        let second = decoder.decode(&bytes[split..]);
+
+        assert_eq!(first, "héllo ");
+        assert_eq!(format!("{}{}", first, second), text);
+        assert_eq!(decoder.finish(), "");
+    }
+
+    #[test]
+    fn test_one_byte_at_a_time() {
+        let text = "→ 🦀 done";
+        let mut decoder = Utf8Decoder::new();
+        let decoded: String = text.as_bytes().iter().map(|b| decoder.decode(&[*b])).collect();
+        assert_eq!(decoded, text);
+    }
+
+    #[test]
+    fn test_invalid_bytes_are_replaced() {
+        let mut decoder = Utf8Decoder::new();
+        assert_eq!(decoder.decode(b"a\xffb"), "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn test_incomplete_sequence_at_end_of_stream() {
+        let mut decoder = Utf8Decoder::new();
+        assert_eq!(decoder.decode(&"✓".as_bytes()[..2]), "");
+        assert_eq!(decoder.finish(), "\u{FFFD}");
+    }
+}
@@ -1,6 +1,6 @@
 import { cn } from "@/lib/utils";
 import { useClaudeState, ClaudeStateDisplay } from "@/hooks/useClaudeState";
-import { Brain, Pencil, Wrench, HelpCircle, AlertCircle, CheckCircle, Circle } from "lucide-react";
+import { Brain, Pencil, Wrench, HelpCircle, AlertCircle, CheckCircle, Circle, ListChecks, ShieldAlert } from "lucide-react";
 
 interface ClaudeStateIndicatorProps {
   terminalId: string;
@@ -20,6 +20,10 @@ const StateIcon = ({ state, className }: { state: ClaudeStateDisplay["state"]; c
       return <Wrench className={cn(iconClass, "animate-spin-slow")} />;
     case "asking":
       return <HelpCircle className={cn(iconClass, "animate-bounce-subtle")} />;
+    case "planning":
+      return <ListChecks className={iconClass} />;
+    case "awaiting_approval":
+      return <ShieldAlert className={cn(iconClass, "animate-bounce-subtle")} />;
     case "error":
       return <AlertCircle className={iconClass} />;
     case "complete":
@@ -47,6 +51,7 @@ export function ClaudeStateIndicator({
           stateDisplay.state === "thinking" && "shadow-[0_0_10px_rgba(168,85,247,0.3)]",
           stateDisplay.state === "tool_use" && "shadow-[0_0_10px_rgba(234,179,8,0.3)]",
           stateDisplay.state === "asking" && "shadow-[0_0_10px_rgba(249,115,22,0.4)]",
+          stateDisplay.state === "awaiting_approval" && "shadow-[0_0_10px_rgba(249,115,22,0.4)]",
           stateDisplay.state === "error" && "shadow-[0_0_10px_rgba(239,68,68,0.3)]",
           stateDisplay.state === "complete" && "shadow-[0_0_10px_rgba(34,197,94,0.3)]",
           className
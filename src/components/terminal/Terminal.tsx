@@ -131,9 +131,19 @@ export function Terminal({ id = "main", onReady }: TerminalProps) {
       });
 
       // Listen for PTY exit
-      const unlistenExit = await listen(`pty-exit-${id}`, () => {
-        xterm.write("\r\n\x1b[33m[Process exited]\x1b[0m\r\n");
-      });
+      const unlistenExit = await listen<{ code: number | null; signal: number | null }>(
+        `pty-exit-${id}`,
+        (event) => {
+          const { code, signal } = event.payload ?? { code: null, signal: null };
+          const detail =
+            signal !== null
+              ? ` (signal ${signal})`
+              : code !== null && code !== 0
+                ? ` (code ${code})`
+                : "";
+          xterm.write(`\r\n\x1b[33m[Process exited${detail}]\x1b[0m\r\n`);
+        }
+      );
 
       // Send input to PTY and track commands
       xterm.onData((data) => {
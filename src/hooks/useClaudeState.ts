@@ -10,9 +10,19 @@ export type ClaudeState =
   | "writing"
   | "tool_use"
   | "asking"
+  | "planning"
+  | "awaiting_approval"
   | "error"
   | "complete";
 
+/**
+ * A choice offered by an approval prompt; `key` is what to write to the terminal
+ */
+export interface ApprovalOption {
+  key: string;
+  label: string;
+}
+
 /**
  * State information emitted from the Rust parser
  */
@@ -22,6 +32,9 @@ export interface ClaudeStateInfo {
   question?: string;
   progress?: number;
   awaiting_input: boolean;
+  plan_approval?: boolean;
+  approval_options?: ApprovalOption[];
+  context_remaining?: number | null;
 }
 
 /**
@@ -36,6 +49,10 @@ export interface ClaudeStateDisplay {
   toolName?: string;
   question?: string;
   awaitingInput: boolean;
+  planApproval: boolean;
+  approvalOptions: ApprovalOption[];
+  /** Percentage of the context window left before auto-compaction, if known */
+  contextRemaining?: number;
 }
 
 const STATE_CONFIG: Record<ClaudeState, { label: string; color: string; bgColor: string; icon: string }> = {
@@ -69,6 +86,18 @@ const STATE_CONFIG: Record<ClaudeState, { label: string; color: string; bgColor:
     bgColor: "bg-[#f97316]/15",
     icon: "?",
   },
+  planning: {
+    label: "Planning",
+    color: "text-[#a855f7]",
+    bgColor: "bg-[#a855f7]/15",
+    icon: "☰",
+  },
+  awaiting_approval: {
+    label: "Needs approval",
+    color: "text-[#f97316]",
+    bgColor: "bg-[#f97316]/15",
+    icon: "!",
+  },
   error: {
     label: "Error",
     color: "text-[#ef4444]",
@@ -109,15 +138,20 @@ export function useClaudeState(terminalId: string): ClaudeStateDisplay {
 
   return {
     state: normalizedState,
-    label: stateInfo.tool_name
-      ? `${config.label}: ${stateInfo.tool_name}`
-      : config.label,
+    label: stateInfo.plan_approval
+      ? "Plan ready — approve?"
+      : stateInfo.tool_name
+        ? `${config.label}: ${stateInfo.tool_name}`
+        : config.label,
     color: config.color,
     bgColor: config.bgColor,
     icon: config.icon,
     toolName: stateInfo.tool_name,
     question: stateInfo.question,
     awaitingInput: stateInfo.awaiting_input,
+    planApproval: stateInfo.plan_approval ?? false,
+    approvalOptions: stateInfo.approval_options ?? [],
+    contextRemaining: stateInfo.context_remaining ?? undefined,
   };
 }
 
@@ -8,6 +8,7 @@ export interface Snapshot {
   timestamp: string;
   filesChanged: number;
   snapshotType: "auto" | "manual";
+  tags: string[];
 }
 
 export interface Project {